@@ -1,4 +1,4 @@
-//! Database tools example - demonstrates all 7 database tools
+//! Database tools example - demonstrates the database tools
 //!
 //! This example shows how to:
 //! - Connect to a database server via connection pool
@@ -7,6 +7,8 @@
 //! - Execute SQL queries
 //! - Monitor connection pool health
 //! - Query stored procedures (PostgreSQL/MySQL only)
+//! - Inspect statements currently running on the server
+//! - Generate CREATE TABLE DDL for existing tables
 //!
 //! # Prerequisites
 //!
@@ -130,7 +132,7 @@ async fn run_database_example(client: &common::LoggingClient) -> Result<()> {
     info!("\n{:=<70}", "");
     info!(" DATABASE TOOLS EXAMPLE");
     info!("{:=<70}\n", "");
-    info!("This example demonstrates all 7 database tools:");
+    info!("This example demonstrates these database tools:");
     info!("  1. list_schemas - Discover available databases/schemas");
     info!("  2. list_tables - List tables in a schema");
     info!("  3. get_table_schema - Inspect table columns");
@@ -139,6 +141,8 @@ async fn run_database_example(client: &common::LoggingClient) -> Result<()> {
     info!("  6. execute_sql (JOIN) - Complex multi-table queries");
     info!("  7. get_pool_stats - Monitor connection health");
     info!("  8. get_stored_procedures - List functions/procedures");
+    info!("  9. list_active_queries - Show statements running on the server");
+    info!(" 10. get_table_ddl - Generate CREATE TABLE statement");
     info!("");
 
     test_database_tools(client).await?;
@@ -146,7 +150,7 @@ async fn run_database_example(client: &common::LoggingClient) -> Result<()> {
     info!("\n{:=<70}", "");
     info!(" ALL TESTS COMPLETE");
     info!("{:=<70}", "");
-    info!("✅ Successfully demonstrated all 7 database tools");
+    info!("✅ Successfully demonstrated the database tools");
     Ok(())
 }
 
@@ -156,7 +160,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("{:=<70}", "");
 
     // Tool 1: LIST_SCHEMAS
-    info!("\n[1/8] Testing list_schemas...");
+    info!("\n[1] Testing list_schemas...");
     client
         .call_tool(DB_LIST_SCHEMAS, json!({}))
         .await
@@ -164,7 +168,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ list_schemas completed");
 
     // Tool 2: LIST_TABLES
-    info!("\n[2/8] Testing list_tables...");
+    info!("\n[2] Testing list_tables...");
     client
         .call_tool(DB_LIST_TABLES, json!({}))
         .await
@@ -172,7 +176,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ list_tables completed");
 
     // Tool 3: GET_TABLE_SCHEMA
-    info!("\n[3/8] Testing get_table_schema on 'employees' table...");
+    info!("\n[3] Testing get_table_schema on 'employees' table...");
     client
         .call_tool(DB_TABLE_SCHEMA, json!({ "table": "employees" }))
         .await
@@ -180,7 +184,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ get_table_schema completed");
 
     // Tool 4: GET_TABLE_INDEXES
-    info!("\n[4/8] Testing get_table_indexes on 'employees' table...");
+    info!("\n[4] Testing get_table_indexes on 'employees' table...");
     client
         .call_tool(DB_TABLE_INDEXES, json!({ "table": "employees" }))
         .await
@@ -188,7 +192,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ get_table_indexes completed");
 
     // Tool 5: EXECUTE_SQL (SELECT)
-    info!("\n[5/8] Testing execute_sql with SELECT...");
+    info!("\n[5] Testing execute_sql with SELECT...");
     client.call_tool(
         DB_EXECUTE_SQL,
         json!({
@@ -200,7 +204,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ execute_sql (SELECT) completed");
 
     // Tool 6: EXECUTE_SQL (JOIN)
-    info!("\n[6/8] Testing execute_sql with JOIN...");
+    info!("\n[6] Testing execute_sql with JOIN...");
     client.call_tool(
         DB_EXECUTE_SQL,
         json!({
@@ -215,7 +219,7 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ execute_sql (JOIN) completed");
 
     // Tool 7: GET_POOL_STATS
-    info!("\n[7/8] Testing get_pool_stats...");
+    info!("\n[7] Testing get_pool_stats...");
     client
         .call_tool(DB_POOL_STATS, json!({}))
         .await
@@ -223,12 +227,37 @@ async fn test_database_tools(client: &common::LoggingClient) -> Result<()> {
     info!("✅ get_pool_stats completed");
 
     // Tool 8: GET_STORED_PROCEDURES
-    info!("\n[8/8] Testing get_stored_procedures...");
+    info!("\n[8] Testing get_stored_procedures...");
     client
         .call_tool(DB_STORED_PROCEDURES, json!({}))
         .await
         .context("get_stored_procedures failed")?;
     info!("✅ get_stored_procedures completed");
 
+    // Tool 9: LIST_ACTIVE_QUERIES
+    info!("\n[9] Testing list_active_queries...");
+    let result = client
+        .call_tool(DB_LIST_ACTIVE_QUERIES, json!({}))
+        .await
+        .context("list_active_queries failed")?;
+    // The tool's own connection must show up in the process list
+    if let Some(output) = result.structured_content {
+        let output: ListActiveQueriesOutput =
+            serde_json::from_value(output).context("Failed to parse list_active_queries output")?;
+        anyhow::ensure!(
+            output.queries.iter().any(|q| q.is_current_connection),
+            "list_active_queries did not report its own connection"
+        );
+    }
+    info!("✅ list_active_queries completed");
+
+    // Tool 10: GET_TABLE_DDL
+    info!("\n[10] Testing get_table_ddl on 'employees' table...");
+    client
+        .call_tool(DB_TABLE_DDL, json!({ "table": "employees" }))
+        .await
+        .context("get_table_ddl failed")?;
+    info!("✅ get_table_ddl completed");
+
     Ok(())
 }
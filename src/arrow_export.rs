@@ -0,0 +1,374 @@
+//! Apache Arrow export of query results (`arrow` feature)
+//!
+//! [`query_to_arrow`] runs a query and returns its rows as one columnar
+//! [`RecordBatch`]. Values are decoded with the same type mapping as
+//! `db_execute_sql` (see `row_to_typed`), then laid out per column: integers
+//! become `Int64`, floats `Float64`, booleans `Boolean`, NUMERIC/DECIMAL
+//! `Decimal128`, binary `Binary`, and text, JSON, UUID and date/time types
+//! `Utf8`. SQL NULLs are entries in each array's null bitmap.
+
+use crate::error::DatabaseError;
+use crate::tools::execute_sql::row_to_typed;
+use crate::types::DatabaseType;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Float64Array, Int64Array, NullArray,
+    RecordBatch, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use kodegen_mcp_schema::database::SqlValue;
+use sqlx::{AnyPool, Column, Row, TypeInfo};
+use std::sync::Arc;
+
+/// Largest precision and scale a `Decimal128` holds
+const MAX_DECIMAL_PRECISION: u8 = 38;
+
+/// Arrow layout chosen for a column from its SQL type name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Int64,
+    Float64,
+    Boolean,
+    Decimal,
+    Binary,
+    Utf8,
+    /// No declared type (SQLite expressions): decided by the values
+    Untyped,
+}
+
+/// Mirrors the type groups of `row_to_typed`
+fn column_kind(type_name: &str) -> ColumnKind {
+    match type_name {
+        "INTEGER" | "INT" | "INT2" | "INT4" | "INT8" | "BIGINT" | "SMALLINT" | "MEDIUMINT"
+        | "SERIAL" | "BIGSERIAL" => ColumnKind::Int64,
+        "BOOLEAN" | "BOOL" | "TINYINT(1)" => ColumnKind::Boolean,
+        "REAL" | "FLOAT" | "FLOAT4" | "FLOAT8" | "DOUBLE" | "DOUBLE PRECISION" => {
+            ColumnKind::Float64
+        }
+        "NUMERIC" | "DECIMAL" | "NUMBER" => ColumnKind::Decimal,
+        "BYTEA" | "BLOB" | "BINARY" | "VARBINARY" => ColumnKind::Binary,
+        "NULL" => ColumnKind::Untyped,
+        _ => ColumnKind::Utf8,
+    }
+}
+
+/// Run `sql` and return its result set as an Arrow [`RecordBatch`]
+///
+/// All columns are nullable. A query that returns no rows yields an empty
+/// batch without columns, since column types are only known from a row.
+/// Decimal columns get precision 38 and the largest scale among their values.
+///
+/// # Example
+/// ```rust,no_run
+/// # use kodegen_tools_database::arrow_export::query_to_arrow;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// # async fn example(pool: &sqlx::AnyPool) -> Result<(), Box<dyn std::error::Error>> {
+/// let batch = query_to_arrow(pool, "SELECT id, name FROM employees", DatabaseType::Postgres).await?;
+/// println!("{} rows, schema {:?}", batch.num_rows(), batch.schema());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns error if the query fails, a column has a type `db_execute_sql`
+/// cannot decode either, a column mixes incompatible values (possible with
+/// SQLite's dynamic typing), or a decimal does not fit in `Decimal128`
+pub async fn query_to_arrow(
+    pool: &AnyPool,
+    sql: &str,
+    db_type: DatabaseType,
+) -> Result<RecordBatch, DatabaseError> {
+    if db_type == DatabaseType::SqlServer {
+        return Err(DatabaseError::FeatureNotSupported(
+            "Arrow export is not supported for SQL Server".to_string(),
+        ));
+    }
+
+    let rows = sqlx::query(sql).fetch_all(pool).await?;
+    let Some(first) = rows.first() else {
+        return Ok(RecordBatch::new_empty(Arc::new(Schema::empty())));
+    };
+    let columns: Vec<(String, ColumnKind)> = first
+        .columns()
+        .iter()
+        .map(|c| (c.name().to_string(), column_kind(c.type_info().name())))
+        .collect();
+
+    let typed = rows.iter().map(row_to_typed).collect::<Result<Vec<_>, _>>()?;
+
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::with_capacity(columns.len());
+    for (i, (name, kind)) in columns.iter().enumerate() {
+        let values: Vec<&SqlValue> = typed.iter().map(|row| &row.columns[i].value).collect();
+        let array = build_array(name, *kind, &values)?;
+        fields.push(Field::new(name, array.data_type().clone(), true));
+        arrays.push(array);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+        .map_err(|e| DatabaseError::QueryError(format!("Failed to build Arrow batch: {}", e)))
+}
+
+/// Build one column's array from its decoded values
+fn build_array(
+    name: &str,
+    kind: ColumnKind,
+    values: &[&SqlValue],
+) -> Result<ArrayRef, DatabaseError> {
+    let mismatch = |value: &SqlValue| {
+        DatabaseError::QueryError(format!(
+            "Column '{}' mixes value types ({:?} in a {:?} column); CAST it to one type",
+            name, value, kind
+        ))
+    };
+
+    let kind = match kind {
+        ColumnKind::Untyped => match values.iter().find(|v| !matches!(v, SqlValue::Null)) {
+            Some(SqlValue::Int(_)) => ColumnKind::Int64,
+            Some(SqlValue::Float(_)) => ColumnKind::Float64,
+            Some(SqlValue::Bool(_)) => ColumnKind::Boolean,
+            Some(SqlValue::Blob(_)) => ColumnKind::Binary,
+            Some(_) => ColumnKind::Utf8,
+            None => return Ok(Arc::new(NullArray::new(values.len()))),
+        },
+        kind => kind,
+    };
+
+    let array: ArrayRef = match kind {
+        ColumnKind::Int64 => Arc::new(Int64Array::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    SqlValue::Null => Ok(None),
+                    SqlValue::Int(i) => Ok(Some(*i)),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ColumnKind::Float64 => Arc::new(Float64Array::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    SqlValue::Null => Ok(None),
+                    SqlValue::Float(f) => Ok(Some(*f)),
+                    // SQLite REAL columns may store whole numbers as integers
+                    SqlValue::Int(i) => Ok(Some(*i as f64)),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ColumnKind::Boolean => Arc::new(BooleanArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    SqlValue::Null => Ok(None),
+                    SqlValue::Bool(b) => Ok(Some(*b)),
+                    SqlValue::Int(i) => Ok(Some(*i != 0)),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        ColumnKind::Decimal => {
+            let texts = values
+                .iter()
+                .map(|v| match v {
+                    SqlValue::Null => Ok(None),
+                    SqlValue::Text(s) => Ok(Some(s.clone())),
+                    SqlValue::Float(f) => Ok(Some(f.to_string())),
+                    SqlValue::Int(i) => Ok(Some(i.to_string())),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(decimal_array(name, &texts)?)
+        }
+        ColumnKind::Binary => Arc::new(
+            values
+                .iter()
+                .map(|v| match v {
+                    SqlValue::Null => Ok(None),
+                    SqlValue::Blob(b) => Ok(Some(b.as_slice())),
+                    SqlValue::Text(s) => Ok(Some(s.as_bytes())),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<BinaryArray, _>>()?,
+        ),
+        ColumnKind::Utf8 | ColumnKind::Untyped => Arc::new(StringArray::from(
+            values
+                .iter()
+                .map(|v| match v {
+                    SqlValue::Null => Ok(None),
+                    SqlValue::Text(s) => Ok(Some(s.clone())),
+                    SqlValue::Int(i) => Ok(Some(i.to_string())),
+                    SqlValue::Float(f) => Ok(Some(f.to_string())),
+                    SqlValue::Bool(b) => Ok(Some(b.to_string())),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+    };
+    Ok(array)
+}
+
+/// Split a decimal literal (`-12.50`) into its unscaled value and scale
+fn parse_decimal(text: &str) -> Option<(i128, u32)> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let unscaled: i128 = format!("{}{}", whole, fraction).parse().ok()?;
+    Some((if negative { -unscaled } else { unscaled }, fraction.len() as u32))
+}
+
+/// Decimal128 array at the largest scale among `texts`
+fn decimal_array(name: &str, texts: &[Option<String>]) -> Result<Decimal128Array, DatabaseError> {
+    let invalid = |detail: String| {
+        DatabaseError::QueryError(format!(
+            "Column '{}' cannot be represented as Decimal128: {}",
+            name, detail
+        ))
+    };
+
+    let parsed = texts
+        .iter()
+        .map(|text| match text {
+            Some(text) => parse_decimal(text)
+                .map(Some)
+                .ok_or_else(|| invalid(format!("'{}' is not a decimal number", text))),
+            None => Ok(None),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let scale = parsed.iter().flatten().map(|(_, s)| *s).max().unwrap_or(0);
+    if scale > MAX_DECIMAL_PRECISION as u32 {
+        return Err(invalid(format!("scale {} exceeds {}", scale, MAX_DECIMAL_PRECISION)));
+    }
+
+    let limit = 10_i128.pow(MAX_DECIMAL_PRECISION as u32);
+    let values = parsed
+        .into_iter()
+        .map(|value| {
+            let Some((unscaled, value_scale)) = value else {
+                return Ok(None);
+            };
+            unscaled
+                .checked_mul(10_i128.pow(scale - value_scale))
+                .filter(|v| v.abs() < limit)
+                .map(Some)
+                .ok_or_else(|| {
+                    invalid(format!("value exceeds {} digits", MAX_DECIMAL_PRECISION))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Decimal128Array::from(values)
+        .with_precision_and_scale(MAX_DECIMAL_PRECISION, scale as i8)
+        .map_err(|e| invalid(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+
+    #[test]
+    fn test_decimal_array_uses_widest_scale() {
+        let texts = vec![
+            Some("12.5".to_string()),
+            None,
+            Some("-3".to_string()),
+            Some("0.125".to_string()),
+        ];
+        let array = decimal_array("amount", &texts).expect("decimal array");
+        assert_eq!(array.data_type(), &DataType::Decimal128(38, 3));
+        assert_eq!(array.value(0), 12_500);
+        assert!(array.is_null(1));
+        assert_eq!(array.value(2), -3_000);
+        assert_eq!(array.value_as_string(3), "0.125");
+    }
+
+    #[test]
+    fn test_decimal_array_rejects_non_numbers() {
+        let texts = vec![Some("12.5".to_string()), Some("1e3".to_string())];
+        let err = decimal_array("amount", &texts).expect_err("exponent rejected");
+        assert!(err.to_string().contains("1e3"));
+        assert_eq!(parse_decimal("."), None);
+        assert_eq!(parse_decimal("+7.10"), Some((710, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_multi_type_query_to_arrow() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        sqlx::query(
+            "CREATE TABLE items (id INTEGER, name TEXT, price REAL, data BLOB, active BOOLEAN)",
+        )
+        .execute(&pool)
+        .await
+        .expect("create fixture");
+        sqlx::query(
+            "INSERT INTO items VALUES (1, 'pen', 1.5, x'0102', 1), (2, NULL, NULL, NULL, 0)",
+        )
+        .execute(&pool)
+        .await
+        .expect("insert fixture");
+
+        let batch = query_to_arrow(
+            &pool,
+            "SELECT id, name, price, data, active FROM items ORDER BY id",
+            DatabaseType::SQLite,
+        )
+        .await
+        .expect("arrow batch");
+
+        let schema = batch.schema();
+        let types: Vec<(&str, &DataType)> = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type()))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("id", &DataType::Int64),
+                ("name", &DataType::Utf8),
+                ("price", &DataType::Float64),
+                ("data", &DataType::Binary),
+                ("active", &DataType::Boolean),
+            ]
+        );
+        assert_eq!(batch.num_rows(), 2);
+
+        let column = |i: usize| batch.column(i).as_any();
+        let ids = column(0).downcast_ref::<Int64Array>().expect("ids");
+        assert_eq!((ids.value(0), ids.value(1)), (1, 2));
+        let names = column(1).downcast_ref::<StringArray>().expect("names");
+        assert_eq!(names.value(0), "pen");
+        assert!(names.is_null(1));
+        let prices = column(2).downcast_ref::<Float64Array>().expect("prices");
+        assert_eq!(prices.value(0), 1.5);
+        assert!(prices.is_null(1));
+        let data = column(3).downcast_ref::<BinaryArray>().expect("data");
+        assert_eq!(data.value(0), &[1, 2]);
+        assert!(data.is_null(1));
+        let active = column(4).downcast_ref::<BooleanArray>().expect("active");
+        assert!(active.value(0));
+        assert!(!active.value(1));
+
+        let empty =
+            query_to_arrow(&pool, "SELECT id FROM items WHERE id > 10", DatabaseType::SQLite)
+                .await
+                .expect("empty batch");
+        assert_eq!(empty.num_rows(), 0);
+    }
+}
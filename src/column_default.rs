@@ -0,0 +1,201 @@
+//! Classification of column default expressions
+//!
+//! Schema queries return the raw default expression (`nextval('seq'::regclass)`,
+//! `now()`, `'pending'::text`). [`classify_default`] lightly parses it into a
+//! [`DefaultKind`] so clients can show "auto-increment" or a clean literal.
+
+use crate::types::DatabaseType;
+use kodegen_mcp_schema::database::DefaultKind;
+use lazy_regex::{Lazy, Regex, lazy_regex};
+
+// PostgreSQL cast suffix: ::text, ::character varying, ::numeric(10,2), ::text[]
+static PG_CAST_REGEX: Lazy<Regex> =
+    lazy_regex!(r"^(.*)::[A-Za-z_][A-Za-z0-9_ ]*(?:\(\d+(?:\s*,\s*\d+)?\))?(?:\[\])?$");
+
+static NUMERIC_REGEX: Lazy<Regex> = lazy_regex!(r"^[+-]?(?:\d+\.?\d*|\.\d+)(?:[eE][+-]?\d+)?$");
+
+// CURRENT_TIMESTAMP, CURRENT_TIMESTAMP(6), now(), localtimestamp, datetime('now'), ...
+static CURRENT_TIMESTAMP_REGEX: Lazy<Regex> = lazy_regex!(
+    r"(?i)^(?:(?:current_timestamp|now|localtimestamp|transaction_timestamp|statement_timestamp|clock_timestamp|getdate|sysdatetime)(?:\s*\(\s*\d*\s*\))?|datetime\s*\(\s*'now'\s*\))$"
+);
+
+static FUNCTION_NAME_REGEX: Lazy<Regex> = lazy_regex!(r"^([A-Za-z_][A-Za-z0-9_.]*)\s*\(");
+
+/// Classify a raw column default expression
+///
+/// Literals are returned without quotes or casts (`'x'::text` → `x`). An
+/// expression that is not a single function call is returned verbatim as
+/// `FunctionCall`.
+///
+/// # Arguments
+/// * `raw` - Default expression as reported by the database, if any
+/// * `db_type` - Database type the expression came from
+///
+/// # Example
+/// ```rust
+/// use kodegen_tools_database::column_default::classify_default;
+/// use kodegen_tools_database::types::DatabaseType;
+/// use kodegen_mcp_schema::database::DefaultKind;
+///
+/// let pg = DatabaseType::Postgres;
+/// assert_eq!(
+///     classify_default(Some("nextval('users_id_seq'::regclass)"), pg),
+///     DefaultKind::SequenceNextval
+/// );
+/// assert_eq!(classify_default(Some("'pending'::text"), pg), DefaultKind::Literal("pending".into()));
+/// ```
+pub fn classify_default(raw: Option<&str>, db_type: DatabaseType) -> DefaultKind {
+    let Some(expr) = raw.map(str::trim).filter(|e| !e.is_empty()) else {
+        return DefaultKind::None;
+    };
+
+    let original = strip_wrapping_parens(expr);
+    let mut expr = original;
+    while let Some(captures) = PG_CAST_REGEX.captures(expr) {
+        let inner = captures.get(1).map_or("", |m| m.as_str()).trim();
+        if inner.is_empty() {
+            break;
+        }
+        expr = strip_wrapping_parens(inner);
+    }
+
+    if expr.eq_ignore_ascii_case("null") {
+        return DefaultKind::None;
+    }
+    if let Some(literal) = unquote(expr) {
+        return DefaultKind::Literal(literal);
+    }
+    if NUMERIC_REGEX.is_match(expr)
+        || expr.eq_ignore_ascii_case("true")
+        || expr.eq_ignore_ascii_case("false")
+    {
+        return DefaultKind::Literal(expr.to_string());
+    }
+    if CURRENT_TIMESTAMP_REGEX.is_match(expr) {
+        return DefaultKind::CurrentTimestamp;
+    }
+    if let Some(name) = function_call_name(expr) {
+        if name == "nextval" {
+            return DefaultKind::SequenceNextval;
+        }
+        return DefaultKind::FunctionCall(name);
+    }
+
+    // MySQL 8 reports string defaults without quotes
+    if matches!(db_type, DatabaseType::MySQL | DatabaseType::MariaDB) {
+        return DefaultKind::Literal(original.to_string());
+    }
+    DefaultKind::FunctionCall(original.to_string())
+}
+
+/// Remove parentheses that wrap the whole expression: `((0))` → `0`
+fn strip_wrapping_parens(mut expr: &str) -> &str {
+    while expr.starts_with('(') && closing_paren(expr, 0) == Some(expr.len() - 1) {
+        expr = expr[1..expr.len() - 1].trim();
+    }
+    expr
+}
+
+/// Byte index of the parenthesis closing the one at `open`, skipping quoted text
+fn closing_paren(expr: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_quote = false;
+    for (i, c) in expr.char_indices().skip_while(|(i, _)| *i < open) {
+        match c {
+            '\'' => in_quote = !in_quote,
+            '(' if !in_quote => depth += 1,
+            ')' if !in_quote => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Lowercased function name when `expr` is exactly one call: `name(...)`
+fn function_call_name(expr: &str) -> Option<String> {
+    let captures = FUNCTION_NAME_REGEX.captures(expr)?;
+    let open = captures.get(0)?.end() - 1;
+    (closing_paren(expr, open)? == expr.len() - 1).then(|| captures[1].to_lowercase())
+}
+
+/// Contents of a single-quoted SQL string, with `''` unescaped
+fn unquote(expr: &str) -> Option<String> {
+    let inner = expr.strip_prefix('\'')?.strip_suffix('\'')?;
+    // Reject 'a' || 'b' and similar: every inner quote must be doubled
+    if inner.replace("''", "").contains('\'') {
+        return None;
+    }
+    Some(inner.replace("''", "'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PG: DatabaseType = DatabaseType::Postgres;
+
+    #[test]
+    fn test_sequence_nextval() {
+        assert_eq!(classify_default(Some("nextval('s')"), PG), DefaultKind::SequenceNextval);
+        assert_eq!(
+            classify_default(Some("nextval('public.orders_id_seq'::regclass)"), PG),
+            DefaultKind::SequenceNextval
+        );
+    }
+
+    #[test]
+    fn test_current_timestamp() {
+        assert_eq!(classify_default(Some("now()"), PG), DefaultKind::CurrentTimestamp);
+        assert_eq!(classify_default(Some("CURRENT_TIMESTAMP"), PG), DefaultKind::CurrentTimestamp);
+        assert_eq!(
+            classify_default(Some("current_timestamp(6)"), DatabaseType::MariaDB),
+            DefaultKind::CurrentTimestamp
+        );
+        assert_eq!(
+            classify_default(Some("datetime('now')"), DatabaseType::SQLite),
+            DefaultKind::CurrentTimestamp
+        );
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(classify_default(Some("'x'::text"), PG), DefaultKind::Literal("x".into()));
+        assert_eq!(
+            classify_default(Some("'it''s'::character varying"), PG),
+            DefaultKind::Literal("it's".into())
+        );
+        assert_eq!(
+            classify_default(Some("(0)::numeric(10,2)"), PG),
+            DefaultKind::Literal("0".into())
+        );
+        assert_eq!(classify_default(Some("false"), PG), DefaultKind::Literal("false".into()));
+        assert_eq!(
+            classify_default(Some("pending"), DatabaseType::MySQL),
+            DefaultKind::Literal("pending".into())
+        );
+    }
+
+    #[test]
+    fn test_function_calls_and_expressions() {
+        assert_eq!(
+            classify_default(Some("gen_random_uuid()"), PG),
+            DefaultKind::FunctionCall("gen_random_uuid".into())
+        );
+        assert_eq!(
+            classify_default(Some("(now() + '1 day'::interval)"), PG),
+            DefaultKind::FunctionCall("now() + '1 day'::interval".into())
+        );
+    }
+
+    #[test]
+    fn test_no_default() {
+        assert_eq!(classify_default(None, PG), DefaultKind::None);
+        assert_eq!(classify_default(Some("NULL::character varying"), PG), DefaultKind::None);
+        assert_eq!(classify_default(Some("NULL"), DatabaseType::MariaDB), DefaultKind::None);
+    }
+}
@@ -0,0 +1,377 @@
+//! Detection of implicit cross joins (cartesian products)
+//!
+//! A comma-separated FROM list whose tables are never correlated, neither by a
+//! join constraint nor by a WHERE predicate, multiplies their row counts. That
+//! is almost always a forgotten join condition, so the query is flagged before
+//! it runs. Explicit `CROSS JOIN` is taken as intentional and never flagged.
+
+use crate::sql_parser::get_dialect;
+use crate::types::DatabaseType;
+use sqlparser::ast::{
+    BinaryOperator, Expr, FunctionArg, FunctionArgExpr, FunctionArguments, Query, Select, SetExpr,
+    Statement, TableFactor, TableWithJoins,
+};
+use sqlparser::parser::Parser;
+
+/// Describe each SELECT in `sql` whose FROM tables are not correlated
+///
+/// Returns one message per uncorrelated FROM list, e.g. `possible cartesian
+/// product between orders and customers`. The check is conservative: SQL that
+/// cannot be parsed, predicates it cannot attribute to tables (subqueries,
+/// unqualified columns compared with each other) and FROM items other than
+/// plain tables (derived tables, table functions) are assumed to correlate.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::cross_join::cartesian_products;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// let pg = DatabaseType::Postgres;
+/// assert_eq!(
+///     cartesian_products("SELECT * FROM orders o, customers c", pg),
+///     vec!["possible cartesian product between orders and customers".to_string()]
+/// );
+/// assert!(cartesian_products("SELECT * FROM orders o, customers c WHERE o.customer_id = c.id", pg).is_empty());
+/// assert!(cartesian_products("SELECT * FROM sizes CROSS JOIN colors", pg).is_empty());
+/// ```
+pub fn cartesian_products(sql: &str, db_type: DatabaseType) -> Vec<String> {
+    let dialect = get_dialect(db_type);
+    let Ok(statements) = Parser::parse_sql(&*dialect, sql) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    for statement in &statements {
+        if let Statement::Query(query) = statement {
+            check_query(query, &mut found);
+        }
+    }
+    found
+}
+
+fn check_query(query: &Query, found: &mut Vec<String>) {
+    if let Some(with) = &query.with {
+        for cte in &with.cte_tables {
+            check_query(&cte.query, found);
+        }
+    }
+    check_set_expr(&query.body, found);
+}
+
+fn check_set_expr(expr: &SetExpr, found: &mut Vec<String>) {
+    match expr {
+        SetExpr::Select(select) => check_select(select, found),
+        SetExpr::Query(query) => check_query(query, found),
+        SetExpr::SetOperation { left, right, .. } => {
+            check_set_expr(left, found);
+            check_set_expr(right, found);
+        }
+        _ => {}
+    }
+}
+
+fn check_select(select: &Select, found: &mut Vec<String>) {
+    for table in &select.from {
+        check_derived_tables(table, found);
+    }
+    if select.from.len() < 2 {
+        return;
+    }
+
+    let units: Vec<FromUnit> = select.from.iter().map(FromUnit::new).collect();
+    // Component label per FROM item; items sharing a label are correlated
+    let mut component: Vec<usize> = (0..units.len()).collect();
+    let mut merge = |indices: &[usize]| {
+        let Some(&first) = indices.first() else { return };
+        let target = component[first];
+        for &index in &indices[1..] {
+            let source = component[index];
+            for label in component.iter_mut() {
+                if *label == source {
+                    *label = target;
+                }
+            }
+        }
+    };
+
+    let all: Vec<usize> = (0..units.len()).collect();
+    if units.iter().any(|unit| unit.opaque) {
+        merge(&all);
+    }
+    if let Some(selection) = &select.selection {
+        let mut predicates = Vec::new();
+        conjuncts(selection, &mut predicates);
+        for predicate in predicates {
+            let mut refs = ColumnRefs::default();
+            refs.collect(predicate);
+            let total = refs.qualifiers.len() + refs.unqualified;
+            if refs.opaque || (refs.unqualified > 0 && total >= 2) {
+                merge(&all);
+                continue;
+            }
+            let indices: Vec<usize> = refs
+                .qualifiers
+                .iter()
+                .filter_map(|qualifier| units.iter().position(|unit| unit.names.contains(qualifier)))
+                .collect();
+            merge(&indices);
+        }
+    }
+
+    let mut labels: Vec<usize> = Vec::new();
+    let mut tables: Vec<&str> = Vec::new();
+    for (unit, label) in units.iter().zip(&component) {
+        if !labels.contains(label) {
+            labels.push(*label);
+            tables.push(&unit.display);
+        }
+    }
+    if let Some((last, rest)) = tables.split_last()
+        && !rest.is_empty()
+    {
+        found.push(format!(
+            "possible cartesian product between {} and {}",
+            rest.join(", "),
+            last
+        ));
+    }
+}
+
+/// Derived tables in FROM are queries of their own
+fn check_derived_tables(table: &TableWithJoins, found: &mut Vec<String>) {
+    let factors = std::iter::once(&table.relation).chain(table.joins.iter().map(|join| &join.relation));
+    for factor in factors {
+        match factor {
+            TableFactor::Derived { subquery, .. } => check_query(subquery, found),
+            TableFactor::NestedJoin {
+                table_with_joins, ..
+            } => check_derived_tables(table_with_joins, found),
+            _ => {}
+        }
+    }
+}
+
+/// One comma-separated FROM item together with its explicit joins
+///
+/// Explicit joins, including `CROSS JOIN`, are taken as written, so the whole
+/// item counts as one correlated unit.
+struct FromUnit {
+    /// Lowercased aliases or table names a column may be qualified with
+    names: Vec<String>,
+    /// Table name used in messages
+    display: String,
+    /// Contains something other than plain tables, which may reference other items
+    opaque: bool,
+}
+
+impl FromUnit {
+    fn new(table: &TableWithJoins) -> Self {
+        let mut unit = FromUnit {
+            names: Vec::new(),
+            display: String::new(),
+            opaque: false,
+        };
+        unit.add(&table.relation);
+        for join in &table.joins {
+            unit.add(&join.relation);
+        }
+        unit
+    }
+
+    fn add(&mut self, factor: &TableFactor) {
+        match factor {
+            TableFactor::Table {
+                name, alias, args, ..
+            } if args.is_none() => {
+                if self.display.is_empty() {
+                    self.display = name.to_string();
+                }
+                let qualifier = match alias {
+                    Some(alias) => Some(alias.name.value.to_lowercase()),
+                    None => name
+                        .0
+                        .last()
+                        .and_then(|part| part.as_ident())
+                        .map(|ident| ident.value.to_lowercase()),
+                };
+                self.names.extend(qualifier);
+            }
+            TableFactor::NestedJoin {
+                table_with_joins, ..
+            } => {
+                self.add(&table_with_joins.relation);
+                for join in &table_with_joins.joins {
+                    self.add(&join.relation);
+                }
+            }
+            other => {
+                if self.display.is_empty() {
+                    self.display = other.to_string();
+                }
+                self.opaque = true;
+            }
+        }
+    }
+}
+
+/// Split a predicate on its top-level ANDs
+fn conjuncts<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            conjuncts(left, out);
+            conjuncts(right, out);
+        }
+        Expr::Nested(inner) => conjuncts(inner, out),
+        other => out.push(other),
+    }
+}
+
+/// Column references found in one predicate
+#[derive(Default)]
+struct ColumnRefs {
+    /// Lowercased table qualifiers of qualified columns (`o` in `o.id`)
+    qualifiers: Vec<String>,
+    /// Number of unqualified column references
+    unqualified: usize,
+    /// The predicate contains something that may reference any table
+    opaque: bool,
+}
+
+impl ColumnRefs {
+    fn collect(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Identifier(..) => self.unqualified += 1,
+            Expr::CompoundIdentifier(idents) => match idents.len() {
+                0 | 1 => self.unqualified += 1,
+                len => self.qualifiers.push(idents[len - 2].value.to_lowercase()),
+            },
+            Expr::Value(..) | Expr::TypedString { .. } | Expr::Interval { .. } => {}
+            Expr::BinaryOp { left, right, .. } => {
+                self.collect(left);
+                self.collect(right);
+            }
+            Expr::UnaryOp { expr, .. }
+            | Expr::Nested(expr)
+            | Expr::Cast { expr, .. }
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsNotTrue(expr)
+            | Expr::IsFalse(expr)
+            | Expr::IsNotFalse(expr) => self.collect(expr),
+            Expr::IsDistinctFrom(left, right) | Expr::IsNotDistinctFrom(left, right) => {
+                self.collect(left);
+                self.collect(right);
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+                self.collect(expr);
+                self.collect(pattern);
+            }
+            Expr::InList { expr, list, .. } => {
+                self.collect(expr);
+                for item in list {
+                    self.collect(item);
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.collect(expr);
+                self.collect(low);
+                self.collect(high);
+            }
+            Expr::Tuple(exprs) => {
+                for expr in exprs {
+                    self.collect(expr);
+                }
+            }
+            Expr::Function(func) => match &func.args {
+                FunctionArguments::List(list) => {
+                    for arg in &list.args {
+                        match arg {
+                            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                            | FunctionArg::Named {
+                                arg: FunctionArgExpr::Expr(expr),
+                                ..
+                            }
+                            | FunctionArg::ExprNamed {
+                                arg: FunctionArgExpr::Expr(expr),
+                                ..
+                            } => self.collect(expr),
+                            _ => {}
+                        }
+                    }
+                }
+                FunctionArguments::None => {}
+                FunctionArguments::Subquery(_) => self.opaque = true,
+            },
+            // Subqueries and anything not modelled above may correlate tables
+            _ => self.opaque = true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PG: DatabaseType = DatabaseType::Postgres;
+
+    #[test]
+    fn test_implicit_cross_join_warns() {
+        assert_eq!(
+            cartesian_products("SELECT * FROM t1, t2", PG),
+            vec!["possible cartesian product between t1 and t2".to_string()]
+        );
+        // A filter on each table alone does not correlate them
+        assert_eq!(
+            cartesian_products(
+                "SELECT * FROM orders o, customers c WHERE o.total > 10 AND c.active",
+                DatabaseType::MySQL
+            ),
+            vec!["possible cartesian product between orders and customers".to_string()]
+        );
+        // Only the uncorrelated item is reported alongside the rest
+        assert_eq!(
+            cartesian_products(
+                "SELECT * FROM a, b, c WHERE a.id = b.a_id",
+                DatabaseType::SQLite
+            ),
+            vec!["possible cartesian product between a and c".to_string()]
+        );
+        // Also inside CTEs and derived tables
+        assert_eq!(
+            cartesian_products("WITH x AS (SELECT * FROM t1, t2) SELECT * FROM x", PG).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_explicit_cross_join_does_not_warn() {
+        assert!(cartesian_products("SELECT * FROM sizes CROSS JOIN colors", PG).is_empty());
+        assert!(
+            cartesian_products("SELECT * FROM sizes s CROSS JOIN colors c WHERE s.id > 1", PG)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_correlated_from_list_does_not_warn() {
+        for sql in [
+            "SELECT * FROM orders o, customers c WHERE o.customer_id = c.id",
+            "SELECT * FROM orders, customers WHERE orders.customer_id = customers.id AND orders.total > 5",
+            "SELECT * FROM a JOIN b ON a.id = b.a_id, c WHERE c.b_id = b.id",
+            // Unqualified columns compared with each other may belong to either table
+            "SELECT * FROM orders, customers WHERE customer_id = id",
+            "SELECT * FROM a, b WHERE EXISTS (SELECT 1 FROM c WHERE c.a = a.id AND c.b = b.id)",
+            "SELECT * FROM t, (SELECT max(x) AS m FROM t) totals",
+            "SELECT 1",
+            "not valid sql",
+        ] {
+            assert!(cartesian_products(sql, PG).is_empty(), "{}", sql);
+        }
+    }
+}
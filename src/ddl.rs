@@ -0,0 +1,592 @@
+//! CREATE TABLE DDL generation
+//!
+//! MySQL/MariaDB and SQLite keep the original DDL (`SHOW CREATE TABLE` and
+//! `sqlite_master.sql`). PostgreSQL has no built-in equivalent, so its DDL is
+//! reconstructed from pg_catalog: columns, defaults, constraints, and indexes.
+//!
+//! MySQL's `SHOW CREATE TABLE` and SQLite's `<schema>.sqlite_master` cannot be
+//! parameterized, so those identifiers are quoted in the dialect's own style
+//! (embedded quote characters doubled) rather than restricted to plain names.
+
+use crate::error::DatabaseError;
+use crate::types::DatabaseType;
+use sqlx::{AnyPool, Row};
+
+/// PostgreSQL column definitions (formatted types and default expressions)
+const PG_COLUMNS_QUERY: &str = "SELECT \
+        CAST(a.attname AS TEXT) as column_name, \
+        CAST(format_type(a.atttypid, a.atttypmod) AS TEXT) as data_type, \
+        a.attnotnull as not_null, \
+        CAST(pg_get_expr(d.adbin, d.adrelid) AS TEXT) as column_default, \
+        CAST(a.attidentity AS TEXT) as identity, \
+        CAST(a.attgenerated AS TEXT) as generated \
+    FROM pg_attribute a \
+    JOIN pg_class c ON c.oid = a.attrelid \
+    JOIN pg_namespace n ON n.oid = c.relnamespace \
+    LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+    WHERE n.nspname = $1 AND c.relname = $2 AND c.relkind IN ('r', 'p') \
+      AND a.attnum > 0 AND NOT a.attisdropped \
+    ORDER BY a.attnum";
+
+/// PostgreSQL table constraints (primary key, unique, check, foreign key, exclusion)
+const PG_CONSTRAINTS_QUERY: &str = "SELECT \
+        CAST(con.conname AS TEXT) as constraint_name, \
+        CAST(pg_get_constraintdef(con.oid) AS TEXT) as definition \
+    FROM pg_constraint con \
+    JOIN pg_class c ON c.oid = con.conrelid \
+    JOIN pg_namespace n ON n.oid = c.relnamespace \
+    WHERE n.nspname = $1 AND c.relname = $2 AND con.contype IN ('p', 'u', 'c', 'f', 'x') \
+    ORDER BY CASE con.contype WHEN 'p' THEN 0 WHEN 'u' THEN 1 WHEN 'c' THEN 2 WHEN 'f' THEN 3 ELSE 4 END, \
+             con.conname";
+
+/// PostgreSQL indexes that are not already implied by a constraint
+const PG_INDEXES_QUERY: &str = "SELECT \
+        CAST(pg_get_indexdef(i.indexrelid) AS TEXT) as definition \
+    FROM pg_index i \
+    JOIN pg_class c ON c.oid = i.indrelid \
+    JOIN pg_class ic ON ic.oid = i.indexrelid \
+    JOIN pg_namespace n ON n.oid = c.relnamespace \
+    WHERE n.nspname = $1 AND c.relname = $2 \
+      AND NOT EXISTS ( \
+          SELECT 1 FROM pg_constraint con \
+          WHERE con.conindid = i.indexrelid AND con.conrelid = i.indrelid \
+            AND con.contype IN ('p', 'u', 'x') \
+      ) \
+    ORDER BY ic.relname";
+
+/// SQLite table DDL as originally written; `{schema}` is `main` or an attached database
+const SQLITE_TABLE_QUERY: &str =
+    "SELECT sql FROM {schema}.sqlite_master WHERE type = 'table' AND name = ?";
+
+/// SQLite explicit index DDL (auto-indexes have NULL sql)
+const SQLITE_INDEXES_QUERY: &str = "SELECT sql FROM {schema}.sqlite_master \
+    WHERE type = 'index' AND tbl_name = ? AND sql IS NOT NULL \
+    ORDER BY name";
+
+/// Column definition used to reconstruct PostgreSQL DDL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DdlColumn {
+    /// Column name
+    pub name: String,
+
+    /// Formatted type, e.g. `character varying(255)`
+    pub data_type: String,
+
+    /// Whether the column has a NOT NULL constraint
+    pub not_null: bool,
+
+    /// Default expression, if any; the generation expression for generated columns
+    pub default: Option<String>,
+
+    /// Identity kind, `ALWAYS` or `BY DEFAULT`, for identity columns
+    pub identity: Option<String>,
+
+    /// Storage, `STORED` or `VIRTUAL`, for generated columns
+    pub generated: Option<String>,
+}
+
+/// Named table constraint used to reconstruct PostgreSQL DDL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DdlConstraint {
+    /// Constraint name
+    pub name: String,
+
+    /// Constraint body as returned by `pg_get_constraintdef()`
+    pub definition: String,
+}
+
+/// Validate schema and table names before generating DDL
+///
+/// Any name the database accepts is allowed, since interpolated names are
+/// quoted (see [`quote_ddl_ident`]); only empty names and NUL characters are
+/// rejected. For SQLite the schema is `main` or the name of an attached database.
+///
+/// # Errors
+///
+/// Returns `DatabaseError::QueryError` for an empty name or one containing NUL,
+/// or `DatabaseError::FeatureNotSupported` for SQL Server.
+pub fn validate_ddl_target(
+    db_type: DatabaseType,
+    schema: &str,
+    table: &str,
+) -> Result<(), DatabaseError> {
+    if matches!(db_type, DatabaseType::SqlServer) {
+        return Err(DatabaseError::FeatureNotSupported(
+            "DDL generation is not supported for SQL Server".to_string(),
+        ));
+    }
+
+    for name in [schema, table] {
+        if name.is_empty() || name.contains('\0') {
+            return Err(DatabaseError::QueryError(format!("Invalid identifier: {:?}", name)));
+        }
+    }
+    Ok(())
+}
+
+/// Quote a single identifier for interpolation into `db_type`'s SQL
+///
+/// MySQL/MariaDB use backticks, everything else double quotes; embedded quote
+/// characters are doubled. Unlike [`crate::upsert::quote_identifier`], dots
+/// are part of the name.
+///
+/// # Example
+///
+/// ```rust
+/// use kodegen_tools_database::ddl::quote_ddl_ident;
+/// use kodegen_tools_database::types::DatabaseType;
+///
+/// assert_eq!(quote_ddl_ident(DatabaseType::MySQL, "order`s"), "`order``s`");
+/// assert_eq!(quote_ddl_ident(DatabaseType::SQLite, "my.db"), "\"my.db\"");
+/// ```
+pub fn quote_ddl_ident(db_type: DatabaseType, name: &str) -> String {
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => format!("`{}`", name.replace('`', "``")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// `SHOW CREATE TABLE` for a MySQL/MariaDB table
+fn mysql_show_create_sql(schema: &str, table: &str) -> String {
+    format!(
+        "SHOW CREATE TABLE {}.{}",
+        quote_ddl_ident(DatabaseType::MySQL, schema),
+        quote_ddl_ident(DatabaseType::MySQL, table)
+    )
+}
+
+/// A SQLite `sqlite_master` query against `schema`
+fn sqlite_master_sql(template: &str, schema: &str) -> String {
+    template.replace("{schema}", &quote_ddl_ident(DatabaseType::SQLite, schema))
+}
+
+/// Column clauses after the type: generation, default and nullability
+fn postgres_column_options(col: &DdlColumn) -> String {
+    let mut options = String::new();
+    match (&col.generated, &col.default) {
+        (Some(storage), Some(expression)) => {
+            options.push_str(&format!(" GENERATED ALWAYS AS ({}) {}", expression, storage));
+        }
+        (_, default) => {
+            if let Some(kind) = &col.identity {
+                options.push_str(&format!(" GENERATED {} AS IDENTITY", kind));
+            } else if let Some(default) = default {
+                options.push_str(&format!(" DEFAULT {}", default));
+            }
+        }
+    }
+    if col.not_null {
+        options.push_str(" NOT NULL");
+    }
+    options
+}
+
+/// Map `pg_attribute.attidentity` to its DDL keyword
+fn identity_kind(code: Option<String>) -> Option<String> {
+    match code.as_deref() {
+        Some("a") => Some("ALWAYS".to_string()),
+        Some("d") => Some("BY DEFAULT".to_string()),
+        _ => None,
+    }
+}
+
+/// Map `pg_attribute.attgenerated` to its DDL keyword
+fn generated_storage(code: Option<String>) -> Option<String> {
+    match code.as_deref() {
+        Some("s") => Some("STORED".to_string()),
+        Some("v") => Some("VIRTUAL".to_string()),
+        _ => None,
+    }
+}
+
+/// Quote a PostgreSQL identifier only when required
+///
+/// Mirrors `quote_ident()`: lowercase names made of letters, digits, and
+/// underscores are emitted bare; anything else is double-quoted.
+///
+/// # Example
+///
+/// ```rust
+/// use kodegen_tools_database::ddl::quote_pg_ident;
+///
+/// assert_eq!(quote_pg_ident("users"), "users");
+/// assert_eq!(quote_pg_ident("UserAccounts"), "\"UserAccounts\"");
+/// ```
+pub fn quote_pg_ident(name: &str) -> String {
+    let is_plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if is_plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Build a PostgreSQL `CREATE TABLE` statement from catalog metadata
+///
+/// Columns come first, followed by named constraints, then any standalone
+/// `CREATE INDEX` statements (as returned by `pg_get_indexdef()`).
+///
+/// # Example
+///
+/// ```rust
+/// use kodegen_tools_database::ddl::{DdlColumn, DdlConstraint, build_postgres_ddl};
+///
+/// let columns = vec![DdlColumn {
+///     name: "id".to_string(),
+///     data_type: "integer".to_string(),
+///     not_null: true,
+///     default: None,
+///     identity: None,
+///     generated: None,
+/// }];
+/// let constraints = vec![DdlConstraint {
+///     name: "users_pkey".to_string(),
+///     definition: "PRIMARY KEY (id)".to_string(),
+/// }];
+///
+/// let ddl = build_postgres_ddl("public", "users", &columns, &constraints, &[]);
+/// assert_eq!(
+///     ddl,
+///     "CREATE TABLE public.users (\n    id integer NOT NULL,\n    CONSTRAINT users_pkey PRIMARY KEY (id)\n);"
+/// );
+/// ```
+pub fn build_postgres_ddl(
+    schema: &str,
+    table: &str,
+    columns: &[DdlColumn],
+    constraints: &[DdlConstraint],
+    indexes: &[String],
+) -> String {
+    let mut definitions: Vec<String> = columns
+        .iter()
+        .map(|col| {
+            format!(
+                "    {} {}{}",
+                quote_pg_ident(&col.name),
+                col.data_type,
+                postgres_column_options(col)
+            )
+        })
+        .collect();
+
+    definitions.extend(constraints.iter().map(|con| {
+        format!("    CONSTRAINT {} {}", quote_pg_ident(&con.name), con.definition)
+    }));
+
+    let mut ddl = format!(
+        "CREATE TABLE {}.{} (\n{}\n);",
+        quote_pg_ident(schema),
+        quote_pg_ident(table),
+        definitions.join(",\n")
+    );
+
+    for index in indexes {
+        ddl.push_str(&format!("\n{};", index));
+    }
+
+    ddl
+}
+
+/// Fetch the `CREATE TABLE` DDL for a table
+///
+/// Identifiers must already have passed [`validate_ddl_target`].
+///
+/// # Returns
+///
+/// * `Ok(Some(ddl))` - DDL (plus index statements where applicable)
+/// * `Ok(None)` - Table does not exist
+/// * `Err(sqlx::Error)` - Query failed
+pub async fn fetch_table_ddl(
+    pool: &AnyPool,
+    db_type: DatabaseType,
+    schema: &str,
+    table: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    match db_type {
+        DatabaseType::Postgres => {
+            let column_rows = sqlx::query(PG_COLUMNS_QUERY)
+                .bind(schema)
+                .bind(table)
+                .fetch_all(pool)
+                .await?;
+            if column_rows.is_empty() {
+                return Ok(None);
+            }
+
+            let columns = column_rows
+                .iter()
+                .map(|row| {
+                    Ok(DdlColumn {
+                        name: row.try_get("column_name")?,
+                        data_type: row.try_get("data_type")?,
+                        not_null: row.try_get("not_null")?,
+                        default: row.try_get("column_default")?,
+                        identity: identity_kind(row.try_get("identity")?),
+                        generated: generated_storage(row.try_get("generated")?),
+                    })
+                })
+                .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+            let constraints = sqlx::query(PG_CONSTRAINTS_QUERY)
+                .bind(schema)
+                .bind(table)
+                .fetch_all(pool)
+                .await?
+                .iter()
+                .map(|row| {
+                    Ok(DdlConstraint {
+                        name: row.try_get("constraint_name")?,
+                        definition: row.try_get("definition")?,
+                    })
+                })
+                .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+            let indexes = sqlx::query(PG_INDEXES_QUERY)
+                .bind(schema)
+                .bind(table)
+                .fetch_all(pool)
+                .await?
+                .iter()
+                .map(|row| row.try_get("definition"))
+                .collect::<Result<Vec<String>, sqlx::Error>>()?;
+
+            Ok(Some(build_postgres_ddl(
+                schema,
+                table,
+                &columns,
+                &constraints,
+                &indexes,
+            )))
+        }
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            // SHOW CREATE TABLE cannot be parameterized - identifiers are quoted
+            let sql = mysql_show_create_sql(schema, table);
+            let row = sqlx::query(&sql).fetch_optional(pool).await?;
+            // Second column is "Create Table"
+            row.map(|row| row.try_get::<String, _>(1).map(|ddl| format!("{};", ddl)))
+                .transpose()
+        }
+        DatabaseType::SQLite => {
+            // sqlite_master cannot be chosen by a bind parameter - schema is quoted
+            let table_sql = sqlite_master_sql(SQLITE_TABLE_QUERY, schema);
+            let Some(row) = sqlx::query(&table_sql)
+                .bind(table)
+                .fetch_optional(pool)
+                .await?
+            else {
+                return Ok(None);
+            };
+            let mut statements: Vec<String> = vec![row.try_get("sql")?];
+
+            let indexes_sql = sqlite_master_sql(SQLITE_INDEXES_QUERY, schema);
+            let index_rows = sqlx::query(&indexes_sql)
+                .bind(table)
+                .fetch_all(pool)
+                .await?;
+            for row in &index_rows {
+                statements.push(row.try_get("sql")?);
+            }
+
+            Ok(Some(
+                statements
+                    .iter()
+                    .map(|stmt| format!("{};", stmt))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ))
+        }
+        DatabaseType::SqlServer => Err(sqlx::Error::Configuration(
+            "DDL generation is not supported for SQL Server".into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn sqlite_pool() -> AnyPool {
+        sqlx::any::install_default_drivers();
+        sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool")
+    }
+
+    async fn apply_ddl(pool: &AnyPool, ddl: &str) {
+        for stmt in ddl.split(";\n").map(|s| s.trim_end_matches(';')) {
+            sqlx::query(stmt).execute(pool).await.expect("apply DDL");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_ddl_round_trip() {
+        let original = "CREATE TABLE employees (\n    \
+                            id INTEGER PRIMARY KEY,\n    \
+                            email TEXT NOT NULL UNIQUE,\n    \
+                            salary REAL DEFAULT 0 CHECK (salary >= 0)\n\
+                        );\n\
+                        CREATE INDEX idx_employees_salary ON employees (salary);";
+
+        let first = sqlite_pool().await;
+        apply_ddl(&first, original).await;
+        let dumped = fetch_table_ddl(&first, DatabaseType::SQLite, "main", "employees")
+            .await
+            .expect("fetch DDL")
+            .expect("table exists");
+        assert!(dumped.contains("CREATE INDEX idx_employees_salary"));
+
+        // Recreate from the dump and dump again - the result must be identical
+        let second = sqlite_pool().await;
+        apply_ddl(&second, &dumped).await;
+        let redumped = fetch_table_ddl(&second, DatabaseType::SQLite, "main", "employees")
+            .await
+            .expect("fetch DDL")
+            .expect("table exists");
+
+        assert_eq!(dumped, redumped);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_missing_table_returns_none() {
+        let pool = sqlite_pool().await;
+        let ddl = fetch_table_ddl(&pool, DatabaseType::SQLite, "main", "missing")
+            .await
+            .expect("fetch DDL");
+        assert!(ddl.is_none());
+    }
+
+    #[test]
+    fn test_build_postgres_ddl_full() {
+        let columns = vec![
+            DdlColumn {
+                name: "id".to_string(),
+                data_type: "integer".to_string(),
+                not_null: true,
+                default: Some("nextval('employees_id_seq'::regclass)".to_string()),
+                identity: None,
+                generated: None,
+            },
+            DdlColumn {
+                name: "Email".to_string(),
+                data_type: "character varying(255)".to_string(),
+                not_null: false,
+                default: None,
+                identity: None,
+                generated: None,
+            },
+        ];
+        let constraints = vec![
+            DdlConstraint {
+                name: "employees_pkey".to_string(),
+                definition: "PRIMARY KEY (id)".to_string(),
+            },
+            DdlConstraint {
+                name: "employees_department_id_fkey".to_string(),
+                definition: "FOREIGN KEY (department_id) REFERENCES departments(id)".to_string(),
+            },
+        ];
+        let indexes =
+            vec!["CREATE INDEX idx_email ON public.employees USING btree (\"Email\")".to_string()];
+
+        let ddl = build_postgres_ddl("public", "employees", &columns, &constraints, &indexes);
+
+        assert_eq!(
+            ddl,
+            "CREATE TABLE public.employees (\n    \
+                 id integer DEFAULT nextval('employees_id_seq'::regclass) NOT NULL,\n    \
+                 \"Email\" character varying(255),\n    \
+                 CONSTRAINT employees_pkey PRIMARY KEY (id),\n    \
+                 CONSTRAINT employees_department_id_fkey FOREIGN KEY (department_id) REFERENCES departments(id)\n\
+             );\n\
+             CREATE INDEX idx_email ON public.employees USING btree (\"Email\");"
+        );
+    }
+
+    #[test]
+    fn test_build_postgres_ddl_identity_and_generated() {
+        let columns = vec![
+            DdlColumn {
+                name: "id".to_string(),
+                data_type: "bigint".to_string(),
+                not_null: true,
+                default: None,
+                identity: identity_kind(Some("a".to_string())),
+                generated: None,
+            },
+            DdlColumn {
+                name: "seq".to_string(),
+                data_type: "integer".to_string(),
+                not_null: true,
+                default: None,
+                identity: identity_kind(Some("d".to_string())),
+                generated: None,
+            },
+            DdlColumn {
+                name: "total".to_string(),
+                data_type: "numeric".to_string(),
+                not_null: false,
+                default: Some("(price * (qty)::numeric)".to_string()),
+                identity: identity_kind(Some(String::new())),
+                generated: generated_storage(Some("s".to_string())),
+            },
+        ];
+
+        let ddl = build_postgres_ddl("public", "orders", &columns, &[], &[]);
+
+        assert_eq!(
+            ddl,
+            "CREATE TABLE public.orders (\n    \
+                 id bigint GENERATED ALWAYS AS IDENTITY NOT NULL,\n    \
+                 seq integer GENERATED BY DEFAULT AS IDENTITY NOT NULL,\n    \
+                 total numeric GENERATED ALWAYS AS ((price * (qty)::numeric)) STORED\n\
+             );"
+        );
+    }
+
+    #[test]
+    fn test_mysql_show_create_quotes_identifiers() {
+        assert_eq!(mysql_show_create_sql("app", "users"), "SHOW CREATE TABLE `app`.`users`");
+        assert_eq!(
+            mysql_show_create_sql("my-app", "odd`; DROP"),
+            "SHOW CREATE TABLE `my-app`.`odd``; DROP`"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_ddl_for_quoted_table_name() {
+        let pool = sqlite_pool().await;
+        apply_ddl(&pool, "CREATE TABLE \"order items\" (id INTEGER)").await;
+        let ddl = fetch_table_ddl(&pool, DatabaseType::SQLite, "main", "order items")
+            .await
+            .expect("fetch DDL")
+            .expect("table exists");
+        assert_eq!(ddl, "CREATE TABLE \"order items\" (id INTEGER);");
+    }
+
+    #[test]
+    fn test_quote_pg_ident() {
+        assert_eq!(quote_pg_ident("employees"), "employees");
+        assert_eq!(quote_pg_ident("_tmp1"), "_tmp1");
+        assert_eq!(quote_pg_ident("Employees"), "\"Employees\"");
+        assert_eq!(quote_pg_ident("1st"), "\"1st\"");
+        assert_eq!(quote_pg_ident("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_validate_ddl_target() {
+        assert!(validate_ddl_target(DatabaseType::MySQL, "app", "users").is_ok());
+        assert!(validate_ddl_target(DatabaseType::MySQL, "my-app", "Order Items").is_ok());
+        assert!(validate_ddl_target(DatabaseType::MySQL, "app", "").is_err());
+        assert!(validate_ddl_target(DatabaseType::Postgres, "public", "users\0").is_err());
+        assert!(validate_ddl_target(DatabaseType::SqlServer, "dbo", "users").is_err());
+    }
+}
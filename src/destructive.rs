@@ -0,0 +1,342 @@
+//! Detection of high-risk, usually accidental, destructive statements
+//!
+//! Flags statements that wipe or remove whole tables: DELETE/UPDATE without a
+//! WHERE clause or with one that holds for every row (`WHERE 1=1`), DROP, and
+//! TRUNCATE. DELETE/UPDATE inside a query (`WITH ... DELETE`, writable CTEs)
+//! count too. Used by `db_confirm_destructive` as a safety net when read-only
+//! mode is off.
+
+use crate::sql_parser::{extract_first_keyword, get_dialect};
+use crate::types::DatabaseType;
+use sqlparser::ast::{
+    BinaryOperator, Expr, FromTable, Query, SetExpr, Statement, TableFactor, TableWithJoins,
+    UnaryOperator, Value, ValueWithSpan,
+};
+use sqlparser::parser::Parser;
+
+/// Describe why a single statement is high-risk, or `None` if it is not
+///
+/// A statement that cannot be parsed but starts with DELETE, UPDATE, DROP or
+/// TRUNCATE is reported as unverifiable rather than let through.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::destructive::destructive_risk;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// let pg = DatabaseType::Postgres;
+/// assert_eq!(
+///     destructive_risk("DELETE FROM users", pg).as_deref(),
+///     Some("DELETE without WHERE on users")
+/// );
+/// assert_eq!(destructive_risk("DELETE FROM users WHERE id = 1", pg), None);
+/// assert_eq!(
+///     destructive_risk("DELETE FROM users WHERE 1=1", pg).as_deref(),
+///     Some("DELETE with always-true WHERE 1 = 1 on users")
+/// );
+/// assert_eq!(destructive_risk("DROP TABLE users", pg).as_deref(), Some("DROP TABLE users"));
+/// ```
+pub fn destructive_risk(sql: &str, db_type: DatabaseType) -> Option<String> {
+    let dialect = get_dialect(db_type);
+    let statements = match Parser::parse_sql(&*dialect, sql) {
+        Ok(statements) => statements,
+        Err(_) => {
+            let keyword = extract_first_keyword(sql, db_type).ok()?;
+            let destructive = matches!(keyword.as_str(), "delete" | "update" | "drop" | "truncate");
+            return destructive.then(|| {
+                format!("{} statement that could not be analyzed", keyword.to_uppercase())
+            });
+        }
+    };
+
+    let risks: Vec<String> = statements.iter().filter_map(statement_risk).collect();
+    (!risks.is_empty()).then(|| risks.join("; "))
+}
+
+fn statement_risk(stmt: &Statement) -> Option<String> {
+    match stmt {
+        Statement::Delete(delete) => {
+            let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) =
+                &delete.from;
+            where_risk("DELETE", delete.selection.as_ref(), from)
+        }
+        Statement::Update(update) => where_risk(
+            "UPDATE",
+            update.selection.as_ref(),
+            std::slice::from_ref(&update.table),
+        ),
+        Statement::Drop {
+            object_type, names, ..
+        } => Some(format!(
+            "DROP {} {}",
+            object_type,
+            names.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+        )),
+        Statement::Truncate(truncate) => Some(format!(
+            "TRUNCATE {}",
+            truncate
+                .table_names
+                .iter()
+                .map(|target| target.name.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        Statement::Query(query) => query_risk(query),
+        _ => None,
+    }
+}
+
+/// Risk of DELETE/UPDATE nested in a query: writable CTEs and `WITH ... DELETE`
+fn query_risk(query: &Query) -> Option<String> {
+    let risks: Vec<String> = query
+        .with
+        .iter()
+        .flat_map(|with| &with.cte_tables)
+        .filter_map(|cte| query_risk(&cte.query))
+        .chain(set_expr_risk(&query.body))
+        .collect();
+    (!risks.is_empty()).then(|| risks.join("; "))
+}
+
+fn set_expr_risk(expr: &SetExpr) -> Option<String> {
+    match expr {
+        SetExpr::Update(stmt) | SetExpr::Delete(stmt) => statement_risk(stmt),
+        SetExpr::Query(query) => query_risk(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            let risks: Vec<String> =
+                [left, right].into_iter().filter_map(|side| set_expr_risk(side)).collect();
+            (!risks.is_empty()).then(|| risks.join("; "))
+        }
+        _ => None,
+    }
+}
+
+/// Risk of a DELETE/UPDATE whose WHERE is missing or holds for every row
+fn where_risk(verb: &str, selection: Option<&Expr>, tables: &[TableWithJoins]) -> Option<String> {
+    match selection {
+        None => Some(format!("{} without WHERE on {}", verb, table_list(tables))),
+        Some(predicate) if always_true(predicate) => Some(format!(
+            "{} with always-true WHERE {} on {}",
+            verb,
+            predicate,
+            table_list(tables)
+        )),
+        Some(_) => None,
+    }
+}
+
+/// Value of a predicate sub-expression that does not depend on any row
+#[derive(Debug, Clone, PartialEq)]
+enum Constant {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    Null,
+}
+
+impl Constant {
+    /// Truth value when used as a predicate; MySQL and SQLite treat non-zero
+    /// numbers as true (`WHERE 1`)
+    fn truth(&self) -> Option<bool> {
+        match self {
+            Constant::Bool(b) => Some(*b),
+            Constant::Number(n) => Some(*n != 0.0),
+            Constant::Text(_) | Constant::Null => None,
+        }
+    }
+}
+
+/// Whether a WHERE predicate holds for every row (`1=1`, `TRUE`, `id = 5 OR 1 = 1`)
+fn always_true(predicate: &Expr) -> bool {
+    constant(predicate).and_then(|value| value.truth()) == Some(true)
+}
+
+/// Evaluate `expr` if it is built only from literals
+///
+/// Conservative: anything involving a column, parameter or function, and
+/// comparisons between different kinds of value, are `None` (not constant).
+fn constant(expr: &Expr) -> Option<Constant> {
+    match expr {
+        Expr::Value(ValueWithSpan { value, .. }) => match value {
+            Value::Boolean(b) => Some(Constant::Bool(*b)),
+            Value::Number(n, _) => n.parse().ok().map(Constant::Number),
+            Value::SingleQuotedString(s) => Some(Constant::Text(s.clone())),
+            Value::Null => Some(Constant::Null),
+            _ => None,
+        },
+        Expr::Nested(inner) => constant(inner),
+        Expr::UnaryOp { op, expr } => match (op, constant(expr)?) {
+            (UnaryOperator::Not, value) => value.truth().map(|b| Constant::Bool(!b)),
+            (UnaryOperator::Minus, Constant::Number(n)) => Some(Constant::Number(-n)),
+            (UnaryOperator::Plus, Constant::Number(n)) => Some(Constant::Number(n)),
+            _ => None,
+        },
+        Expr::IsNull(inner) => Some(Constant::Bool(constant(inner)? == Constant::Null)),
+        Expr::IsNotNull(inner) => Some(Constant::Bool(constant(inner)? != Constant::Null)),
+        Expr::BinaryOp { left, op, right } => {
+            let left = constant(left);
+            let right = constant(right);
+            let truths = (
+                left.as_ref().and_then(Constant::truth),
+                right.as_ref().and_then(Constant::truth),
+            );
+            match op {
+                // One known side can decide OR/AND even if the other depends on the row
+                BinaryOperator::Or => match truths {
+                    (Some(true), _) | (_, Some(true)) => Some(Constant::Bool(true)),
+                    (Some(false), Some(false)) => Some(Constant::Bool(false)),
+                    _ => None,
+                },
+                BinaryOperator::And => match truths {
+                    (Some(false), _) | (_, Some(false)) => Some(Constant::Bool(false)),
+                    (Some(true), Some(true)) => Some(Constant::Bool(true)),
+                    _ => None,
+                },
+                _ => compare(&left?, op, &right?).map(Constant::Bool),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Result of a comparison between two constants of the same kind
+fn compare(left: &Constant, op: &BinaryOperator, right: &Constant) -> Option<bool> {
+    let ordering = match (left, right) {
+        (Constant::Number(a), Constant::Number(b)) => a.partial_cmp(b)?,
+        (Constant::Text(a), Constant::Text(b)) => a.cmp(b),
+        (Constant::Bool(a), Constant::Bool(b)) => a.cmp(b),
+        _ => return None,
+    };
+    match op {
+        BinaryOperator::Eq => Some(ordering.is_eq()),
+        BinaryOperator::NotEq => Some(ordering.is_ne()),
+        BinaryOperator::Lt => Some(ordering.is_lt()),
+        BinaryOperator::LtEq => Some(ordering.is_le()),
+        BinaryOperator::Gt => Some(ordering.is_gt()),
+        BinaryOperator::GtEq => Some(ordering.is_ge()),
+        _ => None,
+    }
+}
+
+/// Base table names of a FROM list, for messages
+fn table_list(tables: &[TableWithJoins]) -> String {
+    let names: Vec<String> = tables
+        .iter()
+        .map(|table| match &table.relation {
+            TableFactor::Table { name, .. } => name.to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PG: DatabaseType = DatabaseType::Postgres;
+
+    #[test]
+    fn test_unqualified_writes_flagged() {
+        assert_eq!(
+            destructive_risk("DELETE FROM users", PG).as_deref(),
+            Some("DELETE without WHERE on users")
+        );
+        assert_eq!(
+            destructive_risk("UPDATE users SET active = false", PG).as_deref(),
+            Some("UPDATE without WHERE on users")
+        );
+        assert_eq!(destructive_risk("UPDATE users SET active = false WHERE id = 3", PG), None);
+    }
+
+    #[test]
+    fn test_always_true_where_flagged() {
+        assert_eq!(
+            destructive_risk("DELETE FROM t WHERE 1=1", PG).as_deref(),
+            Some("DELETE with always-true WHERE 1 = 1 on t")
+        );
+        assert_eq!(
+            destructive_risk("UPDATE t SET a = 1 WHERE TRUE", PG).as_deref(),
+            Some("UPDATE with always-true WHERE true on t")
+        );
+        for sql in [
+            "DELETE FROM t WHERE id = 5 OR 1 = 1",
+            "DELETE FROM t WHERE NOT (1 = 0)",
+            "DELETE FROM t WHERE 'a' = 'a' AND (2 > 1)",
+            "DELETE FROM t WHERE NULL IS NULL",
+        ] {
+            assert!(destructive_risk(sql, PG).is_some(), "{}", sql);
+        }
+        assert!(destructive_risk("DELETE FROM t WHERE 1", DatabaseType::MySQL).is_some());
+    }
+
+    #[test]
+    fn test_selective_where_not_flagged() {
+        for sql in [
+            "DELETE FROM t WHERE id = 5",
+            "DELETE FROM t WHERE id = $1",
+            "DELETE FROM t WHERE 1 = 0",
+            "DELETE FROM t WHERE 1 = 1 AND id = 5",
+            "DELETE FROM t WHERE NULL = NULL",
+            "UPDATE t SET a = 1 WHERE '1' = 1",
+        ] {
+            assert_eq!(destructive_risk(sql, PG), None, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_writes_inside_queries_flagged() {
+        assert_eq!(
+            destructive_risk("WITH x AS (SELECT 1) DELETE FROM users", PG).as_deref(),
+            Some("DELETE without WHERE on users")
+        );
+        assert_eq!(
+            destructive_risk(
+                "WITH gone AS (DELETE FROM users RETURNING id) SELECT count(*) FROM gone",
+                PG
+            )
+            .as_deref(),
+            Some("DELETE without WHERE on users")
+        );
+        assert_eq!(
+            destructive_risk(
+                "WITH bumped AS (UPDATE t SET a = 1 WHERE TRUE RETURNING a) SELECT * FROM bumped",
+                PG
+            )
+            .as_deref(),
+            Some("UPDATE with always-true WHERE true on t")
+        );
+        assert_eq!(
+            destructive_risk(
+                "WITH gone AS (DELETE FROM users WHERE id = 1 RETURNING id) SELECT * FROM gone",
+                PG
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_drop_and_truncate_flagged() {
+        assert_eq!(
+            destructive_risk("DROP TABLE IF EXISTS hr.employees", PG).as_deref(),
+            Some("DROP TABLE hr.employees")
+        );
+        assert_eq!(
+            destructive_risk("TRUNCATE orders", DatabaseType::MySQL).as_deref(),
+            Some("TRUNCATE orders")
+        );
+    }
+
+    #[test]
+    fn test_reads_and_inserts_not_flagged() {
+        assert_eq!(destructive_risk("SELECT * FROM users", PG), None);
+        assert_eq!(destructive_risk("INSERT INTO users (id) VALUES (1)", PG), None);
+    }
+
+    #[test]
+    fn test_unparseable_destructive_statement_flagged() {
+        assert_eq!(
+            destructive_risk("DELETE FROM users WHERE id = ((", PG).as_deref(),
+            Some("DELETE statement that could not be analyzed")
+        );
+    }
+}
@@ -38,6 +38,10 @@ pub enum DatabaseError {
     #[error("Feature not supported: {0}")]
     FeatureNotSupported(String),
 
+    /// Too many queries in flight
+    #[error("Concurrency limit reached: {0}")]
+    ConcurrencyLimit(String),
+
     /// sqlx database error
     #[error("Database error: {0}")]
     Sqlx(#[from] sqlx::Error),
@@ -83,6 +87,9 @@ impl From<DatabaseError> for McpError {
             DatabaseError::FeatureNotSupported(msg) => {
                 McpError::InvalidArguments(format!("[Feature Not Supported] {}", msg))
             }
+            DatabaseError::ConcurrencyLimit(msg) => {
+                McpError::Other(anyhow::anyhow!("[DB Concurrency] {}", msg))
+            }
             DatabaseError::Sqlx(sqlx_err) => convert_sqlx_error(sqlx_err),
             DatabaseError::Ssh(ssh_err) => McpError::Network(format!("[SSH] {}", ssh_err)),
             DatabaseError::UrlParse(url_err) => {
@@ -93,6 +100,31 @@ impl From<DatabaseError> for McpError {
     }
 }
 
+/// Append advice to driver errors about MySQL column types the Any driver cannot read
+///
+/// The `sqlx::any` driver only carries integers, floats, text and blobs, so
+/// MySQL `DATE`/`DATETIME`/`TIMESTAMP`/`TIME`/`DECIMAL`/... columns fail the
+/// whole query. Casting them to `CHAR` returns the stored text unchanged,
+/// including zero dates such as `'0000-00-00'` that have no calendar value.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::error::with_type_hint;
+/// let msg = with_type_hint("Any driver does not support MySql type Date".to_string());
+/// assert!(msg.contains("CAST(column AS CHAR)"));
+/// assert_eq!(with_type_hint("syntax error".to_string()), "syntax error");
+/// ```
+pub fn with_type_hint(message: String) -> String {
+    if !message.contains("does not support MySql type") {
+        return message;
+    }
+    format!(
+        "{}. Select the column as text instead, e.g. CAST(column AS CHAR); \
+         this also returns zero dates such as '0000-00-00' as written",
+        message
+    )
+}
+
 /// Convert sqlx errors to McpError with detailed error handling
 fn convert_sqlx_error(err: sqlx::Error) -> McpError {
     match err {
@@ -121,12 +153,15 @@ fn convert_sqlx_error(err: sqlx::Error) -> McpError {
         sqlx::Error::ColumnDecode { index, source } => McpError::Other(anyhow::anyhow!(
             "Failed to decode column {}: {}",
             index,
-            source
+            with_type_hint(source.to_string())
         )),
         sqlx::Error::Decode(err) => McpError::Other(anyhow::anyhow!("Decode error: {}", err)),
         sqlx::Error::PoolTimedOut => McpError::Network("Connection pool timed out".to_string()),
         sqlx::Error::PoolClosed => McpError::Network("Connection pool closed".to_string()),
         sqlx::Error::WorkerCrashed => McpError::Other(anyhow::anyhow!("Database worker crashed")),
-        _ => McpError::Other(anyhow::anyhow!("Database error: {}", err)),
+        _ => McpError::Other(anyhow::anyhow!(
+            "Database error: {}",
+            with_type_hint(err.to_string())
+        )),
     }
 }
@@ -7,12 +7,33 @@ pub mod error;
 pub mod types;
 
 // Utilities (implemented in later tasks)
+#[cfg(feature = "arrow")]
+pub mod arrow_export;
+pub mod column_default;
+pub mod constraints;
+pub mod cross_join;
+pub mod ddl;
+pub mod destructive;
+pub mod diagnostic;
 pub mod dsn;
+pub mod explain;
+#[cfg(feature = "aws-iam")]
+pub mod iam_auth;
+#[cfg(feature = "postgres")]
+pub mod listen;
+pub mod pagination;
+pub mod pool_registry;
 pub mod readonly;
+pub mod readonly_role;
+pub mod rewrite;
+pub mod preview;
 pub mod schema_queries;
+pub mod security;
 pub mod sql_limiter;
 pub mod sql_parser;
 pub mod ssh_tunnel;
+pub mod transactions;
+pub mod upsert;
 pub mod validate;
 pub mod connection;
 
@@ -23,25 +44,58 @@ pub mod tools;
 pub use secrecy::{ExposeSecret, SecretString};
 
 // Re-exports
+#[cfg(feature = "arrow")]
+pub use arrow_export::query_to_arrow;
+pub use column_default::classify_default;
+pub use constraints::{fetch_table_constraints, parse_sqlite_constraints};
+pub use cross_join::cartesian_products;
+pub use ddl::{build_postgres_ddl, fetch_table_ddl, validate_ddl_target};
+pub use destructive::destructive_risk;
+pub use diagnostic::{ConnectionDiagnostic, ConnectionPhase, diagnose_connection_failure};
 pub use dsn::{
-    DSNInfo, detect_database_type, extract_database, extract_host, extract_port, parse_dsn,
-    rewrite_dsn_for_tunnel, validate_dsn,
+    DEFAULT_DSN, DSNInfo, DefaultPorts, DsnParseOptions, check_auth_params, detect_database_type,
+    dsn_from_env, expand_dsn_env, extract_database, extract_host, extract_port,
+    extract_port_with_defaults, parse_dsn, parse_dsn_with, parse_dsn_with_options, parse_jdbc_dsn,
+    rewrite_dsn_for_tunnel, rewrite_dsn_for_tunnel_with, validate_dsn,
 };
 pub use error::DatabaseError;
-pub use readonly::validate_readonly_sql;
+pub use explain::{estimate_query, explain_query};
+#[cfg(feature = "postgres")]
+pub use listen::listen;
+pub use pagination::paginate;
+pub use readonly::{validate_readonly_sql, validate_readonly_sql_with_max_depth};
+pub use readonly_role::{connection_can_write, verify_readonly_role, write_access};
+pub use rewrite::apply_table_prefix;
 pub use schema_queries::{
-    get_default_schema, get_indexes_query, get_schemas_query, get_stored_procedures_query,
+    get_active_queries_query, get_connection_info_query, get_constraints_query, get_default_schema, get_indexes_query, get_schemas_query,
+    get_procedure_definition_query, get_search_schema_query, get_stored_procedures_query,
     get_table_schema_query, get_tables_query,
 };
-pub use sql_limiter::apply_row_limit;
-pub use sql_parser::{extract_first_keyword, split_sql_statements, strip_comments};
+pub use security::connection_security;
+pub use sql_limiter::{
+    apply_page, apply_row_limit, apply_row_offset, apply_show_limit, extract_row_limit,
+    has_order_by, is_introspection_statement, is_limited_without_order_by,
+};
+pub use sql_parser::{
+    extract_first_keyword, fingerprint_sql, normalize_sql_input, split_sql_statements,
+    strip_comments,
+};
 pub use ssh_tunnel::{SSHAuth, SSHConfig, SSHTunnel, TunnelConfig, establish_tunnel};
-pub use connection::{DatabaseConnection, setup_database_pool, warmup_pool};
-pub use tools::ExecuteSQLTool;
+pub use connection::{
+    DEFAULT_VALIDATION_QUERY, DatabaseConnection, OwnSessions, PoolTuning, PooledConnection,
+    SqlitePragmas, application_name_statement, build_pool_options, charset_statement,
+    session_reset_statement, setup_database_pool, timezone_statement, warmup_pool,
+    warmup_pool_with_concurrency, warmup_pool_with_progress, warmup_pool_with_query,
+    with_statement_cache_capacity,
+};
+pub use tools::{ExecuteSQLTool, QueryAllowlist, QueryObserver, TypeDecode, TypeMapper};
+pub use transactions::TransactionRegistry;
 pub use types::{
-    DatabaseType, ExecuteOptions, SQLResult, StoredProcedure, TableColumn, TableIndex,
+    ActiveQuery, ConnectionInfo, DatabaseType, ExecuteOptions, ExplainResult, Notification,
+    PlanEstimate, ProcedureParameter, QueryStats, SQLResult, SecurityInfo, StoredProcedure, TableColumn, TableConstraint, TableIndex,
 };
-pub use validate::validate_sqlite_identifier;
+pub use upsert::{build_upsert_sql, quote_identifier, upsert};
+pub use validate::{validate_postgres_role, validate_sqlite_identifier};
 
 /// Start the HTTP server programmatically
 ///
@@ -58,6 +112,7 @@ pub use validate::validate_sqlite_identifier;
 ///
 /// # Environment Variables
 /// * `DATABASE_DSN` - Database connection string (defaults to `sqlite::memory:` if not set)
+/// * `DATABASE_DSN_FILE` - File containing the DSN, used when `DATABASE_DSN` is not set
 /// * `SSH_HOST`, `SSH_PORT`, `SSH_USER`, `SSH_AUTH_TYPE` - Optional SSH tunnel config
 pub async fn start_server(
     addr: std::net::SocketAddr,
@@ -197,12 +252,8 @@ pub async fn start_server_with_listener(
             let mut prompt_router = PromptRouter::new();
             let managers = Managers::new();
 
-            // Get DATABASE_DSN from environment (defaults to in-memory SQLite)
-            let dsn = std::env::var("DATABASE_DSN")
-                .unwrap_or_else(|_| {
-                    log::info!("DATABASE_DSN not set, defaulting to sqlite::memory:");
-                    "sqlite::memory:".to_string()
-                });
+            // Get DSN from DATABASE_DSN or DATABASE_DSN_FILE (defaults to in-memory SQLite)
+            let dsn = crate::dsn::dsn_from_env()?;
 
             // Parse optional SSH tunnel configuration
             let ssh_config = parse_ssh_config_from_env()?;
@@ -233,46 +284,60 @@ pub async fn start_server_with_listener(
                 managers.register(TunnelGuard(tunnel_guard)).await;
             }
 
-            // Register all 7 database tools
+            // Register the database tools
             use crate::tools::*;
 
             let pool = db_connection.pool;
+            let metadata_pool = db_connection.metadata_pool;
             let connection_url = &db_connection.connection_url;
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                ExecuteSQLTool::new(pool.clone(), config.clone(), connection_url)?,
+                ExecuteSQLTool::new(pool.clone(), config.clone(), connection_url)?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                ListSchemasTool::new(metadata_pool.clone(), connection_url, config.clone())?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                ListSchemasTool::new(pool.clone(), connection_url, config.clone())?,
+                ListTablesTool::new(metadata_pool.clone(), connection_url, config.clone())?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                ListTablesTool::new(pool.clone(), connection_url, config.clone())?,
+                GetTableSchemaTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                GetTableSchemaTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?,
+                GetTableIndexesTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                GetTableIndexesTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?,
+                GetTableConstraintsTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                GetStoredProceduresTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?,
+                GetStoredProceduresTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
@@ -281,6 +346,67 @@ pub async fn start_server_with_listener(
                 GetPoolStatsTool::new(pool.clone(), connection_url)?,
             );
 
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                ListActiveQueriesTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                KillQueryTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_own_sessions(db_connection.own_sessions.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                SearchSchemaTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                DescribeConnectionTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                SchemaOverviewTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                PreviewWriteTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                TestConnectionTool::new(Arc::new(config.clone())),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                GetIntrospectionSQLTool::new(connection_url, Arc::new(config.clone()))?,
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                GetTableDDLTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
             Ok(RouterSet::new(tool_router, prompt_router, managers))
         })
         .with_listener(listener);
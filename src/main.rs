@@ -4,6 +4,7 @@
 // Managed by kodegend daemon, typically running on port kodegen_config::PORT_DATABASE (30442).
 //
 // Optional: DATABASE_DSN environment variable (defaults to sqlite::memory:)
+// Optional: DATABASE_DSN_FILE path to a file containing the DSN (DATABASE_DSN wins if both are set)
 // Optional: SSH_* environment variables for SSH tunnel support.
 
 use anyhow::{Result, Context};
@@ -142,12 +143,8 @@ async fn main() -> Result<()> {
             let mut prompt_router = PromptRouter::new();
             let managers = Managers::new();
 
-            // Get DATABASE_DSN from environment (defaults to SQLite in-memory)
-            let dsn = std::env::var("DATABASE_DSN")
-                .unwrap_or_else(|_| {
-                    log::info!("DATABASE_DSN not set, defaulting to sqlite::memory:");
-                    "sqlite::memory:".to_string()
-                });
+            // Get DSN from DATABASE_DSN or DATABASE_DSN_FILE (defaults to in-memory SQLite)
+            let dsn = kodegen_tools_database::dsn::dsn_from_env()?;
 
             // Parse optional SSH tunnel configuration
             let ssh_config = parse_ssh_config_from_env()?;
@@ -161,46 +158,60 @@ async fn main() -> Result<()> {
                 managers.register(TunnelGuard(tunnel_guard)).await;
             }
 
-            // Register all 7 database tools
+            // Register the database tools
             use kodegen_tools_database::tools::*;
 
             let pool = db_connection.pool;
+            let metadata_pool = db_connection.metadata_pool;
             let connection_url = &db_connection.connection_url;
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                ExecuteSQLTool::new(pool.clone(), config.clone(), connection_url)?,
+                ExecuteSQLTool::new(pool.clone(), config.clone(), connection_url)?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                ListSchemasTool::new(pool.clone(), connection_url, config.clone())?,
+                ListSchemasTool::new(metadata_pool.clone(), connection_url, config.clone())?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                ListTablesTool::new(pool.clone(), connection_url, config.clone())?,
+                ListTablesTool::new(metadata_pool.clone(), connection_url, config.clone())?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                GetTableSchemaTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?,
+                GetTableSchemaTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                GetTableIndexesTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?,
+                GetTableIndexesTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
                 tool_router,
                 prompt_router,
-                GetStoredProceduresTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?,
+                GetTableConstraintsTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                GetStoredProceduresTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
             );
 
             (tool_router, prompt_router) = register_tool(
@@ -209,6 +220,67 @@ async fn main() -> Result<()> {
                 GetPoolStatsTool::new(pool.clone(), connection_url)?,
             );
 
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                ListActiveQueriesTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                KillQueryTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_own_sessions(db_connection.own_sessions.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                SearchSchemaTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                DescribeConnectionTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                SchemaOverviewTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                PreviewWriteTool::new(pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                TestConnectionTool::new(Arc::new(config.clone())),
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                GetIntrospectionSQLTool::new(connection_url, Arc::new(config.clone()))?,
+            );
+
+            (tool_router, prompt_router) = register_tool(
+                tool_router,
+                prompt_router,
+                GetTableDDLTool::new(metadata_pool.clone(), connection_url, Arc::new(config.clone()))?
+                    .with_query_limiter(db_connection.query_limiter.clone()),
+            );
+
             Ok(RouterSet::new(tool_router, prompt_router, managers))
         })
         .run()
@@ -0,0 +1,156 @@
+//! Page through a query's results without LIMIT/OFFSET bookkeeping
+//!
+//! [`paginate`] runs the base query one page at a time, setting LIMIT and
+//! OFFSET on its AST with [`apply_page`], and stops after the first short
+//! page. Pages are only fetched as the stream is polled.
+
+use crate::error::DatabaseError;
+use crate::sql_limiter::{apply_page, has_order_by};
+use crate::tools::execute_sql::row_to_typed;
+use crate::types::DatabaseType;
+use futures::Stream;
+use kodegen_mcp_schema::database::SqlRow;
+use sqlx::AnyPool;
+
+/// Stream the results of `base_query` in pages of `page_size` rows
+///
+/// Each page is a separate query, so rows inserted or deleted between pages
+/// can shift later pages. Without an ORDER BY, which rows land on which page
+/// is up to the database; a warning is logged. An error ends the stream after
+/// it is yielded.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use futures::StreamExt;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// # async fn example(pool: &sqlx::AnyPool) -> anyhow::Result<()> {
+/// let mut pages = kodegen_tools_database::paginate(
+///     pool,
+///     DatabaseType::Postgres,
+///     "SELECT id, email FROM users ORDER BY id",
+///     500,
+/// );
+/// while let Some(page) = pages.next().await {
+///     for row in page? {
+///         println!("{:?}", row);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn paginate(
+    pool: &AnyPool,
+    db_type: DatabaseType,
+    base_query: &str,
+    page_size: usize,
+) -> impl Stream<Item = Result<Vec<SqlRow>, DatabaseError>> + use<> {
+    if !has_order_by(base_query, db_type) {
+        log::warn!(
+            "Paginating a query without ORDER BY; pages may skip or repeat rows: {}",
+            base_query.chars().take(80).collect::<String>()
+        );
+    }
+
+    let pool = pool.clone();
+    let base_query = base_query.to_string();
+    // State is the next page's offset, or None once the last page was seen
+    futures::stream::unfold(Some(0usize), move |offset| {
+        let pool = pool.clone();
+        let base_query = base_query.clone();
+        async move {
+            let offset = offset?;
+            match fetch_page(&pool, db_type, &base_query, page_size, offset).await {
+                Ok(rows) if rows.is_empty() => None,
+                Ok(rows) => {
+                    let next = (rows.len() == page_size).then_some(offset + page_size);
+                    Some((Ok(rows), next))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    })
+}
+
+/// Fetch the page starting at `offset`
+async fn fetch_page(
+    pool: &AnyPool,
+    db_type: DatabaseType,
+    base_query: &str,
+    page_size: usize,
+    offset: usize,
+) -> Result<Vec<SqlRow>, DatabaseError> {
+    let sql = apply_page(base_query, page_size, offset, db_type)?;
+    let rows = sqlx::query(&sql).fetch_all(pool).await?;
+    rows.iter().map(row_to_typed).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use kodegen_mcp_schema::database::SqlValue;
+
+    async fn fixture() -> AnyPool {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT)")
+            .execute(&pool)
+            .await
+            .expect("create table");
+        for id in [4, 1, 5, 3, 2] {
+            sqlx::query("INSERT INTO items VALUES (?, ?)")
+                .bind(id)
+                .bind(format!("item {}", id))
+                .execute(&pool)
+                .await
+                .expect("insert");
+        }
+        pool
+    }
+
+    fn ids(page: &[SqlRow]) -> Vec<i64> {
+        page.iter()
+            .map(|row| match row.columns[0].value {
+                SqlValue::Int(id) => id,
+                ref other => panic!("unexpected id {:?}", other),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pages_of_two_cover_every_row_once_in_order() {
+        let pool = fixture().await;
+        let pages: Vec<Vec<SqlRow>> =
+            paginate(&pool, DatabaseType::SQLite, "SELECT id, name FROM items ORDER BY id", 2)
+                .map(|page| page.expect("page"))
+                .collect()
+                .await;
+
+        let sizes: Vec<usize> = pages.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [2, 2, 1]);
+        let all: Vec<i64> = pages.iter().flat_map(|page| ids(page)).collect();
+        assert_eq!(all, [1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_exact_multiple_ends_on_empty_page() {
+        let pool = fixture().await;
+        let query = "SELECT id FROM items WHERE id <= 4 ORDER BY id";
+        let pages: Vec<_> = paginate(&pool, DatabaseType::SQLite, query, 2).collect().await;
+        assert_eq!(pages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_query_yields_one_error() {
+        let pool = fixture().await;
+        let query = "SELECT id FROM items LIMIT 3";
+        let pages: Vec<_> = paginate(&pool, DatabaseType::SQLite, query, 2).collect().await;
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Process-wide registry of shared connection pools (`db_share_pools`)
+//!
+//! When several tool servers run in one process against the same database,
+//! each would otherwise open its own pool. With `db_share_pools` set,
+//! `setup_database_pool` registers the pools it builds here, and later calls
+//! with the same normalized DSN and pool settings reuse them.
+//!
+//! The registry only holds weak references: a shared pool closes once the last
+//! server using it drops its `DatabaseConnection`. Entries whose pools are gone
+//! are pruned whenever a key is locked.
+
+use crate::connection::OwnSessions;
+use crate::tools::concurrency::QueryLimiter;
+use kodegen_config_manager::{ConfigManager, ConfigValue};
+use sqlx::AnyPool;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Weak};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// Settings that change how a pool behaves but are not part of its URL
+///
+/// Pools are only shared between callers that agree on all of them, so one
+/// server's validation query or session reset never applies to another's
+/// connections unexpectedly.
+const POOL_SETTING_KEYS: &[&str] = &[
+    "db_application_name",
+    "db_charset",
+    "db_metadata_affinity_connections",
+    "db_preping_idle_secs",
+    "db_queue_multiplier",
+    "db_require_tls",
+    "db_reset_session_on_release",
+    "db_sqlite_busy_timeout_ms",
+    "db_sqlite_wal",
+    "db_timezone",
+    "db_validation_query",
+    "db_verify_readonly_role",
+    "db_warmup_concurrency",
+];
+
+/// Pools built by one `setup_database_pool` call, as registered for reuse
+#[derive(Clone)]
+pub(crate) struct SharedPools {
+    pub(crate) pool: Weak<AnyPool>,
+    pub(crate) metadata_pool: Weak<AnyPool>,
+    pub(crate) connection_url: String,
+    pub(crate) query_limiter: QueryLimiter,
+    pub(crate) own_sessions: OwnSessions,
+}
+
+/// One share key's entry; `None` until a setup registers its pools
+pub(crate) type Slot = Arc<Mutex<Option<SharedPools>>>;
+
+/// Registry entries keyed by [`share_key`]
+///
+/// The map itself is only locked briefly to find or create a slot; connecting
+/// happens under the slot's own lock.
+static SHARED_POOLS: LazyLock<std::sync::Mutex<HashMap<String, Slot>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+/// Lock the registry entry for `key`
+///
+/// The caller holds the lock while it connects, so concurrent setups for the
+/// same database wait for the first pool instead of opening their own. Setups
+/// for other keys are not blocked.
+pub(crate) async fn lock(key: &str) -> OwnedMutexGuard<Option<SharedPools>> {
+    let slot = {
+        let mut slots = SHARED_POOLS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        prune(&mut slots, key);
+        slots.entry(key.to_string()).or_default().clone()
+    };
+    slot.lock_owned().await
+}
+
+/// Drop entries whose pools have closed, or that never got any
+///
+/// Slots locked by a setup in progress, and `keep`, are left alone.
+fn prune(slots: &mut HashMap<String, Slot>, keep: &str) {
+    slots.retain(|key, slot| {
+        if key == keep {
+            return true;
+        }
+        match slot.try_lock() {
+            Ok(shared) => shared.as_ref().is_some_and(|s| s.upgrade().is_some()),
+            // A setup for this key is still connecting
+            Err(_) => true,
+        }
+    });
+}
+
+/// Whether `db_share_pools` is set (default: false)
+pub fn share_pools_from_config(config: &ConfigManager) -> bool {
+    config
+        .get_value("db_share_pools")
+        .and_then(|v| match v {
+            kodegen_config_manager::ConfigValue::Boolean(b) => Some(b),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
+/// Registry key for a pool: the normalized connect URL plus its settings
+///
+/// `connection_url` is the URL after env expansion, default ports and dialect
+/// settings were folded in; `pool_settings` describes sizing and timeouts.
+pub(crate) fn share_key(connection_url: &str, pool_settings: &str, config: &ConfigManager) -> String {
+    share_key_with(connection_url, pool_settings, |setting| config.get_value(setting))
+}
+
+/// [`share_key`] using `lookup` to read the pool settings
+fn share_key_with(
+    connection_url: &str,
+    pool_settings: &str,
+    lookup: impl Fn(&str) -> Option<ConfigValue>,
+) -> String {
+    let mut key = format!("{}\n{}", connection_url, pool_settings);
+    for setting in POOL_SETTING_KEYS {
+        key.push_str(&format!("\n{}={:?}", setting, lookup(setting)));
+    }
+    key
+}
+
+impl SharedPools {
+    /// Register strong pools for sharing
+    pub(crate) fn new(
+        pool: &Arc<AnyPool>,
+        metadata_pool: &Arc<AnyPool>,
+        connection_url: String,
+        query_limiter: QueryLimiter,
+        own_sessions: OwnSessions,
+    ) -> Self {
+        Self {
+            pool: Arc::downgrade(pool),
+            metadata_pool: Arc::downgrade(metadata_pool),
+            connection_url,
+            query_limiter,
+            own_sessions,
+        }
+    }
+
+    /// Both pools, if they are still open
+    pub(crate) fn upgrade(&self) -> Option<(Arc<AnyPool>, Arc<AnyPool>)> {
+        let pool = self.pool.upgrade().filter(|pool| !pool.is_closed())?;
+        let metadata_pool = self.metadata_pool.upgrade()?;
+        Some((pool, metadata_pool))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(shared: Option<SharedPools>) -> Slot {
+        Arc::new(Mutex::new(shared))
+    }
+
+    #[tokio::test]
+    async fn test_prune_drops_dead_and_empty_entries() {
+        sqlx::any::install_default_drivers();
+        let pool = Arc::new(AnyPool::connect("sqlite::memory:").await.expect("sqlite pool"));
+        let limiter = QueryLimiter::from_config(&ConfigManager::new());
+        let shared = |pool: &Arc<AnyPool>| {
+            SharedPools::new(
+                pool,
+                pool,
+                "sqlite::memory:".to_string(),
+                limiter.clone(),
+                OwnSessions::default(),
+            )
+        };
+        let dead = {
+            let gone = Arc::new(AnyPool::connect("sqlite::memory:").await.expect("sqlite pool"));
+            shared(&gone)
+        };
+
+        let busy = slot(None);
+        let _connecting = busy.clone().lock_owned().await;
+        let mut slots = HashMap::from([
+            ("live".to_string(), slot(Some(shared(&pool)))),
+            ("dead".to_string(), slot(Some(dead))),
+            ("failed".to_string(), slot(None)),
+            ("busy".to_string(), busy),
+            ("mine".to_string(), slot(None)),
+        ]);
+        prune(&mut slots, "mine");
+
+        let mut kept: Vec<&str> = slots.keys().map(String::as_str).collect();
+        kept.sort_unstable();
+        assert_eq!(kept, ["busy", "live", "mine"]);
+    }
+
+    #[test]
+    fn test_share_key_differs_by_timezone() {
+        let url = "postgres://app@db:5432/app";
+        let with_zone = |zone: &'static str| {
+            move |setting: &str| {
+                (setting == "db_timezone").then(|| ConfigValue::String(zone.to_string()))
+            }
+        };
+        let utc = share_key_with(url, "sizing", with_zone("UTC"));
+        let berlin = share_key_with(url, "sizing", with_zone("Europe/Berlin"));
+        assert_ne!(utc, berlin);
+        assert_eq!(utc, share_key_with(url, "sizing", with_zone("UTC")));
+    }
+}
@@ -0,0 +1,453 @@
+//! Rewrite UPDATE/DELETE statements into SELECTs of the rows they would touch
+//!
+//! The target table, any joined or `FROM`/`USING` tables, and the WHERE clause
+//! are taken from the parsed statement, so the preview matches exactly the
+//! rows the write would affect. The write itself is never executed.
+//!
+//! A write that joins other tables (`UPDATE ... FROM`, `DELETE ... USING`,
+//! MySQL multi-table forms) is previewed as
+//! `SELECT * FROM target WHERE EXISTS (SELECT 1 FROM <other tables> WHERE ...)`,
+//! so a target row matched by several joined rows is listed and counted once,
+//! as the write touches it once. Inner joins are folded into that subquery;
+//! outer joins and `USING`/`NATURAL` join constraints cannot be, and are
+//! rejected.
+
+use crate::error::DatabaseError;
+use crate::sql_parser::get_dialect;
+use crate::types::DatabaseType;
+use sqlparser::ast::{
+    Expr, FromTable, JoinConstraint, JoinOperator, ObjectName, OrderByExpr, Statement,
+    TableFactor, TableWithJoins, UpdateTableFromKind,
+};
+use sqlparser::parser::Parser;
+
+/// SELECT statements previewing the rows an UPDATE/DELETE would affect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WritePreview {
+    /// "UPDATE" or "DELETE"
+    pub statement_type: &'static str,
+
+    /// Table(s) the write modifies
+    pub table: String,
+
+    /// Returns the rows of the target table(s) matching the write's predicate
+    pub select_sql: String,
+
+    /// Counts the matching rows
+    pub count_sql: String,
+}
+
+/// Build the preview SELECTs for a single UPDATE or DELETE statement
+///
+/// MySQL `ORDER BY` / `LIMIT` on a DELETE are carried over, since they narrow
+/// which rows are deleted.
+///
+/// # Errors
+///
+/// Returns `DatabaseError::QueryError` if the SQL does not parse, is not
+/// exactly one UPDATE or DELETE statement, deletes from several tables at
+/// once, or joins its target in a way the preview cannot express (see the
+/// module docs).
+///
+/// # Example
+///
+/// ```rust
+/// use kodegen_tools_database::preview::preview_write;
+/// use kodegen_tools_database::types::DatabaseType;
+///
+/// let preview = preview_write("DELETE FROM t WHERE x > 5", DatabaseType::Postgres).unwrap();
+/// assert_eq!(preview.select_sql, "SELECT * FROM t WHERE x > 5");
+/// assert_eq!(preview.count_sql, "SELECT COUNT(*) AS affected_rows FROM t WHERE x > 5");
+/// ```
+pub fn preview_write(sql: &str, db_type: DatabaseType) -> Result<WritePreview, DatabaseError> {
+    let dialect = get_dialect(db_type);
+    let statements = Parser::parse_sql(&*dialect, sql)
+        .map_err(|e| DatabaseError::QueryError(format!("SQL parse error: {}", e)))?;
+    let [statement] = statements.as_slice() else {
+        return Err(DatabaseError::QueryError(format!(
+            "Expected a single UPDATE or DELETE statement, got {} statements",
+            statements.len()
+        )));
+    };
+
+    match statement {
+        Statement::Update(update) => {
+            let mut sources = vec![update.table.clone()];
+            if let Some(
+                UpdateTableFromKind::BeforeSet(from) | UpdateTableFromKind::AfterSet(from),
+            ) = &update.from
+            {
+                sources.extend(from.iter().cloned());
+            }
+            let targets = vec![relation_name(&update.table.relation)];
+            build_preview(
+                "UPDATE",
+                &targets,
+                &sources,
+                update.selection.as_ref(),
+                &[],
+                None,
+            )
+        }
+        Statement::Delete(delete) => {
+            let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) =
+                &delete.from;
+            let (targets, sources) = match &delete.using {
+                // MySQL `DELETE FROM t1 USING t1 JOIN t2`: FROM names targets, USING is the source
+                Some(using) if matches!(db_type, DatabaseType::MySQL | DatabaseType::MariaDB) => (
+                    from.iter().map(|t| relation_name(&t.relation)).collect(),
+                    using.clone(),
+                ),
+                // PostgreSQL `DELETE FROM t USING u`: USING adds tables to join against
+                using => {
+                    let mut sources = from.clone();
+                    sources.extend(using.iter().flatten().cloned());
+                    let targets = if delete.tables.is_empty() {
+                        from.first().map(|t| relation_name(&t.relation)).into_iter().collect()
+                    } else {
+                        // MySQL `DELETE t1 FROM t1 JOIN t2`
+                        delete.tables.iter().map(ObjectName::to_string).collect()
+                    };
+                    (targets, sources)
+                }
+            };
+            build_preview(
+                "DELETE",
+                &targets,
+                &sources,
+                delete.selection.as_ref(),
+                &delete.order_by,
+                delete.limit.as_ref(),
+            )
+        }
+        _ => Err(DatabaseError::QueryError(
+            "Only UPDATE and DELETE statements can be previewed".to_string(),
+        )),
+    }
+}
+
+/// Assemble the SELECT and COUNT statements from the write's parts
+fn build_preview(
+    statement_type: &'static str,
+    targets: &[String],
+    sources: &[TableWithJoins],
+    selection: Option<&Expr>,
+    order_by: &[OrderByExpr],
+    limit: Option<&Expr>,
+) -> Result<WritePreview, DatabaseError> {
+    let [target] = targets else {
+        return Err(DatabaseError::QueryError(format!(
+            "Cannot preview a {} that modifies several tables ({}); preview each table separately",
+            statement_type,
+            targets.join(", ")
+        )));
+    };
+
+    let tail = if sources.len() == 1 && sources[0].joins.is_empty() {
+        let mut tail = format!("FROM {}", sources[0]);
+        if let Some(selection) = selection {
+            tail.push_str(&format!(" WHERE {}", selection));
+        }
+        tail
+    } else {
+        let (relation, others, mut conditions) = split_target(target, sources)?;
+        conditions.extend(selection.map(ToString::to_string));
+        let mut exists = format!("SELECT 1 FROM {}", others.join(", "));
+        match conditions.as_slice() {
+            [] => {}
+            [condition] => exists.push_str(&format!(" WHERE {}", condition)),
+            conditions => exists.push_str(&format!(
+                " WHERE {}",
+                conditions.iter().map(|c| format!("({})", c)).collect::<Vec<_>>().join(" AND ")
+            )),
+        }
+        format!("FROM {} WHERE EXISTS ({})", relation, exists)
+    };
+
+    let mut select_sql = format!("SELECT * {}", tail);
+    if !order_by.is_empty() {
+        select_sql.push_str(&format!(
+            " ORDER BY {}",
+            order_by.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let count_sql = match limit {
+        Some(limit) => {
+            select_sql.push_str(&format!(" LIMIT {}", limit));
+            format!("SELECT COUNT(*) AS affected_rows FROM ({}) AS preview_rows", select_sql)
+        }
+        None => format!("SELECT COUNT(*) AS affected_rows {}", tail),
+    };
+
+    Ok(WritePreview {
+        statement_type,
+        table: target.clone(),
+        select_sql,
+        count_sql,
+    })
+}
+
+/// Separate the target relation from the tables it is joined with
+///
+/// Returns the target relation, the remaining `FROM` items, and the `ON`
+/// conditions of the inner joins that were flattened to reach the target.
+fn split_target(
+    target: &str,
+    sources: &[TableWithJoins],
+) -> Result<(String, Vec<String>, Vec<String>), DatabaseError> {
+    let mut relation = None;
+    let mut others = Vec::new();
+    let mut conditions = Vec::new();
+
+    for source in sources {
+        let joined = std::iter::once(&source.relation)
+            .chain(source.joins.iter().map(|join| &join.relation))
+            .any(|factor| relation_name(factor) == target);
+        if !joined || relation.is_some() {
+            others.push(source.to_string());
+            continue;
+        }
+
+        // The target's join tree is flattened into FROM items plus conditions
+        for join in &source.joins {
+            match &join.join_operator {
+                JoinOperator::Join(constraint) | JoinOperator::Inner(constraint) => {
+                    match constraint {
+                        JoinConstraint::On(expr) => conditions.push(expr.to_string()),
+                        JoinConstraint::None => {}
+                        JoinConstraint::Using(_) | JoinConstraint::Natural => {
+                            return Err(unsupported_join(target, join));
+                        }
+                    }
+                }
+                JoinOperator::CrossJoin { .. } => {}
+                _ => return Err(unsupported_join(target, join)),
+            }
+        }
+        for factor in std::iter::once(&source.relation)
+            .chain(source.joins.iter().map(|join| &join.relation))
+        {
+            if relation.is_none() && relation_name(factor) == target {
+                relation = Some(factor.to_string());
+            } else {
+                others.push(factor.to_string());
+            }
+        }
+    }
+
+    let relation = relation.ok_or_else(|| {
+        DatabaseError::QueryError(format!(
+            "Target table {} not found among the joined tables",
+            target
+        ))
+    })?;
+    Ok((relation, others, conditions))
+}
+
+/// Error for a join the EXISTS rewrite cannot preserve
+fn unsupported_join(target: &str, join: &sqlparser::ast::Join) -> DatabaseError {
+    DatabaseError::QueryError(format!(
+        "Cannot preview a write whose target {} is joined with '{}': only inner joins with ON \
+         conditions can be folded into the preview; rewrite the write with WHERE EXISTS",
+        target, join
+    ))
+}
+
+/// Name a relation is referred to by: its alias, else its table name
+fn relation_name(relation: &TableFactor) -> String {
+    match relation {
+        TableFactor::Table {
+            alias: Some(alias), ..
+        } => alias.name.to_string(),
+        TableFactor::Table { name, .. } => name.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Row;
+
+    #[test]
+    fn test_delete_rewritten_to_select() {
+        let preview = preview_write("DELETE FROM t WHERE x > 5", DatabaseType::Postgres)
+            .expect("preview");
+        assert_eq!(preview.statement_type, "DELETE");
+        assert_eq!(preview.table, "t");
+        assert_eq!(preview.select_sql, "SELECT * FROM t WHERE x > 5");
+    }
+
+    #[test]
+    fn test_update_with_from_selects_target_columns() {
+        let preview = preview_write(
+            "UPDATE orders AS o SET status = 'void' FROM customers AS c \
+             WHERE o.customer_id = c.id AND c.banned",
+            DatabaseType::Postgres,
+        )
+        .expect("preview");
+        assert_eq!(preview.table, "o");
+        assert_eq!(
+            preview.select_sql,
+            "SELECT * FROM orders AS o WHERE EXISTS \
+             (SELECT 1 FROM customers AS c WHERE o.customer_id = c.id AND c.banned)"
+        );
+        assert_eq!(
+            preview.count_sql,
+            "SELECT COUNT(*) AS affected_rows FROM orders AS o WHERE EXISTS \
+             (SELECT 1 FROM customers AS c WHERE o.customer_id = c.id AND c.banned)"
+        );
+    }
+
+    #[test]
+    fn test_postgres_delete_using() {
+        let preview = preview_write(
+            "DELETE FROM orders USING customers WHERE orders.customer_id = customers.id",
+            DatabaseType::Postgres,
+        )
+        .expect("preview");
+        assert_eq!(preview.table, "orders");
+        assert_eq!(
+            preview.select_sql,
+            "SELECT * FROM orders WHERE EXISTS \
+             (SELECT 1 FROM customers WHERE orders.customer_id = customers.id)"
+        );
+    }
+
+    #[test]
+    fn test_mysql_multi_table_delete() {
+        let preview = preview_write(
+            "DELETE t1 FROM t1 JOIN t2 ON t1.id = t2.ref WHERE t2.stale = 1",
+            DatabaseType::MySQL,
+        )
+        .expect("preview");
+        assert_eq!(
+            preview.select_sql,
+            "SELECT * FROM t1 WHERE EXISTS \
+             (SELECT 1 FROM t2 WHERE (t1.id = t2.ref) AND (t2.stale = 1))"
+        );
+    }
+
+    #[test]
+    fn test_unpreviewable_joins_rejected() {
+        let outer = preview_write(
+            "DELETE t1 FROM t1 LEFT JOIN t2 ON t1.id = t2.ref WHERE t2.ref IS NULL",
+            DatabaseType::MySQL,
+        );
+        assert!(outer.is_err());
+        let several = preview_write(
+            "DELETE t1, t2 FROM t1 JOIN t2 ON t1.id = t2.ref",
+            DatabaseType::MySQL,
+        );
+        assert!(several.is_err());
+    }
+
+    #[test]
+    fn test_mysql_delete_limit_kept() {
+        let preview = preview_write(
+            "DELETE FROM logs WHERE level = 'debug' ORDER BY id LIMIT 100",
+            DatabaseType::MySQL,
+        )
+        .expect("preview");
+        assert_eq!(
+            preview.select_sql,
+            "SELECT * FROM logs WHERE level = 'debug' ORDER BY id LIMIT 100"
+        );
+        assert!(preview.count_sql.contains("AS preview_rows"));
+    }
+
+    #[test]
+    fn test_non_write_rejected() {
+        assert!(preview_write("SELECT * FROM t", DatabaseType::Postgres).is_err());
+        assert!(preview_write("DELETE FROM a; DELETE FROM b", DatabaseType::Postgres).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_preview_matches_deleted_rows() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY, x INTEGER NOT NULL)")
+            .execute(&pool)
+            .await
+            .expect("create table");
+        sqlx::query("INSERT INTO t (id, x) VALUES (1, 3), (2, 6), (3, 9), (4, 5)")
+            .execute(&pool)
+            .await
+            .expect("insert rows");
+
+        let delete = "DELETE FROM t WHERE x > 5";
+        let preview = preview_write(delete, DatabaseType::SQLite).expect("preview");
+        let previewed: Vec<i64> = sqlx::query(&preview.select_sql)
+            .fetch_all(&pool)
+            .await
+            .expect("preview rows")
+            .iter()
+            .map(|row| row.try_get("id").expect("id"))
+            .collect();
+        let count: i64 = sqlx::query_scalar(&preview.count_sql)
+            .fetch_one(&pool)
+            .await
+            .expect("count");
+
+        let deleted = sqlx::query(delete)
+            .execute(&pool)
+            .await
+            .expect("delete")
+            .rows_affected();
+        let remaining: Vec<i64> = sqlx::query_scalar("SELECT id FROM t ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .expect("remaining");
+
+        assert_eq!(previewed, vec![2, 3]);
+        assert_eq!(count as u64, deleted);
+        assert!(previewed.iter().all(|id| !remaining.contains(id)));
+    }
+
+    #[tokio::test]
+    async fn test_joined_update_counts_each_target_row_once() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        for sql in [
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER, status TEXT)",
+            "CREATE TABLE flags (customer_id INTEGER, reason TEXT)",
+            "INSERT INTO orders VALUES (1, 10, 'open'), (2, 20, 'open'), (3, 30, 'open')",
+            // Customer 10 is flagged twice, so a join would see order 1 twice
+            "INSERT INTO flags VALUES (10, 'fraud'), (10, 'chargeback'), (20, 'fraud')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.expect("fixture");
+        }
+
+        let update = "UPDATE orders SET status = 'void' FROM flags \
+                      WHERE orders.customer_id = flags.customer_id";
+        let preview = preview_write(update, DatabaseType::SQLite).expect("preview");
+        let previewed: Vec<i64> = sqlx::query(&preview.select_sql)
+            .fetch_all(&pool)
+            .await
+            .expect("preview rows")
+            .iter()
+            .map(|row| row.try_get("id").expect("id"))
+            .collect();
+        let count: i64 = sqlx::query_scalar(&preview.count_sql)
+            .fetch_one(&pool)
+            .await
+            .expect("count");
+        let updated = sqlx::query(update)
+            .execute(&pool)
+            .await
+            .expect("update")
+            .rows_affected();
+
+        assert_eq!(previewed, vec![1, 2]);
+        assert_eq!(count, 2);
+        assert_eq!(count as u64, updated);
+    }
+}
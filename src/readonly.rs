@@ -44,6 +44,36 @@ fn get_dialect(db_type: DatabaseType) -> Box<dyn Dialect> {
 /// # }
 /// ```
 pub fn validate_readonly_sql(sql: &str, db_type: DatabaseType) -> Result<(), DatabaseError> {
+    validate_readonly_sql_with_max_depth(sql, db_type, DEFAULT_MAX_VALIDATION_DEPTH)
+}
+
+/// [`validate_readonly_sql`] with an explicit nesting limit (`db_readonly_max_depth`)
+///
+/// Each statement, query, set operation, table factor and expression the
+/// validator descends into counts as one level; a chain of binary operators
+/// or set operations (`a OR b OR ...`, `... UNION ...`) counts once, however
+/// long. A query nested deeper than `max_depth` is rejected, since it could
+/// not be verified without risking a stack overflow.
+///
+/// The parser itself refuses SQL nested more than 50 levels deep (a
+/// `SQL parse error`) before validation starts. One parser level is a few
+/// validator levels (a derived table is a table factor, query and body), so
+/// limits well above that, like the default, are not reached by SQL the
+/// parser accepts.
+///
+/// # Example
+/// ```
+/// # use kodegen_tools_database::readonly::validate_readonly_sql_with_max_depth;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// let nested = "SELECT * FROM (SELECT * FROM (SELECT * FROM (SELECT 1) a) b) c";
+/// assert!(validate_readonly_sql_with_max_depth(nested, DatabaseType::Postgres, 64).is_ok());
+/// assert!(validate_readonly_sql_with_max_depth(nested, DatabaseType::Postgres, 8).is_err());
+/// ```
+pub fn validate_readonly_sql_with_max_depth(
+    sql: &str,
+    db_type: DatabaseType,
+    max_depth: usize,
+) -> Result<(), DatabaseError> {
     let dialect = get_dialect(db_type);
 
     // Parse SQL into AST statements
@@ -51,26 +81,56 @@ pub fn validate_readonly_sql(sql: &str, db_type: DatabaseType) -> Result<(), Dat
         .map_err(|e| DatabaseError::QueryError(format!("SQL parse error: {}", e)))?;
 
     // Validate each statement recursively
+    let depth = Depth { level: 0, max: max_depth };
     for statement in statements {
-        validate_statement_readonly(&statement, db_type)?;
+        validate_statement_readonly(&statement, depth)?;
     }
 
     Ok(())
 }
 
+/// Default for `db_readonly_max_depth`
+///
+/// Far deeper than hand-written SQL nests, and well within a thread's stack.
+/// Operator chains do not count as nesting (see
+/// [`validate_readonly_sql_with_max_depth`]).
+pub const DEFAULT_MAX_VALIDATION_DEPTH: usize = 256;
+
+/// Nesting level of the node being validated
+#[derive(Debug, Clone, Copy)]
+struct Depth {
+    level: usize,
+    max: usize,
+}
+
+impl Depth {
+    /// One level further down, or an error past the limit
+    fn deeper(self) -> Result<Self, DatabaseError> {
+        if self.level >= self.max {
+            return Err(DatabaseError::ReadOnlyViolation(format!(
+                "Query nesting exceeds the read-only validation limit of {} levels \
+                 (db_readonly_max_depth); it cannot be verified as read-only",
+                self.max
+            )));
+        }
+        Ok(Self {
+            level: self.level + 1,
+            ..self
+        })
+    }
+}
+
 /// Validate a top-level Statement
-fn validate_statement_readonly(
-    stmt: &Statement,
-    db_type: DatabaseType,
-) -> Result<(), DatabaseError> {
+fn validate_statement_readonly(stmt: &Statement, depth: Depth) -> Result<(), DatabaseError> {
+    let depth = depth.deeper()?;
     match stmt {
         // Read-only statements
         Statement::Query(query) => {
-            validate_query_readonly(query, db_type)?;
+            validate_query_readonly(query, depth)?;
         }
         Statement::Explain { statement, .. } => {
             // EXPLAIN can wrap any statement, validate the inner statement
-            validate_statement_readonly(statement, db_type)?;
+            validate_statement_readonly(statement, depth)?;
         }
 
         // Show statements are read-only
@@ -167,46 +227,57 @@ fn validate_statement_readonly(
 }
 
 /// Validate a Query (handles CTEs and query body)
-fn validate_query_readonly(query: &Query, db_type: DatabaseType) -> Result<(), DatabaseError> {
+fn validate_query_readonly(query: &Query, depth: Depth) -> Result<(), DatabaseError> {
+    let depth = depth.deeper()?;
     // Validate CTEs (WITH clause)
     if let Some(with) = &query.with {
-        validate_with_readonly(with, db_type)?;
+        validate_with_readonly(with, depth)?;
     }
 
     // Validate main query body
-    validate_set_expr_readonly(&query.body, db_type)?;
+    validate_set_expr_readonly(&query.body, depth)?;
 
     Ok(())
 }
 
 /// Validate WITH clause (CTEs)
-fn validate_with_readonly(with: &With, db_type: DatabaseType) -> Result<(), DatabaseError> {
+fn validate_with_readonly(with: &With, depth: Depth) -> Result<(), DatabaseError> {
     for cte in &with.cte_tables {
-        validate_cte_readonly(cte, db_type)?;
+        validate_cte_readonly(cte, depth)?;
     }
     Ok(())
 }
 
 /// Validate a single CTE
-fn validate_cte_readonly(cte: &Cte, db_type: DatabaseType) -> Result<(), DatabaseError> {
+fn validate_cte_readonly(cte: &Cte, depth: Depth) -> Result<(), DatabaseError> {
     // Each CTE contains a full query that must be validated
-    validate_query_readonly(&cte.query, db_type)?;
+    validate_query_readonly(&cte.query, depth)?;
     Ok(())
 }
 
 /// Validate a SetExpr (query body or set operation)
-fn validate_set_expr_readonly(expr: &SetExpr, db_type: DatabaseType) -> Result<(), DatabaseError> {
+fn validate_set_expr_readonly(expr: &SetExpr, depth: Depth) -> Result<(), DatabaseError> {
+    let depth = depth.deeper()?;
     match expr {
         SetExpr::Select(select) => {
-            validate_select_readonly(select, db_type)?;
+            validate_select_readonly(select, depth)?;
         }
         SetExpr::Query(query) => {
-            validate_query_readonly(query, db_type)?;
+            validate_query_readonly(query, depth)?;
         }
         SetExpr::SetOperation { left, right, .. } => {
-            // UNION, EXCEPT, INTERSECT
-            validate_set_expr_readonly(left, db_type)?;
-            validate_set_expr_readonly(right, db_type)?;
+            // UNION, EXCEPT, INTERSECT. The parser builds `a UNION b UNION ...`
+            // left-deep in a loop, so walk the chain without adding levels.
+            validate_set_expr_readonly(right, depth)?;
+            let mut left = left;
+            while let SetExpr::SetOperation {
+                left: next, right, ..
+            } = &**left
+            {
+                validate_set_expr_readonly(right, depth)?;
+                left = next;
+            }
+            validate_set_expr_readonly(left, depth)?;
         }
         SetExpr::Values(_) => {
             // VALUES clause is read-only (just data)
@@ -240,65 +311,62 @@ fn validate_set_expr_readonly(expr: &SetExpr, db_type: DatabaseType) -> Result<(
 }
 
 /// Validate a SELECT statement
-fn validate_select_readonly(select: &Select, db_type: DatabaseType) -> Result<(), DatabaseError> {
+fn validate_select_readonly(select: &Select, depth: Depth) -> Result<(), DatabaseError> {
     // Validate SELECT projection (select list items)
     for item in &select.projection {
-        validate_select_item_readonly(item, db_type)?;
+        validate_select_item_readonly(item, depth)?;
     }
 
     // Validate FROM clause (table factors and joins)
     for table_with_joins in &select.from {
-        validate_table_with_joins_readonly(table_with_joins, db_type)?;
+        validate_table_with_joins_readonly(table_with_joins, depth)?;
     }
 
     // Validate WHERE clause
     if let Some(expr) = &select.selection {
-        validate_expr_readonly(expr, db_type)?;
+        validate_expr_readonly(expr, depth)?;
     }
 
     // Validate HAVING clause
     if let Some(expr) = &select.having {
-        validate_expr_readonly(expr, db_type)?;
+        validate_expr_readonly(expr, depth)?;
     }
 
     // Validate QUALIFY clause (Snowflake)
     if let Some(expr) = &select.qualify {
-        validate_expr_readonly(expr, db_type)?;
+        validate_expr_readonly(expr, depth)?;
     }
 
     // Validate PREWHERE clause (ClickHouse)
     if let Some(expr) = &select.prewhere {
-        validate_expr_readonly(expr, db_type)?;
+        validate_expr_readonly(expr, depth)?;
     }
 
     // Validate GROUP BY expressions
-    validate_group_by_readonly(&select.group_by, db_type)?;
+    validate_group_by_readonly(&select.group_by, depth)?;
 
     // Validate CLUSTER BY, DISTRIBUTE BY, SORT BY (Hive)
     for expr in &select.cluster_by {
-        validate_expr_readonly(expr, db_type)?;
+        validate_expr_readonly(expr, depth)?;
     }
     for expr in &select.distribute_by {
-        validate_expr_readonly(expr, db_type)?;
+        validate_expr_readonly(expr, depth)?;
     }
     for expr in &select.sort_by {
-        validate_expr_readonly(&expr.expr, db_type)?;
+        validate_expr_readonly(&expr.expr, depth)?;
     }
 
     Ok(())
 }
 
 /// Validate a SELECT list item
-fn validate_select_item_readonly(
-    item: &SelectItem,
-    db_type: DatabaseType,
-) -> Result<(), DatabaseError> {
+fn validate_select_item_readonly(item: &SelectItem, depth: Depth) -> Result<(), DatabaseError> {
     match item {
         SelectItem::UnnamedExpr(expr) => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         SelectItem::ExprWithAlias { expr, .. } => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         SelectItem::QualifiedWildcard(..) | SelectItem::Wildcard(..) => {
             // Wildcards are safe
@@ -308,15 +376,12 @@ fn validate_select_item_readonly(
 }
 
 /// Validate GROUP BY clause
-fn validate_group_by_readonly(
-    group_by: &GroupByExpr,
-    db_type: DatabaseType,
-) -> Result<(), DatabaseError> {
+fn validate_group_by_readonly(group_by: &GroupByExpr, depth: Depth) -> Result<(), DatabaseError> {
     match group_by {
         GroupByExpr::All(..) => {}
         GroupByExpr::Expressions(exprs, ..) => {
             for expr in exprs {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
         }
     }
@@ -326,14 +391,14 @@ fn validate_group_by_readonly(
 /// Validate table with joins (FROM clause element)
 fn validate_table_with_joins_readonly(
     table_with_joins: &TableWithJoins,
-    db_type: DatabaseType,
+    depth: Depth,
 ) -> Result<(), DatabaseError> {
     // Validate main table
-    validate_table_factor_readonly(&table_with_joins.relation, db_type)?;
+    validate_table_factor_readonly(&table_with_joins.relation, depth)?;
 
     // Validate joined tables
     for join in &table_with_joins.joins {
-        validate_table_factor_readonly(&join.relation, db_type)?;
+        validate_table_factor_readonly(&join.relation, depth)?;
 
         // Validate join condition if present
         match &join.join_operator {
@@ -350,16 +415,16 @@ fn validate_table_with_joins_readonly(
             | sqlparser::ast::JoinOperator::LeftAnti(constraint)
             | sqlparser::ast::JoinOperator::RightAnti(constraint) => {
                 if let JoinConstraint::On(expr) = constraint {
-                    validate_expr_readonly(expr, db_type)?;
+                    validate_expr_readonly(expr, depth)?;
                 }
             }
             sqlparser::ast::JoinOperator::AsOf {
                 match_condition,
                 constraint,
             } => {
-                validate_expr_readonly(match_condition, db_type)?;
+                validate_expr_readonly(match_condition, depth)?;
                 if let JoinConstraint::On(expr) = constraint {
-                    validate_expr_readonly(expr, db_type)?;
+                    validate_expr_readonly(expr, depth)?;
                 }
             }
             _ => {
@@ -372,39 +437,37 @@ fn validate_table_with_joins_readonly(
 }
 
 /// Validate a table factor (table reference or derived table)
-fn validate_table_factor_readonly(
-    factor: &TableFactor,
-    db_type: DatabaseType,
-) -> Result<(), DatabaseError> {
+fn validate_table_factor_readonly(factor: &TableFactor, depth: Depth) -> Result<(), DatabaseError> {
+    let depth = depth.deeper()?;
     match factor {
         TableFactor::Table { .. } => {
             // Regular table reference is safe
         }
         TableFactor::Derived { subquery, .. } => {
             // CRITICAL: Derived tables contain subqueries
-            validate_query_readonly(subquery, db_type)?;
+            validate_query_readonly(subquery, depth)?;
         }
         TableFactor::Function { args, .. } => {
             // Table-valued functions may have expression arguments
             for arg in args {
-                validate_function_arg_readonly(arg, db_type)?;
+                validate_function_arg_readonly(arg, depth)?;
             }
         }
         TableFactor::UNNEST { array_exprs, .. } => {
             // UNNEST expressions
             for expr in array_exprs {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
         }
         TableFactor::NestedJoin {
             table_with_joins, ..
         } => {
             // Nested joins
-            validate_table_with_joins_readonly(table_with_joins, db_type)?;
+            validate_table_with_joins_readonly(table_with_joins, depth)?;
         }
         TableFactor::Pivot { table, .. } | TableFactor::Unpivot { table, .. } => {
             // Pivot/Unpivot base tables
-            validate_table_factor_readonly(table, db_type)?;
+            validate_table_factor_readonly(table, depth)?;
         }
         _ => {
             // Other table factor types (JSON tables, etc.) - be conservative
@@ -414,17 +477,14 @@ fn validate_table_factor_readonly(
 }
 
 /// Validate function argument (may contain expressions)
-fn validate_function_arg_readonly(
-    arg: &FunctionArg,
-    db_type: DatabaseType,
-) -> Result<(), DatabaseError> {
+fn validate_function_arg_readonly(arg: &FunctionArg, depth: Depth) -> Result<(), DatabaseError> {
     match arg {
         FunctionArg::Unnamed(arg_expr)
         | FunctionArg::Named { arg: arg_expr, .. }
         | FunctionArg::ExprNamed { arg: arg_expr, .. } => {
             // Extract the actual Expr from FunctionArgExpr
             if let FunctionArgExpr::Expr(expr) = arg_expr {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
             // QualifiedWildcard and Wildcard are safe (no nested queries)
         }
@@ -433,32 +493,44 @@ fn validate_function_arg_readonly(
 }
 
 /// Validate an expression (handles subqueries and nested expressions)
-fn validate_expr_readonly(expr: &Expr, db_type: DatabaseType) -> Result<(), DatabaseError> {
+fn validate_expr_readonly(expr: &Expr, depth: Depth) -> Result<(), DatabaseError> {
+    let depth = depth.deeper()?;
     match expr {
         // CRITICAL: Expression subqueries
         Expr::Subquery(query) => {
-            validate_query_readonly(query, db_type)?;
+            validate_query_readonly(query, depth)?;
         }
         Expr::InSubquery { subquery, .. } => {
-            validate_query_readonly(subquery, db_type)?;
+            validate_query_readonly(subquery, depth)?;
         }
         Expr::Exists { subquery, .. } => {
-            validate_query_readonly(subquery, db_type)?;
+            validate_query_readonly(subquery, depth)?;
         }
 
         // Recursive expression types
         Expr::BinaryOp { left, right, .. } => {
-            validate_expr_readonly(left, db_type)?;
-            validate_expr_readonly(right, db_type)?;
+            // The parser builds `a OR b OR ...` (and `AND`, `+`, `||`) left-deep
+            // in a loop rather than by recursion, so a long generated chain is
+            // not nesting: walk it without adding levels.
+            validate_expr_readonly(right, depth)?;
+            let mut left = left;
+            while let Expr::BinaryOp {
+                left: next, right, ..
+            } = &**left
+            {
+                validate_expr_readonly(right, depth)?;
+                left = next;
+            }
+            validate_expr_readonly(left, depth)?;
         }
         Expr::UnaryOp { expr, .. } => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         Expr::Cast { expr, .. } => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         Expr::Extract { expr, .. } => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         Expr::Substring {
             expr,
@@ -466,16 +538,16 @@ fn validate_expr_readonly(expr: &Expr, db_type: DatabaseType) -> Result<(), Data
             substring_for,
             ..
         } => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
             if let Some(from_expr) = substring_from {
-                validate_expr_readonly(from_expr, db_type)?;
+                validate_expr_readonly(from_expr, depth)?;
             }
             if let Some(for_expr) = substring_for {
-                validate_expr_readonly(for_expr, db_type)?;
+                validate_expr_readonly(for_expr, depth)?;
             }
         }
         Expr::Nested(expr) => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         Expr::Case {
             operand,
@@ -485,16 +557,16 @@ fn validate_expr_readonly(expr: &Expr, db_type: DatabaseType) -> Result<(), Data
         } => {
             // Validate the operand if present
             if let Some(expr) = operand {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
             // Validate each WHEN condition and result
             for case_when in conditions {
-                validate_expr_readonly(&case_when.condition, db_type)?;
-                validate_expr_readonly(&case_when.result, db_type)?;
+                validate_expr_readonly(&case_when.condition, depth)?;
+                validate_expr_readonly(&case_when.result, depth)?;
             }
             // Validate ELSE result if present
             if let Some(expr) = else_result {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
         }
         Expr::Function(func) => {
@@ -502,12 +574,12 @@ fn validate_expr_readonly(expr: &Expr, db_type: DatabaseType) -> Result<(), Data
             match &func.args {
                 sqlparser::ast::FunctionArguments::List(arg_list) => {
                     for arg in &arg_list.args {
-                        validate_function_arg_readonly(arg, db_type)?;
+                        validate_function_arg_readonly(arg, depth)?;
                     }
                 }
                 sqlparser::ast::FunctionArguments::Subquery(query) => {
                     // Function with subquery argument
-                    validate_query_readonly(query, db_type)?;
+                    validate_query_readonly(query, depth)?;
                 }
                 sqlparser::ast::FunctionArguments::None => {
                     // No arguments (e.g., CURRENT_TIMESTAMP)
@@ -515,17 +587,17 @@ fn validate_expr_readonly(expr: &Expr, db_type: DatabaseType) -> Result<(), Data
             }
         }
         Expr::InList { expr, list, .. } => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
             for item in list {
-                validate_expr_readonly(item, db_type)?;
+                validate_expr_readonly(item, depth)?;
             }
         }
         Expr::Between {
             expr, low, high, ..
         } => {
-            validate_expr_readonly(expr, db_type)?;
-            validate_expr_readonly(low, db_type)?;
-            validate_expr_readonly(high, db_type)?;
+            validate_expr_readonly(expr, depth)?;
+            validate_expr_readonly(low, depth)?;
+            validate_expr_readonly(high, depth)?;
         }
         Expr::IsNull(expr)
         | Expr::IsNotNull(expr)
@@ -535,22 +607,22 @@ fn validate_expr_readonly(expr: &Expr, db_type: DatabaseType) -> Result<(), Data
         | Expr::IsNotFalse(expr)
         | Expr::IsUnknown(expr)
         | Expr::IsNotUnknown(expr) => {
-            validate_expr_readonly(expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
         }
         Expr::InUnnest {
             expr, array_expr, ..
         } => {
-            validate_expr_readonly(expr, db_type)?;
-            validate_expr_readonly(array_expr, db_type)?;
+            validate_expr_readonly(expr, depth)?;
+            validate_expr_readonly(array_expr, depth)?;
         }
         Expr::Tuple(exprs) => {
             for expr in exprs {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
         }
         Expr::Array(arr) => {
             for expr in &arr.elem {
-                validate_expr_readonly(expr, db_type)?;
+                validate_expr_readonly(expr, depth)?;
             }
         }
 
@@ -814,4 +886,77 @@ mod tests {
         assert!(result.is_err(), "Should block REVOKE");
         assert!(result.unwrap_err().to_string().contains("REVOKE"));
     }
+
+    /// `levels` derived tables, each wrapping the next
+    fn nested_subqueries(levels: usize) -> String {
+        let mut sql = "SELECT 1".to_string();
+        for level in 0..levels {
+            sql = format!("SELECT * FROM ({}) t{}", sql, level);
+        }
+        sql
+    }
+
+    #[test]
+    fn test_depth_limit_rejects_deep_nesting() {
+        let sql = nested_subqueries(20);
+        let err = validate_readonly_sql_with_max_depth(&sql, DatabaseType::Postgres, 16)
+            .expect_err("nesting exceeds the limit");
+        assert!(matches!(err, DatabaseError::ReadOnlyViolation(_)), "{}", err);
+        assert!(err.to_string().contains("db_readonly_max_depth"), "{}", err);
+
+        assert!(validate_readonly_sql(&nested_subqueries(10), DatabaseType::Postgres).is_ok());
+    }
+
+    #[test]
+    fn test_long_operator_chains_are_not_nesting() {
+        let terms: Vec<String> = (0..300).map(|id| format!("id = {}", id)).collect();
+        let sql = format!("SELECT * FROM users WHERE {}", terms.join(" OR "));
+        validate_readonly_sql(&sql, DatabaseType::Postgres).expect("long OR chain is read-only");
+
+        let sql = vec!["SELECT 1"; 300].join(" UNION ALL ");
+        validate_readonly_sql(&sql, DatabaseType::Postgres).expect("long UNION is read-only");
+
+        // Every term of the chain is still checked, down to the first
+        let sql = format!(
+            "SELECT * FROM users WHERE id IN (SELECT id FROM (DELETE FROM t RETURNING id) d) OR {}",
+            terms.join(" OR ")
+        );
+        let err = validate_readonly_sql(&sql, DatabaseType::Postgres).expect_err("hidden DELETE");
+        assert!(
+            matches!(&err, DatabaseError::ReadOnlyViolation(msg) if msg.contains("DELETE")),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_pathological_nesting_is_a_clean_error() {
+        // Parses (within sqlparser's limit of 50) but nests deeper than max_depth
+        let sql = nested_subqueries(30);
+        let err = validate_readonly_sql_with_max_depth(&sql, DatabaseType::Postgres, 20)
+            .expect_err("nesting exceeds max_depth");
+        assert!(
+            matches!(&err, DatabaseError::ReadOnlyViolation(msg)
+                if msg.contains("limit of 20 levels (db_readonly_max_depth)")),
+            "{}",
+            err
+        );
+
+        // Far beyond the parser's limit: refused while parsing, without overflowing the stack
+        let sql = nested_subqueries(5000);
+        let err = validate_readonly_sql(&sql, DatabaseType::Postgres).expect_err("too deep");
+        assert!(
+            matches!(&err, DatabaseError::QueryError(msg) if msg.contains("recursion limit")),
+            "{}",
+            err
+        );
+
+        let parens = format!("SELECT {}1{}", "(".repeat(5000), ")".repeat(5000));
+        let err = validate_readonly_sql(&parens, DatabaseType::MySQL).expect_err("too deep");
+        assert!(
+            matches!(&err, DatabaseError::QueryError(msg) if msg.contains("recursion limit")),
+            "{}",
+            err
+        );
+    }
 }
@@ -0,0 +1,325 @@
+//! SQL rewriting for per-tenant table-name prefixes
+//!
+//! Multi-tenant single-database setups often prefix tables per tenant
+//! (`t123_users`). [`apply_table_prefix`] parses the SQL, prepends the prefix to
+//! every base-table reference, and regenerates the statements. CTE names and
+//! aliases are left alone, since they do not name real tables.
+//!
+//! References are found with sqlparser's AST visitor (the walk behind
+//! `visit_relations_mut`), so subqueries anywhere in a statement are covered.
+//! The rewrite fails closed: statement kinds it does not handle are rejected
+//! rather than passed through with unprefixed names.
+
+use crate::error::DatabaseError;
+use crate::sql_parser::get_dialect;
+use crate::types::DatabaseType;
+use lazy_regex::{Lazy, Regex, lazy_regex};
+use sqlparser::ast::{
+    ObjectName, ObjectNamePart, Query, SetExpr, Statement, TableAlias, TableFactor, VisitMut,
+    VisitorMut,
+};
+use sqlparser::parser::Parser;
+use std::ops::ControlFlow;
+
+static PREFIX_REGEX: Lazy<Regex> = lazy_regex!(r"^[A-Za-z0-9_]+$");
+
+/// Prepend `prefix` to every base-table name in `sql`
+///
+/// Every table reference in SELECT, INSERT, UPDATE and DELETE statements is
+/// rewritten, including those in subqueries, CTE bodies and `EXPLAIN`; for
+/// qualified names only the table part changes (`hr.users` → `hr.t1_users`).
+/// A prefixed table without an alias is aliased to its original name, so
+/// qualified column references keep working. Names defined by a `WITH` clause
+/// are not touched. The SQL is regenerated from the AST, so formatting and
+/// comments are not preserved.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::rewrite::apply_table_prefix;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sql = apply_table_prefix("SELECT users.id FROM users", "t1_", DatabaseType::Postgres)?;
+/// assert_eq!(sql, "SELECT users.id FROM t1_users AS users");
+///
+/// let sql = apply_table_prefix("WITH a AS (SELECT 1) SELECT * FROM a", "t1_", DatabaseType::Postgres)?;
+/// assert_eq!(sql, "WITH a AS (SELECT 1) SELECT * FROM a");
+///
+/// assert!(apply_table_prefix("DROP TABLE users", "t1_", DatabaseType::Postgres).is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns error if the prefix is not made of letters, digits and underscores,
+/// if the SQL cannot be parsed, or if it contains a statement other than
+/// SELECT, INSERT, UPDATE, DELETE or EXPLAIN of one of those (DDL, TRUNCATE,
+/// MERGE, SHOW, ...)
+pub fn apply_table_prefix(
+    sql: &str,
+    prefix: &str,
+    db_type: DatabaseType,
+) -> Result<String, DatabaseError> {
+    if !PREFIX_REGEX.is_match(prefix) {
+        return Err(DatabaseError::QueryError(format!(
+            "Invalid table prefix '{}': only letters, digits and underscores are allowed",
+            prefix
+        )));
+    }
+
+    let dialect = get_dialect(db_type);
+    let mut statements = Parser::parse_sql(&*dialect, sql)
+        .map_err(|e| DatabaseError::QueryError(format!("SQL parse error: {}", e)))?;
+
+    for statement in &statements {
+        check_rewritable(statement)?;
+    }
+
+    let mut rewriter = PrefixRewriter {
+        prefix,
+        ctes: Vec::new(),
+        delete_targets: Vec::new(),
+    };
+    for statement in &mut statements {
+        let _ = statement.visit(&mut rewriter);
+    }
+
+    Ok(statements
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(";\n"))
+}
+
+/// Reject statement kinds the prefix rewrite does not handle
+fn check_rewritable(stmt: &Statement) -> Result<(), DatabaseError> {
+    match stmt {
+        Statement::Query(_) | Statement::Insert(_) | Statement::Update(_) | Statement::Delete(_) => {
+            Ok(())
+        }
+        Statement::Explain { statement, .. } => check_rewritable(statement),
+        other => {
+            let text = other.to_string();
+            let kind = text.split_whitespace().next().unwrap_or_default().to_uppercase();
+            Err(DatabaseError::QueryError(format!(
+                "Table prefix cannot be applied to {} statements; only SELECT, INSERT, \
+                 UPDATE, DELETE and EXPLAIN are rewritten",
+                kind
+            )))
+        }
+    }
+}
+
+/// AST visitor carrying the prefix and the CTE names in scope
+struct PrefixRewriter<'a> {
+    prefix: &'a str,
+    /// CTE names defined by each enclosing query, innermost last
+    ctes: Vec<Vec<String>>,
+    /// `DELETE t1 FROM t1 JOIN t2` (MySQL) targets, set aside while their statement
+    /// is visited: they refer to FROM entries, which keep their names as aliases
+    delete_targets: Vec<Vec<ObjectName>>,
+}
+
+impl PrefixRewriter<'_> {
+    /// Whether `name` is a real table: not a CTE in scope, and ending in an identifier
+    fn is_base_table(&self, name: &ObjectName) -> bool {
+        if name.0.len() == 1
+            && let Some(ident) = name.0[0].as_ident()
+            && self.is_cte(&ident.value)
+        {
+            return false;
+        }
+        matches!(name.0.last(), Some(ObjectNamePart::Identifier(_)))
+    }
+
+    fn is_cte(&self, name: &str) -> bool {
+        self.ctes.iter().flatten().any(|cte| cte.eq_ignore_ascii_case(name))
+    }
+
+    /// `TABLE users` names its table as a plain string, outside the relation walk
+    fn table_commands(&self, body: &mut SetExpr) {
+        match body {
+            SetExpr::Table(table) => {
+                if let Some(name) = &mut table.table_name
+                    && (table.schema_name.is_some() || !self.is_cte(name))
+                {
+                    name.insert_str(0, self.prefix);
+                }
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                self.table_commands(left);
+                self.table_commands(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl VisitorMut for PrefixRewriter<'_> {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<()> {
+        // All names of this WITH are in scope for its bodies (covers WITH RECURSIVE)
+        let names = query
+            .with
+            .iter()
+            .flat_map(|with| &with.cte_tables)
+            .map(|cte| cte.alias.name.value.clone())
+            .collect();
+        self.ctes.push(names);
+        self.table_commands(&mut query.body);
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_query(&mut self, _query: &mut Query) -> ControlFlow<()> {
+        self.ctes.pop();
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, factor: &mut TableFactor) -> ControlFlow<()> {
+        // Alias the prefixed table to its old name so `users.id` still resolves
+        if let TableFactor::Table { name, alias, .. } = factor
+            && alias.is_none()
+            && self.is_base_table(name)
+            && let Some(original) = name.0.last().and_then(|part| part.as_ident()).cloned()
+        {
+            *alias = Some(TableAlias {
+                explicit: true,
+                name: original,
+                columns: Vec::new(),
+            });
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, name: &mut ObjectName) -> ControlFlow<()> {
+        if self.is_base_table(name)
+            && let Some(ObjectNamePart::Identifier(ident)) = name.0.last_mut()
+        {
+            ident.value.insert_str(0, self.prefix);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_statement(&mut self, stmt: &mut Statement) -> ControlFlow<()> {
+        if let Statement::Delete(delete) = stmt {
+            self.delete_targets.push(std::mem::take(&mut delete.tables));
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_statement(&mut self, stmt: &mut Statement) -> ControlFlow<()> {
+        if let Statement::Delete(delete) = stmt {
+            delete.tables = self.delete_targets.pop().unwrap_or_default();
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PG: DatabaseType = DatabaseType::Postgres;
+
+    #[test]
+    fn test_prefixes_joined_tables() {
+        let sql = apply_table_prefix(
+            "SELECT * FROM users JOIN orders ON orders.user_id = users.id",
+            "t123_",
+            PG,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM t123_users AS users JOIN t123_orders AS orders ON orders.user_id = users.id"
+        );
+    }
+
+    #[test]
+    fn test_leaves_cte_names_and_aliases_alone() {
+        let sql = apply_table_prefix(
+            "WITH recent AS (SELECT * FROM orders) SELECT * FROM recent r JOIN users u ON u.id = r.user_id",
+            "t1_",
+            PG,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "WITH recent AS (SELECT * FROM t1_orders AS orders) SELECT * FROM recent r JOIN t1_users u ON u.id = r.user_id"
+        );
+    }
+
+    #[test]
+    fn test_prefixes_subqueries_and_qualified_names() {
+        let sql = apply_table_prefix(
+            "SELECT * FROM hr.employees WHERE dept_id IN (SELECT id FROM departments)",
+            "t1_",
+            PG,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM hr.t1_employees AS employees WHERE dept_id IN (SELECT id FROM t1_departments AS departments)"
+        );
+    }
+
+    #[test]
+    fn test_prefixes_write_targets() {
+        let sql = apply_table_prefix(
+            "INSERT INTO users (name) VALUES ('a'); UPDATE users SET name = 'b'; DELETE FROM users",
+            "t1_",
+            DatabaseType::SQLite,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO t1_users (name) VALUES ('a');\nUPDATE t1_users AS users SET name = 'b';\nDELETE FROM t1_users AS users"
+        );
+    }
+
+    #[test]
+    fn test_prefixes_subqueries_outside_from_and_where() {
+        let sql = apply_table_prefix(
+            "UPDATE users SET score = (SELECT max(points) FROM games)",
+            "t1_",
+            DatabaseType::MySQL,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE t1_users AS users SET score = (SELECT max(points) FROM t1_games AS games)"
+        );
+
+        let sql = apply_table_prefix(
+            "SELECT id FROM users ORDER BY (SELECT count(*) FROM orders WHERE orders.user_id = users.id)",
+            "t1_",
+            PG,
+        )
+        .unwrap();
+        assert!(sql.contains("FROM t1_orders AS orders"), "{}", sql);
+    }
+
+    #[test]
+    fn test_rejects_unhandled_statement_kinds() {
+        for sql in [
+            "DROP TABLE users",
+            "CREATE TABLE audit (id INT)",
+            "TRUNCATE TABLE users",
+            "ALTER TABLE users ADD COLUMN note TEXT",
+            "MERGE INTO users USING staging ON users.id = staging.id \
+             WHEN MATCHED THEN DELETE",
+            "EXPLAIN DROP TABLE users",
+            "SELECT 1; DROP TABLE users",
+        ] {
+            assert!(apply_table_prefix(sql, "t1_", PG).is_err(), "{}", sql);
+        }
+        let err = apply_table_prefix("TRUNCATE users", "t1_", PG).unwrap_err();
+        assert!(err.to_string().contains("cannot be applied to TRUNCATE"), "{}", err);
+    }
+
+    #[test]
+    fn test_rejects_invalid_prefix() {
+        assert!(apply_table_prefix("SELECT 1", "t1; DROP", PG).is_err());
+        assert!(apply_table_prefix("SELECT 1", "", PG).is_err());
+    }
+}
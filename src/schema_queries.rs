@@ -31,7 +31,7 @@ use crate::types::DatabaseType;
 /// - **PostgreSQL**: `pg_catalog`, `information_schema`, `pg_toast`
 /// - **MySQL/MariaDB**: `information_schema`, `mysql`, `performance_schema`, `sys`
 /// - **SQL Server**: `sys`, `INFORMATION_SCHEMA`
-/// - **SQLite**: N/A (no schemas - tools should return `["main"]` without query)
+/// - **SQLite**: N/A - lists `main` and attached databases from `pragma_database_list`
 ///
 /// ## Example
 ///
@@ -60,9 +60,8 @@ pub fn get_schemas_query(db_type: DatabaseType) -> String {
                 .to_string()
         }
         DatabaseType::SQLite => {
-            // Reference: tmp/dbhub/src/connectors/sqlite/index.ts:141-144
-            // SQLite has no schemas - tools should return ["main"] without query
-            String::new()
+            // `main`, `temp` once used, and every ATTACHed database
+            "SELECT name as schema_name FROM pragma_database_list ORDER BY seq".to_string()
         }
         DatabaseType::SqlServer => "SELECT name as schema_name FROM sys.schemas \
              WHERE name NOT IN ('sys', 'INFORMATION_SCHEMA') \
@@ -73,11 +72,23 @@ pub fn get_schemas_query(db_type: DatabaseType) -> String {
 
 /// Returns SQL to list tables in a schema + parameters
 ///
+/// ## Filtering
+///
+/// - `name_pattern`: optional SQL `LIKE` pattern (e.g. `"emp%"`), always bound as a
+///   parameter and matched case-insensitively (`ILIKE` on PostgreSQL)
+/// - `include_views`: when true, views are listed alongside base tables
+///
+/// ## Return Columns
+///
+/// - `table_name` (String)
+/// - `table_type` (String) - `"BASE TABLE"` or `"VIEW"`
+///
 /// ## Special Cases
 ///
 /// - **PostgreSQL**: Uses `$1` parameter, defaults to "public" schema if None
 /// - **MySQL/MariaDB**: Uses `?` parameter, or `DATABASE()` function if schema is None
-/// - **SQLite**: Queries sqlite_master, excludes system tables (sqlite_%), no parameters
+/// - **SQLite**: Queries `pragma_table_list` for the schema (default "main", or an
+///   attached database), excludes system tables (sqlite_%)
 /// - **SQL Server**: Uses `@P1` parameter, defaults to "dbo" schema if None
 ///
 /// ## Example
@@ -86,55 +97,97 @@ pub fn get_schemas_query(db_type: DatabaseType) -> String {
 /// use kodegen_tools_database::types::DatabaseType;
 /// use kodegen_tools_database::schema_queries::get_tables_query;
 ///
-/// let (sql, params) = get_tables_query(DatabaseType::Postgres, Some("public"));
+/// let (sql, params) = get_tables_query(DatabaseType::Postgres, Some("public"), None, false);
 /// // Returns: ("SELECT table_name FROM ... WHERE table_schema = $1", ["public"])
+///
+/// let (sql, params) = get_tables_query(DatabaseType::Postgres, Some("public"), Some("emp%"), true);
+/// assert!(sql.contains("ILIKE $2"));
+/// assert_eq!(params, vec!["public".to_string(), "emp%".to_string()]);
 /// ```
-pub fn get_tables_query(db_type: DatabaseType, schema: Option<&str>) -> (String, Vec<String>) {
-    match db_type {
+pub fn get_tables_query(
+    db_type: DatabaseType,
+    schema: Option<&str>,
+    name_pattern: Option<&str>,
+    include_views: bool,
+) -> (String, Vec<String>) {
+    let mut params = Vec::new();
+
+    let sql = match db_type {
         DatabaseType::Postgres => {
             // Reference: tmp/dbhub/src/connectors/postgres/index.ts:150-166
             // Use CAST() for sqlx::any compatibility
-            let sql =
-                "SELECT CAST(table_name AS TEXT) as table_name FROM information_schema.tables \
-                       WHERE table_schema = $1 AND table_type = 'BASE TABLE' \
-                       ORDER BY table_name"
-                    .to_string();
-            let params = vec![schema.unwrap_or("public").to_string()];
-            (sql, params)
+            params.push(schema.unwrap_or("public").to_string());
+            let mut sql = "SELECT CAST(table_name AS TEXT) as table_name, \
+                                  CAST(table_type AS TEXT) as table_type \
+                           FROM information_schema.tables \
+                           WHERE table_schema = $1"
+                .to_string();
+            sql.push_str(table_type_filter(include_views));
+            if let Some(pattern) = name_pattern {
+                params.push(pattern.to_string());
+                sql.push_str(" AND table_name ILIKE $2");
+            }
+            sql
         }
         DatabaseType::MySQL | DatabaseType::MariaDB => {
             // Reference: tmp/dbhub/src/connectors/mysql/index.ts:129-154
+            let mut sql = "SELECT table_name, table_type FROM information_schema.tables ".to_string();
             if let Some(s) = schema {
-                let sql = "SELECT table_name FROM information_schema.tables \
-                           WHERE table_schema = ? AND table_type = 'BASE TABLE' \
-                           ORDER BY table_name"
-                    .to_string();
-                (sql, vec![s.to_string()])
+                params.push(s.to_string());
+                sql.push_str("WHERE table_schema = ?");
             } else {
                 // Use DATABASE() to get current database
-                let sql = "SELECT table_name FROM information_schema.tables \
-                           WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' \
-                           ORDER BY table_name"
-                    .to_string();
-                (sql, vec![])
+                sql.push_str("WHERE table_schema = DATABASE()");
+            }
+            sql.push_str(table_type_filter(include_views));
+            if let Some(pattern) = name_pattern {
+                params.push(pattern.to_string());
+                sql.push_str(" AND table_name LIKE ?");
             }
+            sql
         }
         DatabaseType::SQLite => {
             // Reference: tmp/dbhub/src/connectors/sqlite/index.ts:149-161
-            let sql = "SELECT name as table_name FROM sqlite_master \
-                       WHERE type='table' AND name NOT LIKE 'sqlite_%' \
-                       ORDER BY name"
+            // SQLite LIKE is case-insensitive for ASCII by default
+            // pragma_table_list takes the (attached) database name as a bound value
+            params.push(schema.unwrap_or("main").to_string());
+            let mut sql = "SELECT name as table_name, \
+                                  CASE type WHEN 'view' THEN 'VIEW' ELSE 'BASE TABLE' END as table_type \
+                           FROM pragma_table_list \
+                           WHERE schema = ? AND name NOT LIKE 'sqlite_%'"
                 .to_string();
-            (sql, vec![])
+            if !include_views {
+                sql.push_str(" AND type <> 'view'");
+            }
+            if let Some(pattern) = name_pattern {
+                params.push(pattern.to_string());
+                sql.push_str(" AND name LIKE ?");
+            }
+            sql
         }
         DatabaseType::SqlServer => {
-            let sql = "SELECT table_name FROM information_schema.tables \
-                       WHERE table_schema = @P1 AND table_type = 'BASE TABLE' \
-                       ORDER BY table_name"
+            params.push(schema.unwrap_or("dbo").to_string());
+            let mut sql = "SELECT table_name, table_type FROM information_schema.tables \
+                           WHERE table_schema = @P1"
                 .to_string();
-            let params = vec![schema.unwrap_or("dbo").to_string()];
-            (sql, params)
+            sql.push_str(table_type_filter(include_views));
+            if let Some(pattern) = name_pattern {
+                params.push(pattern.to_string());
+                sql.push_str(" AND table_name LIKE @P2");
+            }
+            sql
         }
+    };
+
+    (format!("{} ORDER BY table_name", sql), params)
+}
+
+/// information_schema.tables filter on table_type
+fn table_type_filter(include_views: bool) -> &'static str {
+    if include_views {
+        " AND table_type IN ('BASE TABLE', 'VIEW')"
+    } else {
+        " AND table_type = 'BASE TABLE'"
     }
 }
 
@@ -148,15 +201,17 @@ pub fn get_tables_query(db_type: DatabaseType, schema: Option<&str>) -> (String,
 /// - `is_nullable` (String - "YES" or "NO")
 /// - `column_default` (Option<String>)
 ///
-/// ## SQLite PRAGMA Validation
+/// ## SQLite Binding
 ///
-/// For SQLite, PRAGMA commands do NOT support parameterized queries. This function
-/// automatically validates table names before interpolation to prevent SQL injection.
-/// Validation uses strict rules: alphanumeric + underscore only, no SQL keywords.
+/// `PRAGMA table_info(...)` cannot take bind parameters, so SQLite uses the
+/// `pragma_table_info(table, schema)` table-valued function instead. Both the
+/// table and the schema (`main` or an attached database) are bound, so names
+/// with quotes, spaces or semicolons are looked up literally and never
+/// interpolated into the SQL.
 ///
-/// ## SQLite PRAGMA Return Values
+/// ## SQLite Return Values
 ///
-/// SQLite's `PRAGMA table_info()` returns different column names than information_schema:
+/// SQLite's `pragma_table_info()` returns different column names than information_schema:
 /// - `cid` - column ID
 /// - `name` - use as `column_name`
 /// - `type` - use as `data_type`
@@ -168,7 +223,7 @@ pub fn get_tables_query(db_type: DatabaseType, schema: Option<&str>) -> (String,
 ///
 /// ## Errors
 ///
-/// Returns `DatabaseError::QueryError` if the table name fails validation (SQLite only).
+/// Currently infallible; the `Result` is kept for dialects that interpolate identifiers.
 ///
 /// ## Example
 ///
@@ -214,14 +269,14 @@ pub fn get_table_schema_query(
             Ok((sql, vec![schema.to_string(), table.to_string()]))
         }
         DatabaseType::SQLite => {
-            // SECURITY: Validate identifier before string interpolation
-            // This prevents SQL injection in PRAGMA commands which cannot use parameters
-            crate::validate::validate_sqlite_identifier(table)?;
-
-            let sql = format!("PRAGMA table_info({})", table);
+            // SECURITY: the table-valued form binds both names; PRAGMA would need interpolation
+            let sql = "SELECT cid, name, type, notnull, dflt_value, pk \
+                       FROM pragma_table_info(?, ?) \
+                       ORDER BY cid"
+                .to_string();
             // Note: PRAGMA returns different column names (cid, name, type, notnull, dflt_value, pk)
             // ExecuteSQL tool transforms these to match TableColumn struct
-            Ok((sql, vec![]))
+            Ok((sql, vec![table.to_string(), schema.to_string()]))
         }
         DatabaseType::SqlServer => {
             let sql = "SELECT column_name, data_type, is_nullable, column_default \
@@ -244,32 +299,32 @@ pub fn get_table_schema_query(
 /// - `is_unique` (bool)
 /// - `is_primary` (bool)
 ///
+/// MySQL/MariaDB and SQLite instead return one row per index column
+/// (`index_name`, `column_name`, `seq_in_index`, `is_unique`, `is_primary`),
+/// grouped in Rust. Every dialect reports columns in key order, so composite
+/// keys read left to right as defined.
+///
 /// ## Database-Specific Notes
 ///
 /// ### PostgreSQL
 /// Uses complex joins on pg_catalog tables (pg_class, pg_index, pg_attribute, pg_namespace).
-/// Uses `array_agg()` to aggregate multi-column indexes.
+/// Uses `array_agg()` ordered by position in `indkey` to aggregate multi-column indexes.
 ///
 /// ### MySQL/MariaDB
-/// Uses `information_schema.statistics` with columns fetched per-index to avoid
-/// GROUP_CONCAT truncation limits.
+/// Uses `information_schema.statistics` ordered by `seq_in_index`, grouped in Rust
+/// to avoid GROUP_CONCAT truncation limits.
 ///
 /// ### SQLite
-/// Returns `PRAGMA index_list()` which requires follow-up calls. PRAGMA commands
-/// cannot use parameterized queries, so this function automatically validates
-/// table names before interpolation to prevent SQL injection.
-///
-/// Complete SQLite index info requires:
-/// 1. `PRAGMA index_list(table_name)` - gets index names and unique flags
-/// 2. For each index: `PRAGMA index_info(index_name)` - gets columns
-/// 3. `PRAGMA table_info(table_name)` - finds primary key columns
+/// Joins `pragma_index_list()` with `pragma_index_info()`, ordered by `seqno`.
+/// The table and database names are bound as parameters. Tables keyed by an
+/// `INTEGER PRIMARY KEY` (the rowid) have no primary key index to report.
 ///
 /// ### SQL Server
-/// Uses sys.indexes and sys.index_columns with `STRING_AGG()` for column aggregation.
+/// Uses sys.indexes and sys.index_columns with `STRING_AGG()` ordered by `key_ordinal`.
 ///
 /// ## Errors
 ///
-/// Returns `DatabaseError::QueryError` if the table name fails validation (SQLite only).
+/// Currently infallible; the `Result` is kept for dialects that interpolate identifiers.
 ///
 /// ## Example
 ///
@@ -293,10 +348,13 @@ pub fn get_indexes_query(
         DatabaseType::Postgres => {
             // Reference: tmp/dbhub/src/connectors/postgres/index.ts:200-230
             // Use array_to_string() instead of array_agg() for sqlx::any compatibility
-            // (Any driver doesn't support TEXT[] array type)
+            // (Any driver doesn't support TEXT[] array type). Columns are aggregated
+            // in indkey order, which is the key order of the index definition.
             let sql = "SELECT \
                            CAST(i.relname AS TEXT) as index_name, \
-                           array_to_string(array_agg(CAST(a.attname AS TEXT)), ',') as column_names, \
+                           array_to_string(array_agg(CAST(a.attname AS TEXT) \
+                               ORDER BY array_position(CAST(ix.indkey AS int2[]), a.attnum)), ',') \
+                               as column_names, \
                            ix.indisunique as is_unique, \
                            ix.indisprimary as is_primary \
                        FROM \
@@ -328,10 +386,11 @@ pub fn get_indexes_query(
             // Grouping happens in Rust (get_table_indexes.rs) to avoid:
             // 1. GROUP_CONCAT 1024-byte truncation limit
             // 2. N+1 query pattern
+            // Aliases keep the lowercase names (MySQL 8 reports them uppercase)
             let sql = "SELECT \
-                           index_name, \
-                           column_name, \
-                           seq_in_index, \
+                           index_name as index_name, \
+                           column_name as column_name, \
+                           seq_in_index as seq_in_index, \
                            NOT non_unique as is_unique, \
                            index_name = 'PRIMARY' as is_primary \
                        FROM information_schema.statistics \
@@ -341,18 +400,24 @@ pub fn get_indexes_query(
             Ok((sql, vec![schema.to_string(), table.to_string()]))
         }
         DatabaseType::SQLite => {
-            // SECURITY: Validate identifier before string interpolation
-            crate::validate::validate_sqlite_identifier(table)?;
-
-            let sql = format!("PRAGMA index_list({})", table);
-            // Note: Returns index list only; ExecuteSQL tool makes follow-up calls
-            // to PRAGMA index_info(index_name) for each index to get columns
-            Ok((sql, vec![]))
+            // One row per index column, like MySQL; the pragma table-valued
+            // functions take the table and (attached) database name as bound values
+            let sql = "SELECT \
+                           il.name as index_name, \
+                           ii.name as column_name, \
+                           ii.seqno as seq_in_index, \
+                           il.\"unique\" as is_unique, \
+                           il.origin = 'pk' as is_primary \
+                       FROM pragma_index_list(?, ?) il \
+                       JOIN pragma_index_info(il.name, ?) ii \
+                       ORDER BY il.name, ii.seqno"
+                .to_string();
+            Ok((sql, vec![table.to_string(), schema.to_string(), schema.to_string()]))
         }
         DatabaseType::SqlServer => {
             let sql = "SELECT \
                            i.name as index_name, \
-                           STRING_AGG(c.name, ',') as column_names, \
+                           STRING_AGG(c.name, ',') WITHIN GROUP (ORDER BY ic.key_ordinal) as column_names, \
                            i.is_unique, \
                            i.is_primary_key as is_primary \
                        FROM sys.indexes i \
@@ -417,14 +482,172 @@ pub fn get_index_columns_query(
     }
 }
 
+/// Returns SQL to list a table's constraints + parameters
+///
+/// ## Return Columns
+///
+/// PostgreSQL, MySQL/MariaDB and SQL Server return one row per constrained
+/// column, ordered by constraint and column position; rows of one constraint
+/// are adjacent:
+/// - `constraint_name` (String)
+/// - `constraint_type` (String) - "PRIMARY KEY", "UNIQUE", "CHECK", "FOREIGN KEY" or "NOT NULL"
+/// - `column_name` (Option<String>) - NULL for a CHECK that names no column
+/// - `definition` (Option<String>) - `pg_get_constraintdef()` on PostgreSQL,
+///   the check clause on MySQL/MariaDB and SQL Server
+/// - `column_position` (i64) - ordering only
+///
+/// NOT NULL columns are listed as constraints named `{table}_{column}_not_null`
+/// (PostgreSQL and MySQL/MariaDB).
+///
+/// ### SQLite
+/// Constraints only survive in the original `CREATE TABLE` text, so the query
+/// returns the single `sql` column from `sqlite_master` to be parsed (see
+/// [`crate::constraints::parse_sqlite_constraints`]).
+///
+/// ## Errors
+///
+/// Returns `DatabaseError::QueryError` if an identifier fails validation (SQLite only).
+///
+/// ## Example
+///
+/// ```rust
+/// use kodegen_tools_database::types::DatabaseType;
+/// use kodegen_tools_database::schema_queries::get_constraints_query;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let (sql, params) = get_constraints_query(DatabaseType::Postgres, "public", "orders")?;
+/// assert!(sql.contains("pg_constraint"));
+/// assert_eq!(params, vec!["public", "orders"]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn get_constraints_query(
+    db_type: DatabaseType,
+    schema: &str,
+    table: &str,
+) -> Result<(String, Vec<String>), DatabaseError> {
+    match db_type {
+        DatabaseType::Postgres => {
+            // NOT NULL lives in pg_attribute (pg_constraint only from PostgreSQL 18 on)
+            let sql = "SELECT \
+                           CAST(con.conname AS TEXT) as constraint_name, \
+                           CAST(CASE con.contype WHEN 'p' THEN 'PRIMARY KEY' WHEN 'u' THEN 'UNIQUE' \
+                                WHEN 'c' THEN 'CHECK' ELSE 'FOREIGN KEY' END AS TEXT) as constraint_type, \
+                           CAST(a.attname AS TEXT) as column_name, \
+                           CAST(pg_get_constraintdef(con.oid) AS TEXT) as definition, \
+                           CAST(COALESCE(k.ord, 0) AS BIGINT) as column_position \
+                       FROM pg_constraint con \
+                       JOIN pg_class c ON c.oid = con.conrelid \
+                       JOIN pg_namespace n ON n.oid = c.relnamespace \
+                       LEFT JOIN LATERAL unnest(con.conkey) WITH ORDINALITY AS k(attnum, ord) ON true \
+                       LEFT JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = k.attnum \
+                       WHERE n.nspname = $1 AND c.relname = $2 AND con.contype IN ('p', 'u', 'c', 'f') \
+                       UNION ALL \
+                       SELECT \
+                           CAST(c.relname || '_' || a.attname || '_not_null' AS TEXT), \
+                           CAST('NOT NULL' AS TEXT), \
+                           CAST(a.attname AS TEXT), \
+                           CAST(NULL AS TEXT), \
+                           CAST(1 AS BIGINT) \
+                       FROM pg_attribute a \
+                       JOIN pg_class c ON c.oid = a.attrelid \
+                       JOIN pg_namespace n ON n.oid = c.relnamespace \
+                       WHERE n.nspname = $1 AND c.relname = $2 AND c.relkind IN ('r', 'p') \
+                         AND a.attnum > 0 AND NOT a.attisdropped AND a.attnotnull \
+                       ORDER BY constraint_name, column_position"
+                .to_string();
+            Ok((sql, vec![schema.to_string(), table.to_string()]))
+        }
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            // One row per column; grouped in Rust like indexes (no GROUP_CONCAT truncation)
+            let sql = "SELECT \
+                           tc.constraint_name AS constraint_name, \
+                           tc.constraint_type AS constraint_type, \
+                           kcu.column_name AS column_name, \
+                           cc.check_clause AS definition, \
+                           COALESCE(kcu.ordinal_position, 0) AS column_position \
+                       FROM information_schema.table_constraints tc \
+                       LEFT JOIN information_schema.key_column_usage kcu \
+                           ON kcu.constraint_schema = tc.constraint_schema \
+                           AND kcu.constraint_name = tc.constraint_name \
+                           AND kcu.table_schema = tc.table_schema \
+                           AND kcu.table_name = tc.table_name \
+                       LEFT JOIN information_schema.check_constraints cc \
+                           ON tc.constraint_type = 'CHECK' \
+                           AND cc.constraint_schema = tc.constraint_schema \
+                           AND cc.constraint_name = tc.constraint_name \
+                       WHERE tc.table_schema = ? AND tc.table_name = ? \
+                       UNION ALL \
+                       SELECT \
+                           CONCAT(table_name, '_', column_name, '_not_null'), \
+                           'NOT NULL', \
+                           column_name, \
+                           NULL, \
+                           1 \
+                       FROM information_schema.columns \
+                       WHERE table_schema = ? AND table_name = ? AND is_nullable = 'NO' \
+                       ORDER BY constraint_name, column_position"
+                .to_string();
+            Ok((
+                sql,
+                vec![
+                    schema.to_string(),
+                    table.to_string(),
+                    schema.to_string(),
+                    table.to_string(),
+                ],
+            ))
+        }
+        DatabaseType::SQLite => {
+            // SECURITY: sqlite_master cannot be chosen by a bind parameter
+            crate::validate::validate_sqlite_identifier(schema)?;
+            crate::validate::validate_sqlite_identifier(table)?;
+            let sql = format!(
+                "SELECT sql FROM {}.sqlite_master WHERE type = 'table' AND name = ?",
+                schema
+            );
+            Ok((sql, vec![table.to_string()]))
+        }
+        DatabaseType::SqlServer => {
+            let sql = "SELECT \
+                           tc.CONSTRAINT_NAME as constraint_name, \
+                           tc.CONSTRAINT_TYPE as constraint_type, \
+                           kcu.COLUMN_NAME as column_name, \
+                           cc.CHECK_CLAUSE as definition, \
+                           COALESCE(kcu.ORDINAL_POSITION, 0) as column_position \
+                       FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc \
+                       LEFT JOIN INFORMATION_SCHEMA.KEY_COLUMN_USAGE kcu \
+                           ON kcu.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA \
+                           AND kcu.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+                       LEFT JOIN INFORMATION_SCHEMA.CHECK_CONSTRAINTS cc \
+                           ON cc.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA \
+                           AND cc.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+                       WHERE tc.TABLE_SCHEMA = @P1 AND tc.TABLE_NAME = @P2 \
+                       ORDER BY tc.CONSTRAINT_NAME, column_position"
+                .to_string();
+            Ok((sql, vec![schema.to_string(), table.to_string()]))
+        }
+    }
+}
+
 /// Returns SQL to list stored procedures in a schema + parameters
 ///
+/// Routines are LEFT JOINed with `information_schema.parameters`, so the
+/// result has one row per parameter (or a single row for a routine without
+/// parameters), ordered by routine and then parameter position. Rows of one
+/// routine are adjacent and share `specific_name` and `procedure_type`.
+///
 /// ## Return Columns
 ///
-/// Queries return columns matching the `StoredProcedure` struct (minimum required):
+/// - `specific_name` (String) - distinguishes overloads of the same name
 /// - `procedure_name` (String)
 /// - `procedure_type` (String) - "procedure" or "function"
 /// - `language` (Option<String>)
+/// - `return_type` (Option<String>)
+/// - `definition` (Option<String>) - only when `include_details` is set; MySQL/MariaDB
+///   always return NULL (see [`get_procedure_definition_query`])
+/// - `parameter_name`, `parameter_mode`, `parameter_type` (Option<String>)
+/// - `parameter_position` (Option<i64>) - NULL for routines without parameters
 ///
 /// ## SQLite Support
 ///
@@ -436,39 +659,70 @@ pub fn get_index_columns_query(
 /// use kodegen_tools_database::types::DatabaseType;
 /// use kodegen_tools_database::schema_queries::get_stored_procedures_query;
 ///
-/// let result = get_stored_procedures_query(DatabaseType::Postgres, "public");
-/// // Returns: Some(("SELECT routine_name as procedure_name... WHERE routine_schema = $1", ["public"]))
+/// let result = get_stored_procedures_query(DatabaseType::Postgres, "public", false);
+/// // Returns: Some(("SELECT ... FROM information_schema.routines r LEFT JOIN ... WHERE r.routine_schema = $1", ["public"]))
 ///
-/// let result = get_stored_procedures_query(DatabaseType::SQLite, "main");
+/// let result = get_stored_procedures_query(DatabaseType::SQLite, "main", false);
 /// // Returns: None
 /// ```
 pub fn get_stored_procedures_query(
     db_type: DatabaseType,
     schema: &str,
+    include_details: bool,
 ) -> Option<(String, Vec<String>)> {
     match db_type {
         DatabaseType::Postgres => {
             // Reference: tmp/dbhub/src/connectors/postgres/index.ts:283-297
-            // Use CAST() for sqlx::any compatibility
-            let sql = "SELECT \
-                           CAST(routine_name AS TEXT) as procedure_name, \
-                           CAST(routine_type AS TEXT) as routine_type, \
-                           CASE WHEN routine_type = 'PROCEDURE' THEN 'procedure' ELSE 'function' END as procedure_type, \
-                           CAST(external_language AS TEXT) as language \
-                       FROM information_schema.routines \
-                       WHERE routine_schema = $1 \
-                       ORDER BY routine_name".to_string();
+            // Use CAST() for sqlx::any compatibility. specific_name is `<name>_<oid>`,
+            // which is how the pg_proc row is found for pg_get_functiondef().
+            let definition = if include_details {
+                "CAST(pg_get_functiondef(CAST(substring(CAST(r.specific_name AS TEXT) from '_([0-9]+)$') AS oid)) AS TEXT)"
+            } else {
+                "CAST(NULL AS TEXT)"
+            };
+            let sql = format!(
+                "SELECT \
+                     CAST(r.specific_name AS TEXT) as specific_name, \
+                     CAST(r.routine_name AS TEXT) as procedure_name, \
+                     CAST(r.routine_type AS TEXT) as routine_type, \
+                     CASE WHEN r.routine_type = 'PROCEDURE' THEN 'procedure' ELSE 'function' END as procedure_type, \
+                     CAST(r.external_language AS TEXT) as language, \
+                     CAST(r.data_type AS TEXT) as return_type, \
+                     {} as definition, \
+                     CAST(p.parameter_name AS TEXT) as parameter_name, \
+                     CAST(p.parameter_mode AS TEXT) as parameter_mode, \
+                     CAST(CASE WHEN p.data_type IN ('USER-DEFINED', 'ARRAY') THEN p.udt_name \
+                          ELSE p.data_type END AS TEXT) as parameter_type, \
+                     CAST(p.ordinal_position AS BIGINT) as parameter_position \
+                 FROM information_schema.routines r \
+                 LEFT JOIN information_schema.parameters p \
+                     ON p.specific_schema = r.specific_schema AND p.specific_name = r.specific_name \
+                 WHERE r.routine_schema = $1 \
+                 ORDER BY r.routine_name, r.specific_name, p.ordinal_position",
+                definition
+            );
             Some((sql, vec![schema.to_string()]))
         }
         DatabaseType::MySQL | DatabaseType::MariaDB => {
+            // Position 0 is a function's return value, not a parameter
             let sql = "SELECT \
-                           routine_name as procedure_name, \
-                           routine_type, \
-                           CASE WHEN routine_type = 'PROCEDURE' THEN 'procedure' ELSE 'function' END as procedure_type, \
-                           external_language as language \
-                       FROM information_schema.routines \
-                       WHERE routine_schema = ? \
-                       ORDER BY routine_name".to_string();
+                           r.specific_name, \
+                           r.routine_name as procedure_name, \
+                           r.routine_type, \
+                           CASE WHEN r.routine_type = 'PROCEDURE' THEN 'procedure' ELSE 'function' END as procedure_type, \
+                           r.external_language as language, \
+                           r.dtd_identifier as return_type, \
+                           CAST(NULL AS CHAR) as definition, \
+                           p.parameter_name, \
+                           p.parameter_mode, \
+                           p.dtd_identifier as parameter_type, \
+                           CAST(p.ordinal_position AS SIGNED) as parameter_position \
+                       FROM information_schema.routines r \
+                       LEFT JOIN information_schema.parameters p \
+                           ON p.specific_schema = r.routine_schema AND p.specific_name = r.specific_name \
+                           AND p.routine_type = r.routine_type AND p.ordinal_position > 0 \
+                       WHERE r.routine_schema = ? \
+                       ORDER BY r.routine_name, r.routine_type, p.ordinal_position".to_string();
             Some((sql, vec![schema.to_string()]))
         }
         DatabaseType::SQLite => {
@@ -476,19 +730,401 @@ pub fn get_stored_procedures_query(
             None
         }
         DatabaseType::SqlServer => {
-            let sql = "SELECT \
-                           routine_name as procedure_name, \
-                           routine_type, \
-                           CASE WHEN routine_type = 'PROCEDURE' THEN 'procedure' ELSE 'function' END as procedure_type, \
-                           'SQL' as language \
-                       FROM information_schema.routines \
-                       WHERE routine_schema = @P1 \
-                       ORDER BY routine_name".to_string();
+            let definition = if include_details {
+                "OBJECT_DEFINITION(OBJECT_ID(QUOTENAME(r.routine_schema) + '.' + QUOTENAME(r.routine_name)))"
+            } else {
+                "CAST(NULL AS NVARCHAR(MAX))"
+            };
+            let sql = format!(
+                "SELECT \
+                     r.specific_name, \
+                     r.routine_name as procedure_name, \
+                     r.routine_type, \
+                     CASE WHEN r.routine_type = 'PROCEDURE' THEN 'procedure' ELSE 'function' END as procedure_type, \
+                     'SQL' as language, \
+                     r.data_type as return_type, \
+                     {} as definition, \
+                     p.parameter_name, \
+                     p.parameter_mode, \
+                     p.data_type as parameter_type, \
+                     CAST(p.ordinal_position AS BIGINT) as parameter_position \
+                 FROM information_schema.routines r \
+                 LEFT JOIN information_schema.parameters p \
+                     ON p.specific_schema = r.specific_schema AND p.specific_name = r.specific_name \
+                     AND p.ordinal_position > 0 \
+                 WHERE r.routine_schema = @P1 \
+                 ORDER BY r.routine_name, r.specific_name, p.ordinal_position",
+                definition
+            );
             Some((sql, vec![schema.to_string()]))
         }
     }
 }
 
+/// Returns SQL fetching a MySQL/MariaDB routine's full definition
+///
+/// `SHOW CREATE PROCEDURE` / `SHOW CREATE FUNCTION` cannot be parameterized,
+/// so `schema` and `name` must be validated identifiers (see
+/// [`crate::validate::validate_sqlite_identifier`]). The definition is the
+/// third column (`Create Procedure` / `Create Function`) and is NULL when the
+/// user lacks privileges on the routine.
+///
+/// Returns `None` for other databases, which select the definition inline
+/// in [`get_stored_procedures_query`].
+pub fn get_procedure_definition_query(
+    db_type: DatabaseType,
+    schema: &str,
+    name: &str,
+    procedure_type: &str,
+) -> Option<String> {
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            let kind = if procedure_type == "procedure" { "PROCEDURE" } else { "FUNCTION" };
+            Some(format!("SHOW CREATE {} `{}`.`{}`", kind, schema, name))
+        }
+        _ => None,
+    }
+}
+
+/// Returns SQL to list statements currently running on the server
+///
+/// ## Return Columns
+///
+/// Queries return columns matching the `ActiveQuery` struct:
+/// - `process_id` (i64) - backend PID / connection id / session id
+/// - `user_name` (Option<String>)
+/// - `state` (String) - e.g. "active", "idle in transaction", "Query"
+/// - `duration_ms` (Option<i64>) - elapsed time of the current statement
+/// - `query_text` (String) - raw statement text (callers should fingerprint it)
+/// - `is_current` (i64) - 1 for the connection running this query, 0 otherwise
+///
+/// ## SQLite Support
+///
+/// SQLite is embedded and has no server-side process list. This function returns `None` for SQLite.
+///
+/// ## Example
+///
+/// ```rust
+/// use kodegen_tools_database::types::DatabaseType;
+/// use kodegen_tools_database::schema_queries::get_active_queries_query;
+///
+/// let sql = get_active_queries_query(DatabaseType::Postgres);
+/// assert!(sql.unwrap().contains("pg_stat_activity"));
+///
+/// assert!(get_active_queries_query(DatabaseType::SQLite).is_none());
+/// ```
+pub fn get_active_queries_query(db_type: DatabaseType) -> Option<String> {
+    match db_type {
+        DatabaseType::Postgres => {
+            // Use CAST() for sqlx::any compatibility (pid is int4, interval is not supported)
+            Some(
+                "SELECT \
+                     CAST(pid AS BIGINT) as process_id, \
+                     CAST(usename AS TEXT) as user_name, \
+                     CAST(COALESCE(state, 'unknown') AS TEXT) as state, \
+                     CAST(EXTRACT(EPOCH FROM (now() - query_start)) * 1000 AS BIGINT) as duration_ms, \
+                     CAST(COALESCE(query, '') AS TEXT) as query_text, \
+                     CAST(CASE WHEN pid = pg_backend_pid() THEN 1 ELSE 0 END AS BIGINT) as is_current \
+                 FROM pg_stat_activity \
+                 WHERE backend_type = 'client backend' \
+                 ORDER BY query_start NULLS LAST"
+                    .to_string(),
+            )
+        }
+        DatabaseType::MySQL | DatabaseType::MariaDB => Some(
+            "SELECT \
+                 CAST(id AS SIGNED) as process_id, \
+                 user as user_name, \
+                 COALESCE(state, command) as state, \
+                 CAST(time * 1000 AS SIGNED) as duration_ms, \
+                 COALESCE(info, '') as query_text, \
+                 CAST(CASE WHEN id = CONNECTION_ID() THEN 1 ELSE 0 END AS SIGNED) as is_current \
+             FROM information_schema.processlist \
+             WHERE command <> 'Daemon' \
+             ORDER BY time DESC"
+                .to_string(),
+        ),
+        DatabaseType::SQLite => {
+            // SQLite has no server process list
+            None
+        }
+        DatabaseType::SqlServer => Some(
+            "SELECT \
+                 CAST(r.session_id AS BIGINT) as process_id, \
+                 s.login_name as user_name, \
+                 r.status as state, \
+                 CAST(r.total_elapsed_time AS BIGINT) as duration_ms, \
+                 COALESCE(t.text, '') as query_text, \
+                 CAST(CASE WHEN r.session_id = @@SPID THEN 1 ELSE 0 END AS BIGINT) as is_current \
+             FROM sys.dm_exec_requests r \
+             JOIN sys.dm_exec_sessions s ON s.session_id = r.session_id \
+             CROSS APPLY sys.dm_exec_sql_text(r.sql_handle) t \
+             WHERE s.is_user_process = 1 \
+             ORDER BY r.total_elapsed_time DESC"
+                .to_string(),
+        ),
+    }
+}
+
+/// Returns SQL that cancels or terminates a server process
+///
+/// `terminate = false` cancels only the running statement where the dialect
+/// allows it (`pg_cancel_backend`, `KILL QUERY`); `true` closes the whole
+/// session (`pg_terminate_backend`, `KILL`). SQL Server's `KILL` always ends
+/// the session. PostgreSQL returns one row with a boolean `success` column;
+/// the other dialects return no rows.
+///
+/// ## Example
+///
+/// ```rust
+/// use kodegen_tools_database::types::DatabaseType;
+/// use kodegen_tools_database::schema_queries::get_kill_query_sql;
+///
+/// let sql = get_kill_query_sql(DatabaseType::MySQL, 42, false).unwrap();
+/// assert_eq!(sql, "KILL QUERY 42");
+///
+/// assert!(get_kill_query_sql(DatabaseType::SQLite, 42, false).is_err());
+/// ```
+///
+/// # Errors
+/// Returns error for SQLite, which has no server processes, or a non-positive id
+pub fn get_kill_query_sql(
+    db_type: DatabaseType,
+    process_id: i64,
+    terminate: bool,
+) -> Result<String, DatabaseError> {
+    if process_id <= 0 {
+        return Err(DatabaseError::QueryError(format!(
+            "Invalid process id: {} (must be positive)",
+            process_id
+        )));
+    }
+
+    match db_type {
+        DatabaseType::Postgres => {
+            let function = if terminate {
+                "pg_terminate_backend"
+            } else {
+                "pg_cancel_backend"
+            };
+            Ok(format!("SELECT {}({}) AS success", function, process_id))
+        }
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            if terminate {
+                Ok(format!("KILL {}", process_id))
+            } else {
+                Ok(format!("KILL QUERY {}", process_id))
+            }
+        }
+        DatabaseType::SqlServer => Ok(format!("KILL {}", process_id)),
+        DatabaseType::SQLite => Err(DatabaseError::FeatureNotSupported(
+            "SQLite is embedded and has no server processes to kill".to_string(),
+        )),
+    }
+}
+
+/// Returns SQL selecting the current connection's process id as `process_id`
+///
+/// Matches the ids reported by [`get_active_queries_query`]. Returns `None`
+/// for SQLite.
+pub fn get_current_process_id_query(db_type: DatabaseType) -> Option<&'static str> {
+    match db_type {
+        DatabaseType::Postgres => Some("SELECT CAST(pg_backend_pid() AS BIGINT) as process_id"),
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            Some("SELECT CAST(CONNECTION_ID() AS SIGNED) as process_id")
+        }
+        DatabaseType::SqlServer => Some("SELECT CAST(@@SPID AS BIGINT) as process_id"),
+        DatabaseType::SQLite => None,
+    }
+}
+
+/// Returns SQL describing the server and the current session
+///
+/// ## Return Columns
+///
+/// One row matching the `ConnectionInfo` struct:
+/// - `server_version` (String)
+/// - `version_details` (Option<String>) - full version banner
+/// - `database_name` (Option<String>)
+/// - `user_name` (Option<String>)
+/// - `timezone` (Option<String>)
+/// - `max_connections` (Option<i64>)
+/// - `file_path` (Option<String>) - SQLite only; empty for in-memory databases
+///
+/// ## Example
+///
+/// ```rust
+/// use kodegen_tools_database::types::DatabaseType;
+/// use kodegen_tools_database::schema_queries::get_connection_info_query;
+///
+/// let sql = get_connection_info_query(DatabaseType::Postgres);
+/// assert!(sql.contains("current_database()"));
+///
+/// let sql = get_connection_info_query(DatabaseType::SQLite);
+/// assert!(sql.contains("sqlite_version()"));
+/// ```
+pub fn get_connection_info_query(db_type: DatabaseType) -> &'static str {
+    match db_type {
+        // Use CAST() for sqlx::any compatibility
+        DatabaseType::Postgres => {
+            "SELECT \
+                 CAST(current_setting('server_version') AS TEXT) as server_version, \
+                 CAST(version() AS TEXT) as version_details, \
+                 CAST(current_database() AS TEXT) as database_name, \
+                 CAST(current_user AS TEXT) as user_name, \
+                 CAST(current_setting('TimeZone') AS TEXT) as timezone, \
+                 CAST(current_setting('max_connections') AS BIGINT) as max_connections, \
+                 CAST(NULL AS TEXT) as file_path"
+        }
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            "SELECT \
+                 VERSION() as server_version, \
+                 @@version_comment as version_details, \
+                 DATABASE() as database_name, \
+                 CURRENT_USER() as user_name, \
+                 @@session.time_zone as timezone, \
+                 CAST(@@max_connections AS SIGNED) as max_connections, \
+                 CAST(NULL AS CHAR) as file_path"
+        }
+        DatabaseType::SQLite => {
+            // SQLite has no users, time zones or connection limits
+            "SELECT \
+                 sqlite_version() as server_version, \
+                 NULL as version_details, \
+                 'main' as database_name, \
+                 NULL as user_name, \
+                 NULL as timezone, \
+                 NULL as max_connections, \
+                 (SELECT file FROM pragma_database_list WHERE name = 'main') as file_path"
+        }
+        DatabaseType::SqlServer => {
+            "SELECT \
+                 CAST(SERVERPROPERTY('ProductVersion') AS NVARCHAR(128)) as server_version, \
+                 @@VERSION as version_details, \
+                 DB_NAME() as database_name, \
+                 SUSER_SNAME() as user_name, \
+                 CURRENT_TIMEZONE() as timezone, \
+                 CAST(@@MAX_CONNECTIONS AS BIGINT) as max_connections, \
+                 CAST(NULL AS NVARCHAR(1)) as file_path"
+        }
+    }
+}
+
+/// Longest accepted search term for [`get_search_schema_query`]
+pub const MAX_SEARCH_TERM_LEN: usize = 128;
+
+/// Returns SQL to find tables and columns whose name contains `term` + parameters
+///
+/// Searches every user schema (the current database for MySQL, `main` for SQLite)
+/// case-insensitively. The term is bound as a parameter with LIKE wildcards
+/// (`%`, `_`) escaped, so it always matches literally.
+///
+/// ## Return Columns
+///
+/// - `schema_name` (String)
+/// - `table_name` (String)
+/// - `column_name` (String) - every column of a table whose own name matches
+///
+/// ## SQLite
+///
+/// Joins `sqlite_master` with `pragma_table_info()` so each table's columns are
+/// read in one statement.
+///
+/// ## Example
+///
+/// ```rust
+/// use kodegen_tools_database::types::DatabaseType;
+/// use kodegen_tools_database::schema_queries::get_search_schema_query;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let (sql, params) = get_search_schema_query(DatabaseType::Postgres, "user_id")?;
+/// assert!(sql.contains("information_schema.columns"));
+/// assert_eq!(params, vec!["%user!_id%".to_string()]);
+///
+/// assert!(get_search_schema_query(DatabaseType::Postgres, "  ").is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns error if the term is empty, longer than [`MAX_SEARCH_TERM_LEN`], or
+/// contains control characters
+pub fn get_search_schema_query(
+    db_type: DatabaseType,
+    term: &str,
+) -> Result<(String, Vec<String>), DatabaseError> {
+    let term = term.trim();
+    if term.is_empty() {
+        return Err(DatabaseError::QueryError("Search term must not be empty".to_string()));
+    }
+    if term.chars().count() > MAX_SEARCH_TERM_LEN {
+        return Err(DatabaseError::QueryError(format!(
+            "Search term is longer than {} characters",
+            MAX_SEARCH_TERM_LEN
+        )));
+    }
+    if term.chars().any(char::is_control) {
+        return Err(DatabaseError::QueryError(
+            "Search term must not contain control characters".to_string(),
+        ));
+    }
+
+    // '!' is used as the LIKE escape character: it needs no escaping in any dialect's literals
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for c in term.chars() {
+        if matches!(c, '!' | '%' | '_') {
+            pattern.push('!');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+
+    match db_type {
+        DatabaseType::Postgres => {
+            // Use CAST() for sqlx::any compatibility
+            let sql = "SELECT \
+                           CAST(table_schema AS TEXT) as schema_name, \
+                           CAST(table_name AS TEXT) as table_name, \
+                           CAST(column_name AS TEXT) as column_name \
+                       FROM information_schema.columns \
+                       WHERE table_schema NOT IN ('pg_catalog', 'information_schema') \
+                         AND (table_name ILIKE $1 ESCAPE '!' OR column_name ILIKE $1 ESCAPE '!') \
+                       ORDER BY table_schema, table_name, ordinal_position"
+                .to_string();
+            Ok((sql, vec![pattern]))
+        }
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            let sql = "SELECT table_schema as schema_name, table_name, column_name \
+                       FROM information_schema.columns \
+                       WHERE table_schema = DATABASE() \
+                         AND (table_name LIKE ? ESCAPE '!' OR column_name LIKE ? ESCAPE '!') \
+                       ORDER BY table_name, ordinal_position"
+                .to_string();
+            Ok((sql, vec![pattern.clone(), pattern]))
+        }
+        DatabaseType::SQLite => {
+            // SQLite LIKE is case-insensitive for ASCII by default
+            let sql = "SELECT 'main' as schema_name, m.name as table_name, p.name as column_name \
+                       FROM sqlite_master m \
+                       JOIN pragma_table_info(m.name) p \
+                       WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%' \
+                         AND (m.name LIKE ? ESCAPE '!' OR p.name LIKE ? ESCAPE '!') \
+                       ORDER BY m.name, p.cid"
+                .to_string();
+            Ok((sql, vec![pattern.clone(), pattern]))
+        }
+        DatabaseType::SqlServer => {
+            let sql = "SELECT table_schema as schema_name, table_name, column_name \
+                       FROM information_schema.columns \
+                       WHERE table_name LIKE @P1 ESCAPE '!' OR column_name LIKE @P2 ESCAPE '!' \
+                       ORDER BY table_schema, table_name, ordinal_position"
+                .to_string();
+            Ok((sql, vec![pattern.clone(), pattern]))
+        }
+    }
+}
+
 /// Returns the default schema name for each database type
 ///
 /// ## Return Values
@@ -527,3 +1163,258 @@ pub fn get_default_schema(db_type: DatabaseType) -> Option<&'static str> {
         DatabaseType::SqlServer => Some("dbo"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{AnyPool, Row};
+
+    async fn sqlite_fixture() -> AnyPool {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        for stmt in [
+            "CREATE TABLE employees (id INTEGER PRIMARY KEY)",
+            "CREATE TABLE employee_projects (id INTEGER PRIMARY KEY)",
+            "CREATE TABLE departments (id INTEGER PRIMARY KEY)",
+            "CREATE VIEW employee_summary AS SELECT id FROM employees",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.expect("fixture");
+        }
+        pool
+    }
+
+    async fn table_names(pool: &AnyPool, sql: &str, params: &[String]) -> Vec<String> {
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = query.bind(param);
+        }
+        query
+            .fetch_all(pool)
+            .await
+            .expect("list tables")
+            .iter()
+            .map(|row| row.try_get("table_name").expect("table_name"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_tables_prefix_pattern() {
+        let pool = sqlite_fixture().await;
+        let (sql, params) = get_tables_query(DatabaseType::SQLite, None, Some("EMP%"), false);
+        assert_eq!(
+            table_names(&pool, &sql, &params).await,
+            vec!["employee_projects", "employees"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tables_include_views() {
+        let pool = sqlite_fixture().await;
+
+        let (sql, params) = get_tables_query(DatabaseType::SQLite, None, None, false);
+        assert!(!table_names(&pool, &sql, &params).await.contains(&"employee_summary".to_string()));
+
+        let (sql, params) = get_tables_query(DatabaseType::SQLite, None, Some("emp%"), true);
+        assert_eq!(
+            table_names(&pool, &sql, &params).await,
+            vec!["employee_projects", "employee_summary", "employees"]
+        );
+    }
+
+    #[test]
+    fn test_tables_pattern_is_parameterized() {
+        let pattern = "x' OR '1'='1";
+        let (sql, params) = get_tables_query(DatabaseType::MySQL, None, Some(pattern), true);
+        assert!(!sql.contains(pattern));
+        assert!(sql.contains("table_type IN ('BASE TABLE', 'VIEW')"));
+        assert_eq!(params, vec![pattern.to_string()]);
+
+        let (sql, params) = get_tables_query(DatabaseType::Postgres, Some("hr"), Some(pattern), false);
+        assert!(!sql.contains(pattern));
+        assert!(sql.contains("table_type = 'BASE TABLE'"));
+        assert_eq!(params, vec!["hr".to_string(), pattern.to_string()]);
+    }
+
+    #[test]
+    fn test_kill_query_sql_per_dialect() {
+        assert_eq!(
+            get_kill_query_sql(DatabaseType::Postgres, 7, false).unwrap(),
+            "SELECT pg_cancel_backend(7) AS success"
+        );
+        assert_eq!(
+            get_kill_query_sql(DatabaseType::Postgres, 7, true).unwrap(),
+            "SELECT pg_terminate_backend(7) AS success"
+        );
+        assert_eq!(get_kill_query_sql(DatabaseType::MySQL, 7, false).unwrap(), "KILL QUERY 7");
+        assert_eq!(get_kill_query_sql(DatabaseType::MariaDB, 7, true).unwrap(), "KILL 7");
+        assert_eq!(get_kill_query_sql(DatabaseType::SqlServer, 7, false).unwrap(), "KILL 7");
+        assert!(matches!(
+            get_kill_query_sql(DatabaseType::SQLite, 7, false),
+            Err(DatabaseError::FeatureNotSupported(_))
+        ));
+        assert!(get_kill_query_sql(DatabaseType::MySQL, 0, false).is_err());
+        assert!(get_kill_query_sql(DatabaseType::MySQL, -3, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_schema_partial_column_name() {
+        let pool = sqlite_fixture().await;
+        for stmt in [
+            "CREATE TABLE customers (id INTEGER PRIMARY KEY, email_address TEXT)",
+            "CREATE TABLE invoices (id INTEGER PRIMARY KEY, billing_email TEXT, total REAL)",
+            "CREATE TABLE emailer_log (id INTEGER PRIMARY KEY, sent_at TEXT)",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.expect("fixture");
+        }
+
+        let (sql, params) = get_search_schema_query(DatabaseType::SQLite, "EMAIL").unwrap();
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        let matches: Vec<(String, String, String)> = query
+            .fetch_all(&pool)
+            .await
+            .expect("search schema")
+            .iter()
+            .map(|row| {
+                (
+                    row.try_get("schema_name").expect("schema_name"),
+                    row.try_get("table_name").expect("table_name"),
+                    row.try_get("column_name").expect("column_name"),
+                )
+            })
+            .collect();
+
+        let found = |table: &str, column: &str| {
+            ("main".to_string(), table.to_string(), column.to_string())
+        };
+        assert_eq!(
+            matches,
+            vec![
+                found("customers", "email_address"),
+                // Table name match lists all of the table's columns
+                found("emailer_log", "id"),
+                found("emailer_log", "sent_at"),
+                found("invoices", "billing_email"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_schema_term_is_escaped_and_validated() {
+        let term = "x' OR '1'='1";
+        let (sql, params) = get_search_schema_query(DatabaseType::MySQL, term).unwrap();
+        assert!(!sql.contains(term));
+        assert_eq!(params, vec![format!("%{}%", term), format!("%{}%", term)]);
+
+        let (_, params) = get_search_schema_query(DatabaseType::Postgres, " 100%_off! ").unwrap();
+        assert_eq!(params, vec!["%100!%!_off!!%".to_string()]);
+
+        assert!(get_search_schema_query(DatabaseType::SQLite, "").is_err());
+        assert!(get_search_schema_query(DatabaseType::SQLite, "a\nb").is_err());
+        assert!(get_search_schema_query(DatabaseType::SQLite, &"a".repeat(129)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_attached_database() {
+        let pool = sqlite_fixture().await;
+        for stmt in [
+            "ATTACH DATABASE ':memory:' AS archive",
+            "CREATE TABLE archive.old_orders (id INTEGER PRIMARY KEY, total REAL NOT NULL)",
+            "CREATE INDEX archive.idx_old_orders_total ON old_orders (total)",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.expect("attach fixture");
+        }
+
+        let schemas: Vec<String> = sqlx::query(&get_schemas_query(DatabaseType::SQLite))
+            .fetch_all(&pool)
+            .await
+            .expect("list schemas")
+            .iter()
+            .map(|row| row.try_get("schema_name").expect("schema_name"))
+            .collect();
+        assert_eq!(schemas, vec!["main", "archive"]);
+
+        let (sql, params) = get_tables_query(DatabaseType::SQLite, Some("archive"), None, false);
+        assert_eq!(table_names(&pool, &sql, &params).await, vec!["old_orders"]);
+        let (sql, params) = get_tables_query(DatabaseType::SQLite, None, None, false);
+        assert!(!table_names(&pool, &sql, &params).await.contains(&"old_orders".to_string()));
+
+        let (sql, params) = get_table_schema_query(DatabaseType::SQLite, "archive", "old_orders")
+            .expect("table schema query");
+        assert_eq!(params, vec!["old_orders", "archive"]);
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        let columns = query.fetch_all(&pool).await.expect("table_info");
+        assert_eq!(columns.len(), 2);
+        let (sql, params) = get_indexes_query(DatabaseType::SQLite, "archive", "old_orders")
+            .expect("indexes query");
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        let indexes = query.fetch_all(&pool).await.expect("index_list");
+        assert_eq!(indexes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_table_schema_binds_special_names() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        let hostile = "orders\"); DROP TABLE keep; --";
+        for stmt in [
+            "CREATE TABLE keep (id INTEGER)",
+            "CREATE TABLE \"orders\"\"); DROP TABLE keep; --\" (id INTEGER, \"total $\" REAL)",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.expect("fixture");
+        }
+
+        let (sql, params) =
+            get_table_schema_query(DatabaseType::SQLite, "main", hostile).expect("query");
+        assert!(!sql.contains("DROP"), "{}", sql);
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        let names: Vec<String> = query
+            .fetch_all(&pool)
+            .await
+            .expect("pragma_table_info")
+            .iter()
+            .map(|row| row.try_get("name").expect("name"))
+            .collect();
+        assert_eq!(names, vec!["id", "total $"]);
+
+        // A hostile schema name is just an unknown database: an error, no injection
+        let (sql, params) =
+            get_table_schema_query(DatabaseType::SQLite, "main; DROP TABLE keep", "keep")
+                .expect("query");
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        let err = query.fetch_all(&pool).await.expect_err("unknown schema");
+        assert!(
+            err.to_string().contains("unknown database 'main; DROP TABLE keep'"),
+            "{}",
+            err
+        );
+        let kept: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'keep'")
+                .fetch_one(&pool)
+                .await
+                .expect("count");
+        assert_eq!(kept, 1);
+    }
+}
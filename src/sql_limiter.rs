@@ -1,15 +1,20 @@
 //! SQL query result limiting to prevent excessive data transfer
 
 use crate::error::DatabaseError;
-use crate::sql_parser::extract_first_keyword;
+use crate::sql_parser::{extract_first_keyword, get_dialect};
 use crate::types::DatabaseType;
 use lazy_regex::{Lazy, Regex, lazy_regex};
+use sqlparser::ast::{Expr, LimitClause, Offset, OffsetRows, Statement, Value, ValueWithSpan};
+use sqlparser::parser::Parser;
 
 // Compile-time validated regexes
 static LIMIT_REGEX: Lazy<Regex> = lazy_regex!(r"(?i)\bLIMIT\s+(\d+)");
 static TOP_REGEX: Lazy<Regex> = lazy_regex!(r"(?i)\bSELECT\s+TOP\s+\(?\d+\)?");
 static SELECT_TOP_REPLACE: Lazy<Regex> = lazy_regex!(r"(?i)\bSELECT\s+TOP\s+\(?\d+\)?");
 static SELECT_WORD: Lazy<Regex> = lazy_regex!(r"(?i)\bSELECT\b");
+// MySQL/MariaDB SHOW statements whose grammar ends in an optional LIMIT
+static SHOW_WITH_LIMIT: Lazy<Regex> =
+    lazy_regex!(r"(?i)^\s*SHOW\s+(WARNINGS|ERRORS|BINLOG\s+EVENTS|RELAYLOG\s+EVENTS)\b");
 
 /// Apply row limit to SELECT queries only
 ///
@@ -54,6 +59,262 @@ pub fn apply_row_limit(
     }
 }
 
+/// Check whether a statement is SHOW (or, on SQLite, PRAGMA) introspection
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::is_introspection_statement;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// assert!(is_introspection_statement("SHOW VARIABLES", DatabaseType::MySQL));
+/// assert!(is_introspection_statement("PRAGMA table_info(users)", DatabaseType::SQLite));
+/// assert!(!is_introspection_statement("SELECT 1", DatabaseType::MySQL));
+/// ```
+pub fn is_introspection_statement(sql: &str, db_type: DatabaseType) -> bool {
+    match extract_first_keyword(sql, db_type).as_deref() {
+        Ok("show") => db_type != DatabaseType::SQLite,
+        Ok("pragma") => db_type == DatabaseType::SQLite,
+        _ => false,
+    }
+}
+
+/// Apply row limit to SHOW statements whose dialect accepts a LIMIT clause
+///
+/// Only MySQL/MariaDB `SHOW WARNINGS`, `SHOW ERRORS`, `SHOW BINLOG EVENTS` and
+/// `SHOW RELAYLOG EVENTS` take a LIMIT. Every other statement, including
+/// `SHOW VARIABLES` and SQLite PRAGMAs, is returned unchanged; its rows must be
+/// capped after fetching instead.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::apply_show_limit;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let limited = apply_show_limit("SHOW WARNINGS", 100, DatabaseType::MySQL)?;
+/// assert_eq!(limited, "SHOW WARNINGS LIMIT 100");
+///
+/// let unchanged = apply_show_limit("SHOW VARIABLES", 100, DatabaseType::MySQL)?;
+/// assert_eq!(unchanged, "SHOW VARIABLES");
+/// # Ok(())
+/// # }
+/// ```
+pub fn apply_show_limit(
+    sql: &str,
+    max_rows: usize,
+    db_type: DatabaseType,
+) -> Result<String, DatabaseError> {
+    let accepts_limit = matches!(db_type, DatabaseType::MySQL | DatabaseType::MariaDB)
+        && SHOW_WITH_LIMIT.is_match(sql);
+    if !accepts_limit {
+        return Ok(sql.to_string());
+    }
+    apply_standard_limit(sql, max_rows)
+}
+
+/// Extract an explicit `LIMIT n` from a query, if present
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::extract_row_limit;
+/// assert_eq!(extract_row_limit("SELECT * FROM users LIMIT 25"), Some(25));
+/// assert_eq!(extract_row_limit("SELECT * FROM users"), None);
+/// ```
+pub fn extract_row_limit(sql: &str) -> Option<usize> {
+    LIMIT_REGEX
+        .captures(sql)
+        .and_then(|captures| captures[1].parse().ok())
+}
+
+/// Check whether a query has a LIMIT but no ORDER BY on its outermost query
+///
+/// Such queries return an arbitrary subset of rows, so paging through them
+/// with successive LIMIT/OFFSET calls can skip or repeat rows. Statements that
+/// fail to parse are reported as `false`.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::is_limited_without_order_by;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// assert!(is_limited_without_order_by("SELECT * FROM users LIMIT 10", DatabaseType::Postgres));
+/// assert!(!is_limited_without_order_by("SELECT * FROM users ORDER BY id LIMIT 10", DatabaseType::Postgres));
+/// ```
+pub fn is_limited_without_order_by(sql: &str, db_type: DatabaseType) -> bool {
+    let dialect = get_dialect(db_type);
+    let Ok(statements) = Parser::parse_sql(dialect.as_ref(), sql) else {
+        return false;
+    };
+    match statements.first() {
+        Some(Statement::Query(query)) => query.limit_clause.is_some() && query.order_by.is_none(),
+        _ => false,
+    }
+}
+
+/// Check whether a query's outermost level has an ORDER BY
+///
+/// Statements that fail to parse are reported as `false`.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::has_order_by;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// assert!(has_order_by("SELECT * FROM users ORDER BY id", DatabaseType::SQLite));
+/// assert!(!has_order_by("SELECT * FROM users", DatabaseType::SQLite));
+/// ```
+pub fn has_order_by(sql: &str, db_type: DatabaseType) -> bool {
+    let dialect = get_dialect(db_type);
+    let Ok(statements) = Parser::parse_sql(dialect.as_ref(), sql) else {
+        return false;
+    };
+    matches!(statements.first(), Some(Statement::Query(query)) if query.order_by.is_some())
+}
+
+/// Set `LIMIT page_size OFFSET offset` on the outermost level of a query
+///
+/// The query is parsed and the clause set on its AST, so ORDER BY, UNION and
+/// CTEs keep their meaning; the result is re-rendered from the AST.
+///
+/// # Errors
+/// Returns error if `sql` is not a single query, already has a LIMIT, OFFSET
+/// or FETCH, or `page_size` is 0. SQL Server is not supported.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::apply_page;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let page = apply_page("SELECT * FROM users ORDER BY id", 50, 100, DatabaseType::Postgres)?;
+/// assert_eq!(page, "SELECT * FROM users ORDER BY id LIMIT 50 OFFSET 100");
+/// # Ok(())
+/// # }
+/// ```
+pub fn apply_page(
+    sql: &str,
+    page_size: usize,
+    offset: usize,
+    db_type: DatabaseType,
+) -> Result<String, DatabaseError> {
+    if db_type == DatabaseType::SqlServer {
+        return Err(DatabaseError::FeatureNotSupported(
+            "LIMIT/OFFSET paging is not supported on SQL Server".to_string(),
+        ));
+    }
+    if page_size == 0 {
+        return Err(DatabaseError::QueryError("page_size must be at least 1".to_string()));
+    }
+
+    let dialect = get_dialect(db_type);
+    let mut statements = Parser::parse_sql(dialect.as_ref(), sql)
+        .map_err(|e| DatabaseError::QueryError(format!("Failed to parse query: {}", e)))?;
+    let query = match statements.as_mut_slice() {
+        [Statement::Query(query)] => query,
+        _ => {
+            return Err(DatabaseError::QueryError(
+                "Paging needs exactly one SELECT query".to_string(),
+            ));
+        }
+    };
+    if query.limit_clause.is_some() || query.fetch.is_some() {
+        return Err(DatabaseError::QueryError(
+            "Query to page already has LIMIT, OFFSET or FETCH".to_string(),
+        ));
+    }
+
+    let number = |n: usize| Expr::value(Value::Number(n.to_string(), false));
+    query.limit_clause = Some(LimitClause::LimitOffset {
+        limit: Some(number(page_size)),
+        offset: Some(Offset {
+            value: number(offset),
+            rows: OffsetRows::None,
+        }),
+        limit_by: Vec::new(),
+    });
+    Ok(statements[0].to_string())
+}
+
+/// `LIMIT` written when an OFFSET needs one: MySQL and SQLite reject OFFSET alone
+const UNBOUNDED_LIMIT: u64 = i64::MAX as u64;
+
+/// Skip the first `skip_rows` rows of a single SELECT through its OFFSET
+///
+/// An existing `LIMIT n OFFSET m` (or MySQL `LIMIT m, n`) becomes
+/// `LIMIT n - skip_rows OFFSET m + skip_rows`, so the skip applies to the
+/// query's own result; without a LIMIT, an unbounded one is added. Apply this
+/// before [`apply_row_limit`], which then caps the remaining rows.
+///
+/// Returns `None` when the statement cannot take the skip in SQL: anything but
+/// one SELECT, SQL Server, `FETCH`, or a LIMIT/OFFSET that is not a number. The
+/// caller then discards the leading rows after fetching instead.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_limiter::{apply_row_limit, apply_row_offset};
+/// # use kodegen_tools_database::types::DatabaseType;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sql = "SELECT * FROM users ORDER BY id LIMIT 20 OFFSET 10";
+/// let skipped = apply_row_offset(sql, 5, DatabaseType::Postgres)?;
+/// assert_eq!(skipped.as_deref(), Some("SELECT * FROM users ORDER BY id LIMIT 15 OFFSET 15"));
+///
+/// let skipped = apply_row_offset("SELECT * FROM users ORDER BY id", 5, DatabaseType::SQLite)?;
+/// let page = apply_row_limit(&skipped.unwrap_or_default(), 5, DatabaseType::SQLite)?;
+/// assert_eq!(page, "SELECT * FROM users ORDER BY id LIMIT 5 OFFSET 5");
+/// # Ok(())
+/// # }
+/// ```
+pub fn apply_row_offset(
+    sql: &str,
+    skip_rows: usize,
+    db_type: DatabaseType,
+) -> Result<Option<String>, DatabaseError> {
+    if db_type == DatabaseType::SqlServer || extract_first_keyword(sql, db_type)? != "select" {
+        return Ok(None);
+    }
+
+    let dialect = get_dialect(db_type);
+    let Ok(mut statements) = Parser::parse_sql(dialect.as_ref(), sql) else {
+        return Ok(None);
+    };
+    let query = match statements.as_mut_slice() {
+        [Statement::Query(query)] if query.fetch.is_none() => query,
+        _ => return Ok(None),
+    };
+
+    let literal = |expr: &Expr| match expr {
+        Expr::Value(ValueWithSpan { value: Value::Number(n, _), .. }) => n.parse::<u64>().ok(),
+        _ => None,
+    };
+    let (limit, offset) = match &query.limit_clause {
+        None => (None, Some(0)),
+        Some(LimitClause::LimitOffset { limit, offset, limit_by }) if limit_by.is_empty() => {
+            if limit.as_ref().is_some_and(|limit| literal(limit).is_none()) {
+                return Ok(None);
+            }
+            let offset = offset.as_ref().map_or(Some(0), |offset| literal(&offset.value));
+            (limit.as_ref().and_then(literal), offset)
+        }
+        Some(LimitClause::OffsetCommaLimit { offset, limit }) => {
+            let Some(limit) = literal(limit) else {
+                return Ok(None);
+            };
+            (Some(limit), literal(offset))
+        }
+        Some(_) => return Ok(None),
+    };
+    let Some(offset) = offset else {
+        return Ok(None);
+    };
+
+    let skip = skip_rows as u64;
+    let number = |n: u64| Expr::value(Value::Number(n.to_string(), false));
+    query.limit_clause = Some(LimitClause::LimitOffset {
+        limit: Some(number(limit.map_or(UNBOUNDED_LIMIT, |limit| limit.saturating_sub(skip)))),
+        offset: Some(Offset {
+            value: number(offset.saturating_add(skip)),
+            rows: OffsetRows::None,
+        }),
+        limit_by: Vec::new(),
+    });
+    Ok(Some(statements[0].to_string()))
+}
+
 /// Apply LIMIT clause for PostgreSQL, MySQL, MariaDB, SQLite
 fn apply_standard_limit(sql: &str, max_rows: usize) -> Result<String, DatabaseError> {
     if let Some(captures) = LIMIT_REGEX.captures(sql) {
@@ -154,4 +415,98 @@ mod tests {
             assert!(!result.contains("LIMIT"));
         }
     }
+
+    #[test]
+    fn test_limited_without_order_by_detected() {
+        let limited = apply_row_limit("SELECT * FROM users", 100, DatabaseType::Postgres).unwrap();
+        assert!(is_limited_without_order_by(&limited, DatabaseType::Postgres));
+        assert!(is_limited_without_order_by("SELECT id FROM t LIMIT 5 OFFSET 10", DatabaseType::MySQL));
+    }
+
+    #[test]
+    fn test_limited_with_order_by_not_flagged() {
+        let limited =
+            apply_row_limit("SELECT * FROM users ORDER BY id", 100, DatabaseType::SQLite).unwrap();
+        assert!(!is_limited_without_order_by(&limited, DatabaseType::SQLite));
+        // Inner ORDER BY doesn't order the outer result
+        assert!(is_limited_without_order_by(
+            "SELECT * FROM (SELECT * FROM t ORDER BY id) s LIMIT 5",
+            DatabaseType::Postgres
+        ));
+        // No LIMIT, or not a query
+        assert!(!is_limited_without_order_by("SELECT * FROM users", DatabaseType::Postgres));
+        assert!(!is_limited_without_order_by("DELETE FROM users", DatabaseType::Postgres));
+    }
+
+    #[test]
+    fn test_show_limit_only_where_dialect_permits() {
+        let limited = apply_show_limit("SHOW WARNINGS;", 100, DatabaseType::MariaDB).unwrap();
+        assert_eq!(limited, "SHOW WARNINGS LIMIT 100;");
+        let limited =
+            apply_show_limit("show binlog events limit 500", 100, DatabaseType::MySQL).unwrap();
+        assert_eq!(limited, "show binlog events LIMIT 100");
+
+        for (sql, db_type) in [
+            ("SHOW VARIABLES", DatabaseType::MySQL),
+            ("SHOW ALL", DatabaseType::Postgres),
+            ("PRAGMA table_info(users)", DatabaseType::SQLite),
+        ] {
+            assert_eq!(apply_show_limit(sql, 100, db_type).unwrap(), sql);
+            assert!(is_introspection_statement(sql, db_type), "{}", sql);
+        }
+        assert!(!is_introspection_statement("PRAGMA foo", DatabaseType::Postgres));
+    }
+
+    #[test]
+    fn test_apply_page() {
+        let page = apply_page("SELECT id FROM t ORDER BY id", 2, 4, DatabaseType::SQLite).unwrap();
+        assert_eq!(page, "SELECT id FROM t ORDER BY id LIMIT 2 OFFSET 4");
+        // The clause goes on the whole UNION, not its last branch
+        let page =
+            apply_page("SELECT a FROM x UNION SELECT a FROM y", 10, 0, DatabaseType::MySQL).unwrap();
+        assert_eq!(page, "SELECT a FROM x UNION SELECT a FROM y LIMIT 10 OFFSET 0");
+
+        assert!(apply_page("SELECT id FROM t LIMIT 5", 2, 0, DatabaseType::SQLite).is_err());
+        assert!(apply_page("DELETE FROM t", 2, 0, DatabaseType::SQLite).is_err());
+        assert!(apply_page("SELECT 1; SELECT 2", 2, 0, DatabaseType::SQLite).is_err());
+        assert!(apply_page("SELECT id FROM t", 0, 0, DatabaseType::SQLite).is_err());
+    }
+
+    #[test]
+    fn test_apply_row_offset() {
+        let skip = |sql: &str, db_type| apply_row_offset(sql, 5, db_type).unwrap();
+        assert_eq!(
+            skip("SELECT id FROM t ORDER BY id", DatabaseType::Postgres).as_deref(),
+            Some("SELECT id FROM t ORDER BY id LIMIT 9223372036854775807 OFFSET 5")
+        );
+        assert_eq!(
+            skip("SELECT id FROM t ORDER BY id LIMIT 20 OFFSET 10", DatabaseType::SQLite)
+                .as_deref(),
+            Some("SELECT id FROM t ORDER BY id LIMIT 15 OFFSET 15")
+        );
+        // Skipping past the query's own LIMIT leaves nothing
+        assert_eq!(
+            skip("SELECT id FROM t LIMIT 3", DatabaseType::Postgres).as_deref(),
+            Some("SELECT id FROM t LIMIT 0 OFFSET 5")
+        );
+        assert_eq!(
+            skip("SELECT id FROM t ORDER BY id LIMIT 10, 20", DatabaseType::MySQL).as_deref(),
+            Some("SELECT id FROM t ORDER BY id LIMIT 15 OFFSET 15")
+        );
+
+        // Left to the caller to discard rows after fetching
+        assert_eq!(skip("SHOW TABLES", DatabaseType::MySQL), None);
+        assert_eq!(skip("SELECT id FROM t", DatabaseType::SqlServer), None);
+        assert_eq!(skip("SELECT id FROM t LIMIT $1", DatabaseType::Postgres), None);
+        assert_eq!(skip("SELECT 1; SELECT 2", DatabaseType::SQLite), None);
+    }
+
+    #[test]
+    fn test_row_offset_then_row_limit() {
+        let sql = apply_row_offset("SELECT id FROM t ORDER BY id", 5, DatabaseType::SQLite)
+            .unwrap()
+            .unwrap();
+        let page = apply_row_limit(&sql, 6, DatabaseType::SQLite).unwrap();
+        assert_eq!(page, "SELECT id FROM t ORDER BY id LIMIT 6 OFFSET 5");
+    }
 }
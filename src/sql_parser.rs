@@ -4,12 +4,18 @@
 
 use crate::error::DatabaseError;
 use crate::types::DatabaseType;
+use lazy_regex::{Lazy, Regex, lazy_regex};
 use sqlparser::dialect::{Dialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
 use sqlparser::parser::Parser;
 use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
 
+// Compile-time validated regexes used by fingerprint_sql
+static FALLBACK_STRING_REGEX: Lazy<Regex> = lazy_regex!(r"'(?:[^']|'')*'?");
+static FALLBACK_NUMBER_REGEX: Lazy<Regex> = lazy_regex!(r"\b\d+(?:\.\d+)?\b");
+static IN_LIST_REGEX: Lazy<Regex> = lazy_regex!(r"\(\s*\?(?:\s*,\s*\?)+\s*\)");
+
 /// Get appropriate SQL dialect for the database type
-fn get_dialect(db_type: DatabaseType) -> Box<dyn Dialect> {
+pub(crate) fn get_dialect(db_type: DatabaseType) -> Box<dyn Dialect> {
     match db_type {
         DatabaseType::Postgres => Box::new(PostgreSqlDialect {}),
         DatabaseType::MySQL | DatabaseType::MariaDB => Box::new(MySqlDialect {}),
@@ -23,6 +29,10 @@ fn get_dialect(db_type: DatabaseType) -> Box<dyn Dialect> {
 /// Uses sqlparser crate for proper SQL parsing with validation.
 /// Detects unterminated string literals and returns an error.
 ///
+/// Trailing semicolons are tolerated, and empty statements (`SELECT 1;;`,
+/// a leading `;`, or whitespace between semicolons) are dropped rather than
+/// returned for execution, so input of only semicolons yields no statements.
+///
 /// # Examples
 /// ```
 /// # use kodegen_tools_database::sql_parser::split_sql_statements;
@@ -47,7 +57,13 @@ pub fn split_sql_statements(
     let dialect = get_dialect(db_type);
 
     Parser::parse_sql(&*dialect, sql)
-        .map(|stmts| stmts.iter().map(|s| s.to_string()).collect())
+        .map(|stmts| {
+            stmts
+                .iter()
+                .map(|s| s.to_string())
+                .filter(|s| !s.trim().is_empty())
+                .collect()
+        })
         .map_err(|e| DatabaseError::QueryError(format!("SQL parse error: {}", e)))
 }
 
@@ -115,6 +131,25 @@ pub fn strip_comments(sql: &str, db_type: DatabaseType) -> String {
     }
 }
 
+/// Byte order mark that Windows editors put at the start of UTF-8 files
+const UTF8_BOM: char = '\u{feff}';
+
+/// Prepare raw SQL input for parsing
+///
+/// Strips a leading UTF-8 byte order mark, which is not whitespace to the
+/// tokenizer and would otherwise be read as part of the first keyword. CRLF
+/// line endings are left as they are: the tokenizer already treats `\r` as
+/// whitespace, and rewriting them would change multi-line string literals.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_parser::normalize_sql_input;
+/// assert_eq!(normalize_sql_input("\u{feff}SELECT 1"), "SELECT 1");
+/// ```
+pub fn normalize_sql_input(sql: &str) -> &str {
+    sql.trim_start_matches(UTF8_BOM)
+}
+
 /// Extract first SQL keyword from statement (after stripping comments)
 ///
 /// # Examples
@@ -131,7 +166,7 @@ pub fn strip_comments(sql: &str, db_type: DatabaseType) -> String {
 /// # }
 /// ```
 pub fn extract_first_keyword(sql: &str, db_type: DatabaseType) -> Result<String, DatabaseError> {
-    let cleaned = strip_comments(sql, db_type);
+    let cleaned = strip_comments(normalize_sql_input(sql), db_type);
     let trimmed = cleaned.trim();
 
     if trimmed.is_empty() {
@@ -150,10 +185,78 @@ pub fn extract_first_keyword(sql: &str, db_type: DatabaseType) -> Result<String,
     Ok(keyword)
 }
 
+/// Normalize SQL into a literal-free fingerprint
+///
+/// Replaces string and numeric literals (and bind placeholders) with `?`, strips
+/// comments, collapses whitespace, and folds `IN (?, ?, ...)` lists into `IN (?)`.
+/// Queries that differ only in their literal values produce the same fingerprint,
+/// which makes the result safe to display without leaking data.
+///
+/// # Examples
+/// ```
+/// # use kodegen_tools_database::sql_parser::fingerprint_sql;
+/// # use kodegen_tools_database::types::DatabaseType;
+/// let fp = fingerprint_sql(
+///     "SELECT * FROM users WHERE email = 'alice@example.com' AND id IN (1, 2, 3)",
+///     DatabaseType::Postgres,
+/// );
+/// assert_eq!(fp, "SELECT * FROM users WHERE email = ? AND id IN (?)");
+/// ```
+///
+/// # Error Handling
+/// If tokenization fails (e.g. query text truncated by the server), falls back to
+/// regex-based literal replacement so literals are never echoed back.
+pub fn fingerprint_sql(sql: &str, db_type: DatabaseType) -> String {
+    let dialect = get_dialect(db_type);
+    let mut tokenizer = Tokenizer::new(&*dialect, sql);
+
+    let normalized = match tokenizer.tokenize() {
+        Ok(tokens) => tokens
+            .iter()
+            .filter_map(|token| match token {
+                // Comments and all other whitespace collapse to a single separator
+                Token::Whitespace(_) => Some(" ".to_string()),
+                Token::Number(..)
+                | Token::SingleQuotedString(_)
+                | Token::DoubleQuotedString(_)
+                | Token::TripleSingleQuotedString(_)
+                | Token::TripleDoubleQuotedString(_)
+                | Token::DollarQuotedString(_)
+                | Token::NationalStringLiteral(_)
+                | Token::EscapedStringLiteral(_)
+                | Token::HexStringLiteral(_)
+                | Token::Placeholder(_) => Some("?".to_string()),
+                Token::EOF => None,
+                other => Some(other.to_string()),
+            })
+            .collect::<String>(),
+        Err(_) => {
+            // Fallback: mask quoted strings (including unterminated ones) and numbers
+            let masked = FALLBACK_STRING_REGEX.replace_all(sql, "?");
+            FALLBACK_NUMBER_REGEX.replace_all(&masked, "?").to_string()
+        }
+    };
+
+    let collapsed = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+    IN_LIST_REGEX.replace_all(&collapsed, "(?)").to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_bom_prefixed_select_classified_as_read() {
+        let sql = "\u{feff}SELECT id FROM users";
+        assert_eq!(extract_first_keyword(sql, DatabaseType::Postgres).expect("keyword"), "select");
+        assert_eq!(normalize_sql_input(sql), "SELECT id FROM users");
+        // CRLF is only stripped of the BOM, so literals keep their line endings
+        assert_eq!(
+            normalize_sql_input("\u{feff}SELECT 'a\r\nb'\r\nFROM t"),
+            "SELECT 'a\r\nb'\r\nFROM t"
+        );
+    }
+
     #[test]
     fn test_split_respects_string_literals() {
         let sql = "SELECT 1; INSERT INTO t VALUES ('a;b;c'); SELECT 2;";
@@ -165,6 +268,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_drops_empty_statements() {
+        for db_type in [DatabaseType::Postgres, DatabaseType::MySQL, DatabaseType::SQLite] {
+            for sql in ["SELECT 1;;", ";SELECT 1", "SELECT 1; ", "SELECT 1; ;\n;"] {
+                let stmts = split_sql_statements(sql, db_type).expect("splits");
+                assert_eq!(stmts, vec!["SELECT 1".to_string()], "{:?} on {}", sql, db_type);
+            }
+            assert!(split_sql_statements(";;", db_type).expect("splits").is_empty());
+        }
+    }
+
     #[test]
     fn test_strip_preserves_strings() {
         let sql = "SELECT '-- not a comment' FROM t";
@@ -441,4 +555,33 @@ mod tests {
             cleaned
         );
     }
+
+    #[test]
+    fn test_fingerprint_replaces_literals() {
+        let sql = "SELECT name FROM users WHERE id = 42 AND email = 'bob@example.com'";
+        let fp = fingerprint_sql(sql, DatabaseType::Postgres);
+        assert_eq!(fp, "SELECT name FROM users WHERE id = ? AND email = ?");
+    }
+
+    #[test]
+    fn test_fingerprint_collapses_in_lists_and_whitespace() {
+        let sql = "SELECT *\n  FROM t -- trailing\n WHERE id IN ( 1,2 , 3 )";
+        let fp = fingerprint_sql(sql, DatabaseType::MySQL);
+        assert_eq!(fp, "SELECT * FROM t WHERE id IN (?)");
+    }
+
+    #[test]
+    fn test_fingerprint_masks_placeholders_and_dollar_quotes() {
+        let sql = "UPDATE t SET body = $$secret$$ WHERE id = $1";
+        let fp = fingerprint_sql(sql, DatabaseType::Postgres);
+        assert_eq!(fp, "UPDATE t SET body = ? WHERE id = ?");
+    }
+
+    #[test]
+    fn test_fingerprint_truncated_query_does_not_leak_literals() {
+        // Servers often truncate long query text mid-literal
+        let sql = "SELECT * FROM t WHERE token = 'abc123secr";
+        let fp = fingerprint_sql(sql, DatabaseType::Postgres);
+        assert!(!fp.contains("abc123secr"), "literal leaked: {}", fp);
+    }
 }
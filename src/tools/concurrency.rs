@@ -0,0 +1,139 @@
+//! Concurrency limiting for query execution
+//!
+//! Bounds how many queries may be in flight (running or waiting for a pool
+//! connection) so that a flood of tool calls fails fast instead of queuing
+//! without limit.
+//!
+//! One limiter (`DatabaseConnection::query_limiter`) is shared by every tool
+//! that runs SQL on the pools, except `KillQueryTool`: cancelling a runaway
+//! statement must still work when every slot is taken.
+
+use crate::error::DatabaseError;
+use kodegen_config_manager::ConfigManager;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Semaphore placed in front of the connection pool
+///
+/// Sized to `db_max_connections * db_queue_multiplier`: up to
+/// `db_max_connections` queries run while the rest wait for a connection.
+/// Requests beyond that are rejected immediately.
+#[derive(Clone, Debug)]
+pub struct QueryLimiter {
+    permits: Arc<Semaphore>,
+    limit: usize,
+}
+
+impl QueryLimiter {
+    /// Create a limiter allowing `limit` concurrent queries (minimum 1)
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(limit)),
+            limit,
+        }
+    }
+
+    /// Create a limiter sized from configuration
+    ///
+    /// # Configuration
+    ///
+    /// * `db_max_connections` - Pool size (default: 10)
+    /// * `db_queue_multiplier` - Allowed in-flight queries per connection (default: 4)
+    pub fn from_config(config: &ConfigManager) -> Self {
+        let max_connections = config
+            .get_value("db_max_connections")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(n as usize),
+                _ => None,
+            })
+            .unwrap_or(10); // Matches pool default in connection.rs
+
+        let queue_multiplier = config
+            .get_value("db_queue_multiplier")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(n as usize),
+                _ => None,
+            })
+            .unwrap_or(4);
+
+        Self::new(max_connections.saturating_mul(queue_multiplier))
+    }
+
+    /// Maximum number of concurrent queries
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Reserve a slot for one query without waiting
+    ///
+    /// The slot is released when the returned permit is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DatabaseError::ConcurrencyLimit` if all slots are in use.
+    pub fn try_acquire(&self) -> Result<OwnedSemaphorePermit, DatabaseError> {
+        self.permits.clone().try_acquire_owned().map_err(|_| {
+            DatabaseError::ConcurrencyLimit(format!(
+                "Too many concurrent queries (limit: {}). Retry shortly or raise \
+                 db_queue_multiplier / db_max_connections.",
+                self.limit
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_rejects_beyond_limit() {
+        let limiter = QueryLimiter::new(2);
+        let first = limiter.try_acquire();
+        let second = limiter.try_acquire();
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+
+        let third = limiter.try_acquire();
+        assert!(matches!(third, Err(DatabaseError::ConcurrencyLimit(_))));
+
+        // Releasing a slot lets the next query in
+        drop(first);
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_zero_limit_is_clamped() {
+        let limiter = QueryLimiter::new(0);
+        assert_eq!(limiter.limit(), 1);
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_excess_rejected_promptly_while_in_limit_proceed() {
+        let limiter = QueryLimiter::new(3);
+
+        // Three long-running "queries" hold every slot
+        let mut running = Vec::new();
+        for _ in 0..3 {
+            let permit = limiter.try_acquire().expect("in-limit query admitted");
+            running.push(tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                drop(permit);
+            }));
+        }
+
+        // An excess query is rejected immediately rather than waiting
+        let started = std::time::Instant::now();
+        assert!(limiter.try_acquire().is_err());
+        assert!(started.elapsed() < Duration::from_millis(50));
+
+        // In-limit queries complete and free their slots
+        for handle in running {
+            handle.await.expect("query task");
+        }
+        assert!(limiter.try_acquire().is_ok());
+    }
+}
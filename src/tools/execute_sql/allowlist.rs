@@ -0,0 +1,170 @@
+//! Restrict execution to a fixed set of vetted query shapes
+//!
+//! With `db_query_allowlist_path` set, the file is read once when the tool is
+//! created. Each non-empty line that doesn't start with `#` is a query; it is
+//! reduced to its [`fingerprint_sql`] form, so literals in the file are
+//! placeholders and callers may substitute any values. Statements whose
+//! fingerprint is not listed are rejected before they run.
+//!
+//! The allowlist is enforced by `ExecuteSQLTool` only, on the SQL as written
+//! (before `db_table_prefix` rewriting). The other tools run their own fixed
+//! introspection queries, and `db_preview_write` only runs a read-only
+//! `SELECT` derived from the write it is given.
+
+use crate::sql_parser::fingerprint_sql;
+use crate::types::DatabaseType;
+use anyhow::Context;
+use kodegen_config_manager::ConfigManager;
+use kodegen_mcp_schema::McpError;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Fingerprints of the queries allowed to run
+///
+/// # Example
+/// ```rust
+/// # use kodegen_tools_database::{DatabaseType, QueryAllowlist};
+/// let allowlist = QueryAllowlist::parse(
+///     "# vetted reports\nSELECT name FROM users WHERE id = ?\n",
+///     DatabaseType::Postgres,
+/// );
+/// assert!(allowlist.allows("SELECT name FROM users WHERE id = 42"));
+/// assert!(!allowlist.allows("SELECT * FROM users"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryAllowlist {
+    db_type: DatabaseType,
+    fingerprints: HashSet<String>,
+}
+
+impl QueryAllowlist {
+    /// Build an allowlist from one query per line; blank lines and `#` comments are skipped
+    pub fn parse(contents: &str, db_type: DatabaseType) -> Self {
+        let fingerprints = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| fingerprint_sql(line.trim_end_matches(';'), db_type))
+            .collect();
+        Self {
+            db_type,
+            fingerprints,
+        }
+    }
+
+    /// Read an allowlist file
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read
+    pub fn from_file(path: &Path, db_type: DatabaseType) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read query allowlist {}", path.display()))?;
+        Ok(Self::parse(&contents, db_type))
+    }
+
+    /// Load the file named by `db_query_allowlist_path`; `None` when unset
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read
+    pub fn from_config(
+        config: &ConfigManager,
+        db_type: DatabaseType,
+    ) -> anyhow::Result<Option<Self>> {
+        match config.get_value("db_query_allowlist_path") {
+            Some(kodegen_config_manager::ConfigValue::String(path)) if !path.is_empty() => {
+                let allowlist = Self::from_file(Path::new(&path), db_type)?;
+                log::info!(
+                    "Query allowlist enabled: {} fingerprints from {}",
+                    allowlist.len(),
+                    path
+                );
+                Ok(Some(allowlist))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Number of distinct allowed fingerprints
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Whether nothing is allowed
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    /// Whether `sql` has an allowed shape
+    pub fn allows(&self, sql: &str) -> bool {
+        let sql = sql.trim().trim_end_matches(';');
+        self.fingerprints.contains(&fingerprint_sql(sql, self.db_type))
+    }
+
+    /// Reject the first statement whose shape is not allowed
+    ///
+    /// Only the fingerprint is echoed back, never the literals.
+    pub fn check(&self, statements: &[String]) -> Result<(), McpError> {
+        let rejected = statements
+            .iter()
+            .enumerate()
+            .find(|(_, statement)| !self.allows(statement));
+        match rejected {
+            Some((index, statement)) => Err(McpError::InvalidArguments(format!(
+                "Statement {} is not in the query allowlist (db_query_allowlist_path): {}",
+                index + 1,
+                fingerprint_sql(statement, self.db_type)
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALLOWLIST: &str = "\
+# Vetted reporting queries
+SELECT id, total FROM orders WHERE customer_id = 1 AND status IN ('open');
+
+SELECT COUNT(*) FROM orders WHERE created_at > ?
+";
+
+    #[test]
+    fn test_allowed_shape_with_other_literals_passes() {
+        let allowlist = QueryAllowlist::parse(ALLOWLIST, DatabaseType::Postgres);
+        assert_eq!(allowlist.len(), 2);
+        let statements = vec![
+            "SELECT id, total FROM orders WHERE customer_id = 981 AND status IN ('paid', 'open')"
+                .to_string(),
+            "SELECT COUNT(*) FROM orders WHERE created_at > '2026-01-01';".to_string(),
+        ];
+        assert!(allowlist.check(&statements).is_ok());
+    }
+
+    #[test]
+    fn test_unlisted_shape_rejected() {
+        let allowlist = QueryAllowlist::parse(ALLOWLIST, DatabaseType::Postgres);
+        let statements = vec![
+            "SELECT COUNT(*) FROM orders WHERE created_at > '2026-01-01'".to_string(),
+            "SELECT id, total FROM orders WHERE customer_id = 981 OR 1 = 1".to_string(),
+        ];
+        let err = allowlist.check(&statements).expect_err("unlisted shape");
+        let message = err.to_string();
+        assert!(message.contains("Statement 2"), "{}", message);
+        assert!(!message.contains("981"), "literals must not be echoed: {}", message);
+
+        assert!(!allowlist.allows("DELETE FROM orders"));
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join(format!("allowlist-{}.sql", uuid::Uuid::new_v4()));
+        std::fs::write(&path, ALLOWLIST).expect("write allowlist");
+        let allowlist = QueryAllowlist::from_file(&path, DatabaseType::SQLite);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(allowlist.expect("read allowlist").len(), 2);
+
+        assert!(QueryAllowlist::from_file(&path, DatabaseType::SQLite).is_err());
+    }
+}
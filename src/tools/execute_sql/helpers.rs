@@ -2,7 +2,14 @@
 //!
 //! Utility functions for determining execution strategies.
 
-use crate::{DatabaseType, extract_first_keyword};
+use crate::{DatabaseType, cartesian_products, destructive_risk, extract_first_keyword};
+use crate::sql_limiter::{
+    extract_row_limit, is_introspection_statement, is_limited_without_order_by,
+};
+use crate::sql_parser::get_dialect;
+use kodegen_mcp_schema::McpError;
+use sqlparser::ast::Statement;
+use sqlparser::parser::Parser;
 
 /// Determine if statements contain write operations requiring transaction
 ///
@@ -14,8 +21,12 @@ use crate::{DatabaseType, extract_first_keyword};
 /// * `db_type` - Database type for keyword extraction
 ///
 /// # Returns
-/// `true` if any statement is a write operation requiring transaction
+/// `true` if any statement is a write operation requiring transaction, and the
+/// batch does not manage its own transaction (see [`has_transaction_control`])
 pub fn should_use_transaction(statements: &[String], db_type: DatabaseType) -> bool {
+    if has_transaction_control(statements, db_type) {
+        return false;
+    }
     statements.iter().any(|stmt| {
         if let Ok(keyword) = extract_first_keyword(stmt, db_type) {
             matches!(
@@ -28,3 +39,461 @@ pub fn should_use_transaction(statements: &[String], db_type: DatabaseType) -> b
         }
     })
 }
+
+/// Determine if a pure-read batch should share one read-only snapshot
+///
+/// Applies when `read_consistency` is requested for a batch of several
+/// statements that neither writes (see [`should_use_transaction`]) nor
+/// manages its own transaction.
+pub fn should_use_read_snapshot(
+    statements: &[String],
+    db_type: DatabaseType,
+    read_consistency: bool,
+) -> bool {
+    read_consistency
+        && statements.len() > 1
+        && !has_transaction_control(statements, db_type)
+        && !should_use_transaction(statements, db_type)
+}
+
+/// Statement opening a read-only transaction whose reads share one snapshot
+///
+/// PostgreSQL needs REPEATABLE READ, since READ COMMITTED takes a new snapshot
+/// per statement. InnoDB's default REPEATABLE READ keeps the snapshot taken
+/// by `WITH CONSISTENT SNAPSHOT`. SQLite holds its read snapshot from the
+/// first read of a deferred transaction until it ends. SQL Server only gets a
+/// plain transaction (snapshot isolation must be enabled on the database).
+pub fn begin_read_snapshot_sql(db_type: DatabaseType) -> &'static str {
+    match db_type {
+        DatabaseType::Postgres => "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY",
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            "START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY"
+        }
+        DatabaseType::SQLite => "BEGIN DEFERRED",
+        DatabaseType::SqlServer => "BEGIN TRANSACTION",
+    }
+}
+
+/// Effect of a statement on an explicit, user-driven transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionControl {
+    /// `BEGIN` / `START TRANSACTION`
+    Begin,
+    /// `COMMIT` / `END` / `ROLLBACK` (but not `ROLLBACK TO SAVEPOINT`)
+    End,
+}
+
+/// Classify a statement as explicit transaction control, if it is one
+///
+/// The statement is parsed, so keywords inside comments or string literals
+/// never count. Statements the parser rejects (e.g. PostgreSQL `ABORT`) fall
+/// back to their first keyword, which is itself read with comments stripped.
+///
+/// # Arguments
+/// * `sql` - SQL statement to analyze
+/// * `db_type` - Database type for parsing and comment stripping
+pub fn transaction_control(sql: &str, db_type: DatabaseType) -> Option<TransactionControl> {
+    let dialect = get_dialect(db_type);
+    if let Ok(statements) = Parser::parse_sql(&*dialect, sql)
+        && let [statement] = statements.as_slice()
+    {
+        return match statement {
+            Statement::StartTransaction { .. } => Some(TransactionControl::Begin),
+            // Covers END as well
+            Statement::Commit { .. } => Some(TransactionControl::End),
+            // ROLLBACK [WORK | TRANSACTION] TO SAVEPOINT keeps the transaction open
+            Statement::Rollback { savepoint: None, .. } => Some(TransactionControl::End),
+            _ => None,
+        };
+    }
+
+    let keyword = extract_first_keyword(sql, db_type).ok()?;
+    match keyword.trim_end_matches(';') {
+        "begin" => Some(TransactionControl::Begin),
+        "commit" | "end" | "abort" | "rollback" => Some(TransactionControl::End),
+        _ => None,
+    }
+}
+
+/// Determine if a batch drives its own transaction with BEGIN/COMMIT/ROLLBACK
+///
+/// Such batches must run on a single connection without the implicit
+/// transaction wrapper, otherwise the user's `BEGIN` nests inside ours.
+pub fn has_transaction_control(statements: &[String], db_type: DatabaseType) -> bool {
+    statements
+        .iter()
+        .any(|stmt| transaction_control(stmt, db_type).is_some())
+}
+
+/// Determine if a statement is a stored procedure call that may return several result sets
+///
+/// Scoped to MySQL/MariaDB, where procedures commonly return multiple result sets.
+pub fn is_procedure_call(sql: &str, db_type: DatabaseType) -> bool {
+    matches!(db_type, DatabaseType::MySQL | DatabaseType::MariaDB)
+        && extract_first_keyword(sql, db_type).is_ok_and(|keyword| keyword == "call")
+}
+
+/// Determine if a statement should be fetched as a stream in batches
+///
+/// Only row-returning queries (SELECT / WITH) qualify, and only when they
+/// don't already carry an explicit LIMIT that fits within a single batch.
+///
+/// # Arguments
+/// * `sql` - SQL statement to analyze
+/// * `db_type` - Database type for keyword extraction
+/// * `fetch_size` - Configured batch size (`db_fetch_size`)
+///
+/// # Returns
+/// `true` if the statement should use the streaming fetch path
+pub fn should_stream_results(sql: &str, db_type: DatabaseType, fetch_size: usize) -> bool {
+    if fetch_size == 0 {
+        return false;
+    }
+
+    let Ok(keyword) = extract_first_keyword(sql, db_type) else {
+        return false;
+    };
+    if !matches!(keyword.as_str(), "select" | "with") {
+        return false;
+    }
+
+    match extract_row_limit(sql) {
+        Some(limit) => limit > fetch_size,
+        None => true,
+    }
+}
+
+/// Config key holding the execution timeout for a statement
+///
+/// Reads (SELECT, WITH, SHOW, EXPLAIN, DESCRIBE, VALUES) use
+/// `db_read_timeout_secs` and everything else `db_write_timeout_secs`, each
+/// falling back to `db_query_timeout_secs` when `is_set` reports it unset.
+/// Statements whose keyword can't be extracted count as writes.
+pub fn statement_timeout_key(
+    sql: &str,
+    db_type: DatabaseType,
+    is_set: impl Fn(&str) -> bool,
+) -> &'static str {
+    let is_read = extract_first_keyword(sql, db_type).is_ok_and(|keyword| {
+        matches!(
+            keyword.as_str(),
+            "select" | "with" | "show" | "explain" | "describe" | "desc" | "values"
+        )
+    });
+    let key = if is_read {
+        "db_read_timeout_secs"
+    } else {
+        "db_write_timeout_secs"
+    };
+    if is_set(key) { key } else { "db_query_timeout_secs" }
+}
+
+/// Reject batches containing more statements than `max_statements`
+///
+/// Bounds transaction size and parse cost for a single `execute_sql` call.
+///
+/// # Errors
+/// Returns `McpError::InvalidArguments` naming the statement count and limit
+pub fn check_statement_count(statements: &[String], max_statements: usize) -> Result<(), McpError> {
+    if statements.len() > max_statements {
+        return Err(McpError::InvalidArguments(format!(
+            "SQL contains {} statements, exceeding the limit of {} (db_max_statements)",
+            statements.len(),
+            max_statements
+        )));
+    }
+    Ok(())
+}
+
+/// Reject high-risk statements unless the caller confirmed them
+///
+/// Used when `db_confirm_destructive` is on: DELETE/UPDATE without WHERE (or
+/// with an always-true one such as `WHERE 1=1`), DROP, and TRUNCATE need
+/// `confirm_destructive: true` on the call.
+///
+/// # Errors
+/// Returns `McpError::InvalidArguments` listing each flagged statement and its risk
+pub fn check_destructive_confirmed(
+    statements: &[String],
+    db_type: DatabaseType,
+    confirmed: bool,
+) -> Result<(), McpError> {
+    if confirmed {
+        return Ok(());
+    }
+    let risks: Vec<String> = statements
+        .iter()
+        .enumerate()
+        .filter_map(|(index, stmt)| {
+            destructive_risk(stmt, db_type).map(|risk| format!("statement {}: {}", index + 1, risk))
+        })
+        .collect();
+    if risks.is_empty() {
+        return Ok(());
+    }
+    Err(McpError::InvalidArguments(format!(
+        "Destructive statement requires confirm_destructive: true (db_confirm_destructive \
+         is enabled). Risks: {}",
+        risks.join("; ")
+    )))
+}
+
+/// Warnings for row-limited statements that lack an ORDER BY
+///
+/// Without ORDER BY, which rows fall inside the LIMIT is up to the database,
+/// so paging through results can skip or repeat rows.
+pub fn missing_order_by_warnings(statements: &[String], db_type: DatabaseType) -> Vec<String> {
+    statements
+        .iter()
+        .enumerate()
+        .filter(|(_, stmt)| is_limited_without_order_by(stmt, db_type))
+        .map(|(index, _)| {
+            format!(
+                "Statement {} is row-limited but has no ORDER BY; returned rows may differ \
+                 between calls. Add ORDER BY for deterministic pagination.",
+                index + 1
+            )
+        })
+        .collect()
+}
+
+/// Row cap for a SHOW/PRAGMA result under `db_limit_show_statements`
+///
+/// Returns `Some(max_rows)` when `sql` is introspection that returned more than
+/// `max_rows` rows, so the rest should be dropped before conversion.
+pub fn introspection_row_cap(
+    sql: &str,
+    db_type: DatabaseType,
+    row_count: usize,
+    max_rows: usize,
+) -> Option<usize> {
+    (row_count > max_rows && is_introspection_statement(sql, db_type)).then_some(max_rows)
+}
+
+/// Warnings for SELECTs whose FROM tables are never correlated
+///
+/// See [`cartesian_products`]; explicit `CROSS JOIN` is not reported.
+pub fn cartesian_product_warnings(statements: &[String], db_type: DatabaseType) -> Vec<String> {
+    statements
+        .iter()
+        .enumerate()
+        .flat_map(|(index, stmt)| {
+            cartesian_products(stmt, db_type).into_iter().map(move |risk| {
+                format!(
+                    "Statement {}: {}. Add a join condition, or use CROSS JOIN if this is intended.",
+                    index + 1,
+                    risk
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streams_unbounded_select() {
+        assert!(should_stream_results("SELECT * FROM big_table", DatabaseType::Postgres, 500));
+        assert!(should_stream_results(
+            "WITH t AS (SELECT 1) SELECT * FROM t",
+            DatabaseType::MySQL,
+            500
+        ));
+    }
+
+    #[test]
+    fn test_skips_small_explicit_limit() {
+        assert!(!should_stream_results("SELECT * FROM t LIMIT 10", DatabaseType::Postgres, 500));
+        assert!(should_stream_results("SELECT * FROM t LIMIT 10000", DatabaseType::Postgres, 500));
+    }
+
+    #[test]
+    fn test_skips_writes_and_zero_fetch_size() {
+        assert!(!should_stream_results("DELETE FROM t", DatabaseType::Postgres, 500));
+        assert!(!should_stream_results("SELECT * FROM t", DatabaseType::Postgres, 0));
+    }
+
+    #[test]
+    fn test_select_uses_read_timeout_and_update_write_timeout() {
+        let db = DatabaseType::Postgres;
+        let both = |key: &str| matches!(key, "db_read_timeout_secs" | "db_write_timeout_secs");
+        assert_eq!(statement_timeout_key("SELECT * FROM t", db, both), "db_read_timeout_secs");
+        assert_eq!(
+            statement_timeout_key("WITH x AS (SELECT 1) SELECT * FROM x", db, both),
+            "db_read_timeout_secs"
+        );
+        assert_eq!(
+            statement_timeout_key("UPDATE t SET a = 1 WHERE id = 2", db, both),
+            "db_write_timeout_secs"
+        );
+        assert_eq!(statement_timeout_key("DELETE FROM t", db, both), "db_write_timeout_secs");
+    }
+
+    #[test]
+    fn test_statement_timeout_falls_back_to_query_timeout() {
+        let db = DatabaseType::MySQL;
+        let read_only = |key: &str| key == "db_read_timeout_secs";
+        assert_eq!(statement_timeout_key("SELECT 1", db, read_only), "db_read_timeout_secs");
+        assert_eq!(statement_timeout_key("UPDATE t SET a = 1", db, read_only), "db_query_timeout_secs");
+        assert_eq!(statement_timeout_key("SELECT 1", db, |_| false), "db_query_timeout_secs");
+    }
+
+    #[test]
+    fn test_transaction_control_detection() {
+        let db = DatabaseType::Postgres;
+        assert_eq!(transaction_control("BEGIN", db), Some(TransactionControl::Begin));
+        assert_eq!(
+            transaction_control("start transaction read only", db),
+            Some(TransactionControl::Begin)
+        );
+        assert_eq!(transaction_control("COMMIT;", db), Some(TransactionControl::End));
+        assert_eq!(transaction_control("ROLLBACK", db), Some(TransactionControl::End));
+        assert_eq!(transaction_control("ROLLBACK TO SAVEPOINT sp1", db), None);
+        assert_eq!(transaction_control("START SLAVE", DatabaseType::MySQL), None);
+        assert_eq!(transaction_control("DO $$ BEGIN PERFORM 1; END $$", db), None);
+        assert_eq!(transaction_control("END", db), Some(TransactionControl::End));
+        assert_eq!(transaction_control("ABORT", db), Some(TransactionControl::End));
+    }
+
+    #[test]
+    fn test_transaction_control_ignores_comments() {
+        let db = DatabaseType::Postgres;
+        assert_eq!(transaction_control("SELECT 1 /* COMMIT */", db), None);
+        assert_eq!(transaction_control("SELECT 'ROLLBACK' -- BEGIN", db), None);
+        assert_eq!(
+            transaction_control("ROLLBACK /* to be safe */", db),
+            Some(TransactionControl::End)
+        );
+        assert_eq!(
+            transaction_control("START /* now */ TRANSACTION", db),
+            Some(TransactionControl::Begin)
+        );
+    }
+
+    #[test]
+    fn test_batch_with_transaction_control() {
+        let batch: Vec<String> = ["BEGIN", "INSERT INTO t VALUES (1)", "COMMIT"]
+            .map(String::from)
+            .to_vec();
+        assert!(has_transaction_control(&batch, DatabaseType::SQLite));
+        let batch: Vec<String> = ["INSERT INTO t VALUES (1)", "SELECT * FROM t"]
+            .map(String::from)
+            .to_vec();
+        assert!(!has_transaction_control(&batch, DatabaseType::SQLite));
+    }
+
+    #[test]
+    fn test_read_snapshot_for_multi_select_batch() {
+        let reads: Vec<String> = ["SELECT * FROM orders", "SELECT * FROM order_items"]
+            .map(String::from)
+            .to_vec();
+        assert!(should_use_read_snapshot(&reads, DatabaseType::Postgres, true));
+        assert!(!should_use_read_snapshot(&reads, DatabaseType::Postgres, false));
+        assert!(!should_use_read_snapshot(&reads[..1], DatabaseType::Postgres, true));
+
+        let mixed: Vec<String> = ["SELECT * FROM orders", "DELETE FROM orders WHERE id = 1"]
+            .map(String::from)
+            .to_vec();
+        assert!(!should_use_read_snapshot(&mixed, DatabaseType::Postgres, true));
+    }
+
+    #[test]
+    fn test_begin_read_snapshot_sql() {
+        assert_eq!(
+            begin_read_snapshot_sql(DatabaseType::Postgres),
+            "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY"
+        );
+        assert!(begin_read_snapshot_sql(DatabaseType::MySQL).ends_with("READ ONLY"));
+        assert_eq!(begin_read_snapshot_sql(DatabaseType::SQLite), "BEGIN DEFERRED");
+    }
+
+    #[test]
+    fn test_procedure_call_detection() {
+        assert!(is_procedure_call("CALL report_totals(2024)", DatabaseType::MySQL));
+        assert!(is_procedure_call("call report_totals()", DatabaseType::MariaDB));
+        assert!(!is_procedure_call("CALL refresh_stats()", DatabaseType::Postgres));
+        assert!(!is_procedure_call("SELECT 1", DatabaseType::MySQL));
+    }
+
+    #[test]
+    fn test_statement_count_over_limit_rejected() {
+        let statements = vec!["SELECT 1".to_string(); 4];
+        let err = check_statement_count(&statements, 3).expect_err("over the limit");
+        match err {
+            McpError::InvalidArguments(msg) => assert!(msg.contains("4 statements")),
+            other => panic!("expected InvalidArguments, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_statement_count_at_limit_passes() {
+        let statements = vec!["SELECT 1".to_string(); 3];
+        assert!(check_statement_count(&statements, 3).is_ok());
+        assert!(check_statement_count(&statements[..1], 1).is_ok());
+    }
+
+    #[test]
+    fn test_unconfirmed_delete_without_where_blocked() {
+        let statements = vec![
+            "SELECT 1".to_string(),
+            "DELETE FROM users".to_string(),
+        ];
+        let err = check_destructive_confirmed(&statements, DatabaseType::Postgres, false)
+            .expect_err("unqualified DELETE must be confirmed");
+        assert!(err.to_string().contains("statement 2: DELETE without WHERE on users"));
+    }
+
+    #[test]
+    fn test_confirmed_or_safe_statements_pass() {
+        let delete_all = vec!["DELETE FROM users".to_string()];
+        assert!(check_destructive_confirmed(&delete_all, DatabaseType::Postgres, true).is_ok());
+
+        let scoped = vec!["DELETE FROM users WHERE id = 1".to_string()];
+        assert!(check_destructive_confirmed(&scoped, DatabaseType::Postgres, false).is_ok());
+    }
+
+    #[test]
+    fn test_missing_order_by_warning() {
+        let statements = vec!["SELECT * FROM users LIMIT 100".to_string()];
+        let warnings = missing_order_by_warnings(&statements, DatabaseType::Postgres);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Statement 1"));
+        assert!(warnings[0].contains("ORDER BY"));
+    }
+
+    #[test]
+    fn test_ordered_limit_has_no_warning() {
+        let statements = vec!["SELECT * FROM users ORDER BY id LIMIT 100".to_string()];
+        assert!(missing_order_by_warnings(&statements, DatabaseType::Postgres).is_empty());
+    }
+
+    #[test]
+    fn test_cartesian_product_warning() {
+        let statements = vec![
+            "SELECT * FROM users WHERE id = 1".to_string(),
+            "SELECT * FROM users u, orders o".to_string(),
+        ];
+        let warnings = cartesian_product_warnings(&statements, DatabaseType::Postgres);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with(
+            "Statement 2: possible cartesian product between users and orders"
+        ));
+
+        let explicit = vec!["SELECT * FROM users CROSS JOIN orders".to_string()];
+        assert!(cartesian_product_warnings(&explicit, DatabaseType::Postgres).is_empty());
+    }
+
+    #[test]
+    fn test_show_variables_capped() {
+        let sql = "SHOW VARIABLES";
+        assert_eq!(introspection_row_cap(sql, DatabaseType::MySQL, 650, 100), Some(100));
+        assert_eq!(introspection_row_cap(sql, DatabaseType::MySQL, 80, 100), None);
+        assert_eq!(
+            introspection_row_cap("PRAGMA compile_options", DatabaseType::SQLite, 200, 100),
+            Some(100)
+        );
+        assert_eq!(introspection_row_cap("SELECT * FROM t", DatabaseType::MySQL, 650, 100), None);
+    }
+}
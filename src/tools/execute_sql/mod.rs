@@ -3,15 +3,45 @@
 //! Integrates read-only mode enforcement, row limiting, multi-statement support,
 //! and transaction wrapping for consistent database operations.
 
+mod allowlist;
 mod executor;
 mod helpers;
+mod lenient_dates;
+mod notices;
+mod observer;
+mod params;
+mod plan_guard;
+mod pragma;
+mod role;
 mod row_converter;
+mod session;
+mod truncation;
+mod type_mapper;
 
-pub use executor::ExecuteSQLTool;
-use helpers::should_use_transaction;
+pub use allowlist::QueryAllowlist;
+pub use executor::{DiagnosticsCapture, ExecuteSQLTool};
+pub use observer::QueryObserver;
+pub use plan_guard::PlanGuard;
+pub use type_mapper::{TypeDecode, TypeMapper};
+pub(crate) use params::bind_params;
+pub(crate) use row_converter::row_to_typed;
+use helpers::{
+    cartesian_product_warnings, check_destructive_confirmed, check_statement_count,
+    has_transaction_control, missing_order_by_warnings, should_use_read_snapshot,
+    should_use_transaction,
+};
+use pragma::normalize_pragma_output;
+use role::{execute_as_role, is_role_change};
+use row_converter::{
+    ColumnCase, ColumnProjection, ColumnRedaction, disambiguate_output_columns,
+    normalize_column_case, project_output_columns, redact_output_columns,
+};
+use truncation::{limit_bytes, limit_columns, limit_rows, skip_leading_rows};
 
 use crate::{
-    apply_row_limit, split_sql_statements, validate_readonly_sql,
+    apply_row_limit, apply_row_offset, apply_show_limit, apply_table_prefix, normalize_sql_input,
+    split_sql_statements, error::DatabaseError, types::{DatabaseType, ExecuteOptions},
+    readonly::{DEFAULT_MAX_VALIDATION_DEPTH, validate_readonly_sql_with_max_depth},
 };
 use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
 use kodegen_mcp_schema::ToolArgs;
@@ -31,16 +61,63 @@ impl Tool for ExecuteSQLTool {
          \n\n\
          MULTI-STATEMENT BEHAVIOR:\n\
          - Write operations (INSERT/UPDATE/DELETE/CREATE/ALTER/DROP) use transactions\n\
-         - Read operations (SELECT/EXPLAIN/SHOW) execute independently without transaction\n\
+         - Read operations (SELECT/EXPLAIN/SHOW) execute independently without transaction, \
+         unless read_consistency is set: then a pure-read batch shares one read-only snapshot\n\
+         - Batches with explicit BEGIN/COMMIT/ROLLBACK run on one connection without an implicit \
+         transaction; an unterminated transaction is rolled back\n\
          - On transactional error: returns error details without data (all changes rolled back)\n\
          - On non-transactional error: returns committed data plus errors array\n\
          \n\
          Returns query results as JSON with:\n\
          - rows: array of result rows\n\
+         - column_meta: column names with database types (and nullability when known)\n\
          - row_count: number of rows returned\n\
+         - has_result_set: false for statements that return no columns (DDL, SET), so \
+           \"OK\" can be told apart from an empty result\n\
          - errors: array of errors (if any failures in non-transactional mode)\n\
+         - warnings: row-limited queries without ORDER BY, possible cartesian products \
+           (comma-joined tables never correlated), plus MySQL/MariaDB SHOW WARNINGS \
+           output when surface_warnings is set\n\
+         - notices: PostgreSQL NOTICE/RAISE messages when capture_notices is set (best \
+           effort: read from the driver's log events, so a notice may be missed)\n\
+         \n\
+         MySQL/MariaDB CALL statements return every result set in result_sets; columns/rows \
+         hold the first one.\n\
+         \n\
+         Set partial_on_timeout to return the rows fetched so far (with timed_out: true) when a \
+         streamed read (db_fetch_size) hits the query timeout; writes always fail on timeout.\n\
+         \n\
+         Set force_custom_plan (PostgreSQL) to plan the statements with their actual \
+         parameter values (plan_cache_mode = force_custom_plan) when a cached generic plan \
+         is bad for skewed data; the setting is reset afterwards.\n\
+         \n\
+         When db_confirm_destructive is enabled, DELETE/UPDATE without WHERE (or with an \
+         always-true WHERE like 1=1), DROP and TRUNCATE are rejected unless \
+         confirm_destructive is true.\n\
+         \n\
+         Set skip_rows to skip the first N rows of a single statement's result; with \
+         max_rows this pages through a result (add ORDER BY for stable pages).\n\
+         \n\
+         Set include_columns or exclude_columns (names, case-insensitive) to drop result \
+         columns after fetching, e.g. a large blob from a SELECT *; the SQL is not changed.\n\
+         \n\
+         When rows or columns are cut off by max_rows, db_max_result_bytes or db_max_columns, \
+         truncation reports the reason (row_limit, byte_limit, column_limit), the limit and \
+         how many rows (or columns) were returned.\n\
+         \n\
+         Pass params to bind values to a single statement's placeholders ($1, $2, ... for \
+         PostgreSQL, ? for MySQL/SQLite). An array value binds a whole list: \
+         `id = ANY($1)` on PostgreSQL, `id IN (?)` on MySQL/SQLite.\n\
+         \n\
+         When db_max_estimated_rows or db_max_plan_cost is set (PostgreSQL/MySQL), each SELECT \
+         is EXPLAINed first and refused if the planner's estimate exceeds the limit.\n\
          \n\
-         Supports read-only mode enforcement and automatic row limiting."
+         Set normalize_pragma to rename SQLite PRAGMA table_info/index_list/foreign_key_list \
+         columns to friendly names (column_name, data_type, referenced_table, ...).\n\
+         \n\
+         Supports read-only mode enforcement and automatic row limiting. \
+         Optional `options` ({readonly, timeout_secs, max_rows}) override server \
+         settings for this call, but can only tighten them."
     }
 
     fn read_only() -> bool {
@@ -64,8 +141,13 @@ impl Tool for ExecuteSQLTool {
     {
         let start_time = std::time::Instant::now();
 
-        // 1. Get configuration
-        let readonly = self
+        // 0. Reject immediately if too many queries are already in flight
+        let _permit = self.query_limiter.try_acquire()?;
+
+        // 1. Get configuration, applying per-call overrides (which may only tighten it)
+        let options = args.options.clone().map(ExecuteOptions::from).unwrap_or_default();
+
+        let server_readonly = self
             .config
             .get_value("readonly")
             .and_then(|v| match v {
@@ -73,56 +155,348 @@ impl Tool for ExecuteSQLTool {
                 _ => None,
             })
             .unwrap_or(false);
+        let readonly = options.effective_readonly(server_readonly);
 
-        let max_rows = self.config.get_value("max_rows").and_then(|v| match v {
+        let server_max_rows = self.config.get_value("max_rows").and_then(|v| match v {
             kodegen_config_manager::ConfigValue::Number(n) => Some(n as usize),
             _ => None,
         });
+        let max_rows = options.effective_max_rows(server_max_rows);
+
+        let max_statements = self
+            .config
+            .get_value("db_max_statements")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(n as usize),
+                _ => None,
+            })
+            .unwrap_or(50);
 
         // 2. Get database type
         let db_type = self.get_database_type()?;
 
+        // Multi-tenant setups: prefix base-table names with the configured tenant prefix
+        let table_prefix = self
+            .config
+            .get_value("db_table_prefix")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::String(s) if !s.is_empty() => Some(s),
+                _ => None,
+            });
+        // Input pasted from Windows-saved files may start with a byte order mark
+        let input = normalize_sql_input(&args.sql);
+
+        // High-security mode: only pre-approved query shapes may run. Checked on
+        // the SQL as written, so the vetted file never needs tenant prefixes.
+        if let Some(allowlist) = &self.allowlist {
+            let written = split_sql_statements(input, db_type)
+                .map_err(|e| anyhow::anyhow!("SQL parse error: {}", e))?;
+            allowlist.check(&written)?;
+        }
+
+        let sql = match table_prefix {
+            Some(prefix) => apply_table_prefix(input, &prefix, db_type)
+                .map_err(|e| anyhow::anyhow!("Table prefix rewrite failed: {}", e))?,
+            None => input.to_string(),
+        };
+
         // 3. Validate read-only mode if enabled
         if readonly {
-            validate_readonly_sql(&args.sql, db_type)
+            let max_depth = self
+                .config
+                .get_value("db_readonly_max_depth")
+                .and_then(|v| match v {
+                    kodegen_config_manager::ConfigValue::Number(n) if n > 0 => Some(n as usize),
+                    _ => None,
+                })
+                .unwrap_or(DEFAULT_MAX_VALIDATION_DEPTH);
+            validate_readonly_sql_with_max_depth(&sql, db_type, max_depth)
                 .map_err(|e| anyhow::anyhow!("Read-only violation: {}", e))?;
         }
 
-        // 4. Apply row limiting if configured
-        let sql = if let Some(max_rows) = max_rows {
-            apply_row_limit(&args.sql, max_rows, db_type)
-                .map_err(|e| anyhow::anyhow!("Row limit failed: {}", e))?
+        // 4. Split into statements
+        let statements = split_sql_statements(&sql, db_type)
+            .map_err(|e| anyhow::anyhow!("SQL parse error: {}", e))?;
+        check_statement_count(&statements, max_statements)?;
+
+        // skip_rows: a single SELECT gets an OFFSET; anything else fetches the
+        // skipped rows too and drops them after conversion
+        let skip_rows = args.skip_rows;
+        if skip_rows > 0 && statements.len() != 1 {
+            return Err(McpError::InvalidArguments(format!(
+                "skip_rows can only be applied to a single statement, got {}",
+                statements.len()
+            )));
+        }
+        let (statements, discard_rows) = if skip_rows > 0 {
+            match apply_row_offset(&statements[0], skip_rows, db_type)
+                .map_err(|e| anyhow::anyhow!("Row offset failed: {}", e))?
+            {
+                Some(offset_sql) => (vec![offset_sql], 0),
+                None => (statements, skip_rows),
+            }
         } else {
-            args.sql.clone()
+            (statements, 0)
         };
 
-        // 5. Split into statements
-        let statements = split_sql_statements(&sql, db_type)
-            .map_err(|e| anyhow::anyhow!("SQL parse error: {}", e))?;
+        // 5. Apply row limiting if configured. Each statement fetches one extra
+        // row so that a result cut off by the limit can be reported as truncated.
+        // With db_limit_show_statements, SHOW statements that accept a LIMIT get
+        // one too; other SHOW/PRAGMA results are capped as they are converted.
+        let limit_show = self.limit_show_statements();
+        let fetch_rows = max_rows
+            .map(|max_rows| max_rows.saturating_add(1).saturating_add(discard_rows));
+        let statements = match fetch_rows {
+            Some(fetch_rows) => {
+                statements
+                    .iter()
+                    .map(|statement| {
+                        let limited = apply_row_limit(statement, fetch_rows, db_type)?;
+                        if limit_show {
+                            apply_show_limit(&limited, fetch_rows, db_type)
+                        } else {
+                            Ok(limited)
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| anyhow::anyhow!("Row limit failed: {}", e))?
+            }
+            None => statements,
+        };
 
-        // 6. Execute single or multi-statement (returns typed ExecuteSQLOutput directly)
-        let mut output = if statements.len() == 1 {
-            self.execute_single(&statements[0]).await?
+        // Statements must not escape the configured db_execute_as_role
+        if execute_as_role(&self.config, db_type)?.is_some()
+            && let Some(statement) = statements.iter().find(|s| is_role_change(s, db_type))
+        {
+            return Err(McpError::InvalidArguments(format!(
+                "Changing the session role is not allowed while db_execute_as_role is set: {}",
+                statement.chars().take(50).collect::<String>()
+            )));
+        }
+
+        // Safety net outside read-only mode: unqualified DELETE/UPDATE, DROP, TRUNCATE
+        let confirm_destructive = self
+            .config
+            .get_value("db_confirm_destructive")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Boolean(b) => Some(b),
+                _ => None,
+            })
+            .unwrap_or(false);
+        if confirm_destructive {
+            check_destructive_confirmed(&statements, db_type, args.confirm_destructive)?;
+        }
+
+        // Row-limited SELECTs without ORDER BY page nondeterministically
+        let pagination_warnings = if max_rows.is_some() || skip_rows > 0 {
+            missing_order_by_warnings(&statements, db_type)
         } else {
-            // Route based on statement types
-            if should_use_transaction(&statements, db_type) {
-                self.execute_multi_transactional(&statements).await?
+            Vec::new()
+        };
+        if let Some(warning) = pagination_warnings.first() {
+            let require_order_by = self
+                .config
+                .get_value("db_require_order_by_with_limit")
+                .and_then(|v| match v {
+                    kodegen_config_manager::ConfigValue::Boolean(b) => Some(b),
+                    _ => None,
+                })
+                .unwrap_or(false);
+            if require_order_by {
+                return Err(McpError::InvalidArguments(format!(
+                    "{} (db_require_order_by_with_limit is enabled)",
+                    warning
+                )));
+            }
+        }
+
+        // Uncorrelated FROM lists usually mean a forgotten join condition
+        let cartesian_warnings = cartesian_product_warnings(&statements, db_type);
+
+        // Plan guard: EXPLAIN each SELECT first and refuse (or flag) expensive ones.
+        // Parameterized statements cannot be planned without their values.
+        let mut plan_warnings = Vec::new();
+        if args.params.is_empty()
+            && let Some(guard) = PlanGuard::from_config(&self.config)
+        {
+            let check = guard.check(&self.config, &self.pool, db_type, &statements).await;
+            if guard.warn_only {
+                plan_warnings = check.violations;
+            } else if !check.violations.is_empty() {
+                return Err(McpError::InvalidArguments(format!(
+                    "Query refused by the plan guard: {}",
+                    check.violations.join("; ")
+                )));
+            }
+            plan_warnings.extend(check.unchecked);
+        }
+
+        // 6. Execute single or multi-statement (returns typed ExecuteSQLOutput directly)
+        let capture = DiagnosticsCapture {
+            warnings: args.surface_warnings,
+            notices: args.capture_notices,
+        };
+        if !args.params.is_empty() && statements.len() != 1 {
+            return Err(McpError::InvalidArguments(format!(
+                "params can only be bound to a single statement, got {}",
+                statements.len()
+            )));
+        }
+        let execution = async {
+            if !args.params.is_empty() {
+                self.execute_parameterized(&statements[0], &args.params, args.force_custom_plan)
+                    .await
+            } else if statements.len() == 1 {
+                self.execute_single(
+                    &statements[0],
+                    capture,
+                    args.partial_on_timeout,
+                    args.force_custom_plan,
+                    fetch_rows,
+                )
+                .await
+            } else if has_transaction_control(&statements, db_type) {
+                // Explicit BEGIN/COMMIT: one connection, no implicit wrapper
+                self.execute_multi_session(
+                    &statements,
+                    capture,
+                    args.force_custom_plan,
+                    max_rows,
+                )
+                .await
+            } else if should_use_transaction(&statements, db_type) {
+                // Route based on statement types
+                self.execute_multi_transactional(
+                    &statements,
+                    capture,
+                    args.force_custom_plan,
+                    max_rows,
+                )
+                .await
+            } else if should_use_read_snapshot(&statements, db_type, args.read_consistency) {
+                // Pure reads that must agree with each other: one read-only snapshot
+                self.execute_multi_read_snapshot(
+                    &statements,
+                    capture,
+                    args.force_custom_plan,
+                    max_rows,
+                )
+                .await
             } else {
-                self.execute_multi_non_transactional(&statements).await?
+                self.execute_multi_non_transactional(
+                    &statements,
+                    capture,
+                    args.force_custom_plan,
+                    max_rows,
+                )
+                .await
             }
         };
 
-        // 7. Set execution time (executor methods set it to 0)
+        // Per-call timeout bounds the whole call; server per-statement timeouts still apply
+        let mut output = match options.timeout() {
+            Some(limit) => tokio::time::timeout(limit, execution).await.map_err(|_| {
+                DatabaseError::QueryError(format!(
+                    "SQL execution exceeded the per-call timeout of {:?}",
+                    limit
+                ))
+            })??,
+            None => execution.await?,
+        };
+
+        if discard_rows > 0 {
+            skip_leading_rows(&mut output, discard_rows);
+            output.warnings.push(format!(
+                "skip_rows could not be applied as an OFFSET; the first {} rows were fetched \
+                 and discarded",
+                discard_rows
+            ));
+        }
+
+        output.warnings.extend(pagination_warnings);
+        output.warnings.extend(cartesian_warnings);
+        output.warnings.extend(plan_warnings);
+        if args.force_custom_plan && db_type != DatabaseType::Postgres {
+            output.warnings.push(
+                "force_custom_plan only applies to PostgreSQL; it was ignored".to_string(),
+            );
+        }
+
+        // Mask sensitive columns before any renaming, matching the names the
+        // database reported
+        let redaction = ColumnRedaction::from_config(&self.config).map_err(|e| {
+            McpError::Other(anyhow::anyhow!("Invalid db_redact_columns pattern: {}", e))
+        })?;
+        redact_output_columns(&mut output, &redaction);
+
+        // include_columns / exclude_columns: trim the result, not the SQL
+        let projection = ColumnProjection::new(&args.include_columns, &args.exclude_columns);
+        if !projection.is_empty() && output.has_result_set {
+            for name in projection.unmatched(&output.columns) {
+                output
+                    .warnings
+                    .push(format!("Column '{}' is not in the result; ignored", name));
+            }
+            project_output_columns(&mut output, &projection);
+        }
+
+        // SQLite PRAGMA results: rename internal column names when requested
+        if args.normalize_pragma && statements.len() == 1 {
+            normalize_pragma_output(&statements[0], db_type, &mut output);
+        }
+
+        // 7. Normalize column name case, then make duplicate column names
+        // (e.g. JOINs on `id`) unique unless disabled
+        normalize_column_case(&mut output, ColumnCase::from_config(&self.config));
+
+        let disambiguate_columns = self
+            .config
+            .get_value("db_disambiguate_columns")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Boolean(b) => Some(b),
+                _ => None,
+            })
+            .unwrap_or(true);
+        if disambiguate_columns {
+            disambiguate_output_columns(&mut output);
+        }
+
+        // 8. Trim oversized results, recording the reason in output.truncation
+        let max_columns = self.config.get_value("db_max_columns").and_then(|v| match v {
+            kodegen_config_manager::ConfigValue::Number(n) if n > 0 => Some(n as usize),
+            _ => None,
+        });
+        if let Some(max_columns) = max_columns {
+            limit_columns(&mut output, max_columns);
+        }
+        if let Some(max_rows) = max_rows
+            && statements.len() == 1
+        {
+            limit_rows(&mut output, max_rows);
+        }
+        let max_bytes = self.config.get_value("db_max_result_bytes").and_then(|v| match v {
+            kodegen_config_manager::ConfigValue::Number(n) if n > 0 => Some(n as usize),
+            _ => None,
+        });
+        if let Some(max_bytes) = max_bytes {
+            limit_bytes(&mut output, max_bytes);
+        }
+
+        // 9. Set execution time (executor methods set it to 0)
         let elapsed_ms = start_time.elapsed().as_millis() as u64;
         output.execution_time_ms = elapsed_ms;
 
         // Human-readable display
-        let display = format!(
-            "\x1b[36m SQL Executed\x1b[0m\n\
-             Rows: {} · Time: {}ms",
-            output.row_count,
-            elapsed_ms
-        );
+        let display = if output.has_result_set {
+            format!(
+                "\x1b[36m SQL Executed\x1b[0m\n\
+                 Rows: {} · Time: {}ms",
+                output.row_count,
+                elapsed_ms
+            )
+        } else {
+            format!("\x1b[36m SQL Executed\x1b[0m\nOK · Time: {}ms", elapsed_ms)
+        };
         
         Ok(ToolResponse::new(display, output))
     }
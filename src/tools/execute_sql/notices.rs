@@ -0,0 +1,182 @@
+//! PostgreSQL NOTICE capture for ExecuteSQL
+//!
+//! sqlx has no per-connection notice callback: the Postgres driver emits each
+//! `NoticeResponse` as a tracing event on the `sqlx::postgres::notice` target
+//! while the statement's future is being polled. Running that future under a
+//! scoped subscriber collects exactly the notices raised by the statement;
+//! every other event and span is forwarded to the previously active dispatcher.
+//!
+//! This is best effort and depends on sqlx's logging: levels compiled out
+//! with `tracing`'s `max_level_*` features never reach the collector, and the
+//! severity is reconstructed from the event level. The tool description and
+//! README say so.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::instrument::WithSubscriber;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Dispatch, Event, Level, Metadata, Subscriber};
+
+/// Tracing target sqlx uses for PostgreSQL server notices
+const NOTICE_TARGET: &str = "sqlx::postgres::notice";
+
+/// Run `fut`, collecting PostgreSQL notices emitted while it is polled
+///
+/// Notices are formatted as `SEVERITY: message`, e.g. `NOTICE: table created`.
+pub(super) async fn capture_notices<F: Future>(fut: F) -> (F::Output, Vec<String>) {
+    let collector = NoticeCollector {
+        notices: Arc::default(),
+        inner: tracing::dispatcher::get_default(Dispatch::clone),
+    };
+    let notices = collector.notices.clone();
+
+    let output = fut.with_subscriber(collector).await;
+
+    let notices = std::mem::take(&mut *notices.lock().unwrap_or_else(|e| e.into_inner()));
+    (output, notices)
+}
+
+/// Subscriber that records notice events and delegates everything else
+struct NoticeCollector {
+    notices: Arc<Mutex<Vec<String>>>,
+    inner: Dispatch,
+}
+
+fn is_notice(metadata: &Metadata<'_>) -> bool {
+    metadata.target() == NOTICE_TARGET
+}
+
+/// PostgreSQL severity name for the level sqlx mapped it to
+fn severity(level: &Level) -> &'static str {
+    match *level {
+        Level::ERROR => "ERROR",
+        Level::WARN => "WARNING",
+        Level::INFO => "NOTICE",
+        Level::DEBUG => "DEBUG",
+        Level::TRACE => "INFO",
+    }
+}
+
+/// Extracts the `message` field of a notice event
+#[derive(Default)]
+struct MessageVisitor {
+    message: Option<String>,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" && self.message.is_none() {
+            self.message = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl Subscriber for NoticeCollector {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if is_notice(metadata) {
+            Interest::always()
+        } else {
+            // Decided per event by the delegated `enabled`
+            Interest::sometimes()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        is_notice(metadata) || self.inner.enabled(metadata)
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.inner.new_span(span)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        self.inner.record(span, values)
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        self.inner.record_follows_from(span, follows)
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        if is_notice(metadata) {
+            let mut visitor = MessageVisitor::default();
+            event.record(&mut visitor);
+            if let Some(message) = visitor.message {
+                self.notices
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(format!("{}: {}", severity(metadata.level()), message));
+            }
+        }
+        if self.inner.enabled(metadata) {
+            self.inner.event(event);
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        self.inner.enter(span)
+    }
+
+    fn exit(&self, span: &Id) {
+        self.inner.exit(span)
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.inner.clone_span(id)
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        self.inner.try_close(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_captures_notice_events() {
+        let ((), notices) = capture_notices(async {
+            tracing::info!(target: "sqlx::postgres::notice", message = "table \"t\" does not exist, skipping");
+            tokio::task::yield_now().await;
+            tracing::warn!(target: "sqlx::postgres::notice", message = "nonstandard use of \\\\ in a string literal");
+        })
+        .await;
+
+        assert_eq!(
+            notices,
+            vec![
+                "NOTICE: table \"t\" does not exist, skipping".to_string(),
+                "WARNING: nonstandard use of \\\\ in a string literal".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ignores_other_targets() {
+        let ((), notices) = capture_notices(async {
+            tracing::info!(target: "sqlx::query", message = "SELECT 1");
+        })
+        .await;
+        assert!(notices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_notices_outside_capture_are_not_collected() {
+        let ((), first) = capture_notices(async {
+            tracing::info!(target: "sqlx::postgres::notice", message = "inside");
+        })
+        .await;
+        tracing::info!(target: "sqlx::postgres::notice", message = "outside");
+        assert_eq!(first, vec!["NOTICE: inside".to_string()]);
+    }
+}
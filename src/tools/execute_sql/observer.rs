@@ -0,0 +1,31 @@
+//! Query execution hooks for metrics and tracing
+//!
+//! Implement [`QueryObserver`] and register it with
+//! [`ExecuteSQLTool::with_observer`](super::ExecuteSQLTool::with_observer) to
+//! feed Prometheus, OpenTelemetry, or anything else, without this crate
+//! depending on those libraries.
+
+use std::time::Duration;
+
+/// Hook invoked around every statement the ExecuteSQL tool runs
+///
+/// Statements are identified by their literal-free fingerprint (see
+/// [`fingerprint_sql`](crate::sql_parser::fingerprint_sql)), so queries that
+/// differ only in their values share one metric series. Callbacks run inline
+/// on the executing task and should return quickly.
+pub trait QueryObserver: Send + Sync {
+    /// A statement is about to run
+    fn on_start(&self, _fingerprint: &str) {}
+
+    /// A statement finished
+    ///
+    /// `rows` is the number of rows it returned (0 on failure), and `result`
+    /// carries the error reported for it, if any.
+    fn on_finish(
+        &self,
+        fingerprint: &str,
+        duration: Duration,
+        rows: usize,
+        result: &anyhow::Result<()>,
+    );
+}
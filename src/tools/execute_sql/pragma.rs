@@ -0,0 +1,263 @@
+//! Friendly column names for SQLite PRAGMA results
+//!
+//! Introspection PRAGMAs return one row per column/index/key of the target
+//! table, using SQLite's internal column names (`cid`, `notnull`, `dflt_value`).
+//! These helpers rename them to the cross-dialect vocabulary used by the
+//! schema tools (`column_name`, `data_type`, `column_default`, ...).
+
+use crate::sql_parser::strip_comments;
+use crate::types::DatabaseType;
+use kodegen_mcp_schema::database::{ExecuteSQLOutput, SqlRow};
+use lazy_regex::{Lazy, Regex, lazy_regex};
+
+// Matches `PRAGMA name`, `PRAGMA schema.name(...)`, `PRAGMA name = value`
+static PRAGMA_NAME_REGEX: Lazy<Regex> = lazy_regex!(r"(?i)^\s*PRAGMA\s+(?:\w+\.)?(\w+)");
+
+/// SQLite column name → friendly column name, per PRAGMA
+fn pragma_column_map(pragma_name: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    match pragma_name {
+        "table_info" => Some(&[
+            ("cid", "ordinal_position"),
+            ("name", "column_name"),
+            ("type", "data_type"),
+            ("notnull", "not_null"),
+            ("dflt_value", "column_default"),
+            ("pk", "primary_key_position"),
+        ]),
+        "table_xinfo" => Some(&[
+            ("cid", "ordinal_position"),
+            ("name", "column_name"),
+            ("type", "data_type"),
+            ("notnull", "not_null"),
+            ("dflt_value", "column_default"),
+            ("pk", "primary_key_position"),
+            ("hidden", "hidden"),
+        ]),
+        "index_list" => Some(&[
+            ("seq", "ordinal"),
+            ("name", "index_name"),
+            ("unique", "is_unique"),
+            ("origin", "origin"),
+            ("partial", "is_partial"),
+        ]),
+        "index_info" => Some(&[
+            ("seqno", "ordinal_position"),
+            ("cid", "column_ordinal"),
+            ("name", "column_name"),
+        ]),
+        "index_xinfo" => Some(&[
+            ("seqno", "ordinal_position"),
+            ("cid", "column_ordinal"),
+            ("name", "column_name"),
+            ("desc", "is_descending"),
+            ("coll", "collation"),
+            ("key", "is_key"),
+        ]),
+        "foreign_key_list" => Some(&[
+            ("id", "constraint_id"),
+            ("seq", "ordinal_position"),
+            ("table", "referenced_table"),
+            ("from", "column_name"),
+            ("to", "referenced_column"),
+            ("on_update", "on_update"),
+            ("on_delete", "on_delete"),
+            ("match", "match_type"),
+        ]),
+        _ => None,
+    }
+}
+
+fn friendly_name(map: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+    map.iter()
+        .find(|(sqlite, _)| sqlite.eq_ignore_ascii_case(name))
+        .map(|(_, friendly)| *friendly)
+}
+
+/// Extract the lowercased PRAGMA name from a statement, if it is a PRAGMA
+pub fn pragma_name(sql: &str) -> Option<String> {
+    let stripped = strip_comments(sql, DatabaseType::SQLite);
+    PRAGMA_NAME_REGEX
+        .captures(&stripped)
+        .map(|captures| captures[1].to_lowercase())
+}
+
+/// Rename well-known PRAGMA result columns to friendly, cross-dialect names
+///
+/// Supports `table_info`, `table_xinfo`, `index_list`, `index_info`,
+/// `index_xinfo`, and `foreign_key_list`. Rows of other PRAGMAs, and columns
+/// not in the mapping, are returned unchanged.
+pub fn normalize_pragma_result(pragma_name: &str, mut rows: Vec<SqlRow>) -> Vec<SqlRow> {
+    let Some(map) = pragma_column_map(pragma_name) else {
+        return rows;
+    };
+    for row in &mut rows {
+        for column in &mut row.columns {
+            if let Some(friendly) = friendly_name(map, &column.name) {
+                column.name = friendly.to_string();
+            }
+        }
+    }
+    rows
+}
+
+/// Apply [`normalize_pragma_result`] to a whole ExecuteSQL result
+///
+/// No-op unless `sql` is a recognized SQLite PRAGMA.
+pub fn normalize_pragma_output(sql: &str, db_type: DatabaseType, output: &mut ExecuteSQLOutput) {
+    if db_type != DatabaseType::SQLite {
+        return;
+    }
+    let Some(name) = pragma_name(sql) else {
+        return;
+    };
+    let Some(map) = pragma_column_map(&name) else {
+        return;
+    };
+
+    for column in &mut output.columns {
+        if let Some(friendly) = friendly_name(map, column) {
+            *column = friendly.to_string();
+        }
+    }
+    for meta in &mut output.column_meta {
+        if let Some(friendly) = friendly_name(map, &meta.name) {
+            meta.name = friendly.to_string();
+        }
+    }
+    output.rows = normalize_pragma_result(&name, std::mem::take(&mut output.rows));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::execute_sql::row_converter::row_to_typed;
+    use kodegen_mcp_schema::database::SqlValue;
+    use sqlx::AnyPool;
+
+    async fn fixture() -> AnyPool {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        for sql in [
+            "CREATE TABLE departments (id INTEGER PRIMARY KEY DEFAULT 0, name TEXT NOT NULL DEFAULT '')",
+            "CREATE TABLE employees (id INTEGER PRIMARY KEY DEFAULT 0, \
+             department_id INTEGER DEFAULT 0 REFERENCES departments(id) ON DELETE CASCADE)",
+            "CREATE UNIQUE INDEX idx_departments_name ON departments(name)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.expect("fixture DDL");
+        }
+        pool
+    }
+
+    async fn pragma_rows(pool: &AnyPool, sql: &str) -> Vec<SqlRow> {
+        sqlx::query(sql)
+            .fetch_all(pool)
+            .await
+            .expect("pragma query")
+            .iter()
+            .map(|row| row_to_typed(row).expect("convert row"))
+            .collect()
+    }
+
+    fn value<'a>(row: &'a SqlRow, name: &str) -> &'a SqlValue {
+        &row.columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("missing column {}", name))
+            .value
+    }
+
+    #[test]
+    fn test_pragma_name() {
+        assert_eq!(
+            pragma_name("PRAGMA table_info(users)").as_deref(),
+            Some("table_info")
+        );
+        assert_eq!(
+            pragma_name("pragma main.INDEX_LIST('t')").as_deref(),
+            Some("index_list")
+        );
+        assert_eq!(pragma_name("SELECT 1"), None);
+    }
+
+    #[tokio::test]
+    async fn test_normalize_table_info() {
+        let pool = fixture().await;
+        let rows = normalize_pragma_result(
+            "table_info",
+            pragma_rows(&pool, "PRAGMA table_info(departments)").await,
+        );
+        assert_eq!(rows.len(), 2);
+        let names: Vec<&str> = rows[1].columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "ordinal_position",
+                "column_name",
+                "data_type",
+                "not_null",
+                "column_default",
+                "primary_key_position"
+            ]
+        );
+        assert!(matches!(value(&rows[1], "column_name"), SqlValue::Text(s) if s == "name"));
+        assert!(matches!(value(&rows[1], "not_null"), SqlValue::Int(1)));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_index_list() {
+        let pool = fixture().await;
+        let rows = normalize_pragma_result(
+            "index_list",
+            pragma_rows(&pool, "PRAGMA index_list(departments)").await,
+        );
+        let row = rows
+            .iter()
+            .find(|r| matches!(value(r, "index_name"), SqlValue::Text(s) if s == "idx_departments_name"))
+            .expect("named index listed");
+        assert!(matches!(value(row, "is_unique"), SqlValue::Int(1)));
+        assert!(matches!(value(row, "origin"), SqlValue::Text(s) if s == "c"));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_foreign_key_list() {
+        let pool = fixture().await;
+        let rows = normalize_pragma_result(
+            "foreign_key_list",
+            pragma_rows(&pool, "PRAGMA foreign_key_list(employees)").await,
+        );
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+        assert!(matches!(value(row, "referenced_table"), SqlValue::Text(s) if s == "departments"));
+        assert!(matches!(value(row, "column_name"), SqlValue::Text(s) if s == "department_id"));
+        assert!(matches!(value(row, "referenced_column"), SqlValue::Text(s) if s == "id"));
+        assert!(matches!(value(row, "on_delete"), SqlValue::Text(s) if s == "CASCADE"));
+    }
+
+    #[test]
+    fn test_unknown_pragma_unchanged() {
+        let mut output = ExecuteSQLOutput {
+            columns: vec!["journal_mode".into()],
+            column_meta: vec![],
+            rows: vec![],
+            row_count: 0,
+            affected_rows: None,
+            execution_time_ms: 0,
+            executed_statements: None,
+            total_statements: None,
+            errors: None,
+            warnings: vec![],
+            notices: vec![],
+            timed_out: false,
+            result_sets: vec![],
+            has_result_set: true,
+            truncation: None,
+            statement_timings: vec![],
+        };
+        normalize_pragma_output("PRAGMA journal_mode", DatabaseType::SQLite, &mut output);
+        assert_eq!(output.columns, vec!["journal_mode".to_string()]);
+    }
+}
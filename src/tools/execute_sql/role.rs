@@ -0,0 +1,116 @@
+//! Run statements under a configured PostgreSQL role (`db_execute_as_role`)
+//!
+//! Switching role before each statement lets the database enforce
+//! least-privilege permissions, independent of the tool's readonly check.
+
+use crate::error::DatabaseError;
+use crate::types::DatabaseType;
+use crate::validate::validate_postgres_role;
+use kodegen_config_manager::ConfigManager;
+use lazy_regex::{Lazy, Regex, lazy_regex};
+use sqlx::AnyConnection;
+
+// SET [SESSION | LOCAL] ROLE / SESSION AUTHORIZATION, RESET ROLE / ALL, DISCARD ALL,
+// and set_config('role', ...) can all change or drop the active role. Besides the
+// statement start, these are matched wherever a PL/pgSQL body or EXECUTE string
+// can begin a statement (after `;`, a dollar quote, a quote, BEGIN/THEN/ELSE/LOOP),
+// so function bodies are caught while `UPDATE t SET role = ...` is not.
+static ROLE_CHANGE_REGEX: Lazy<Regex> = lazy_regex!(
+    r"(?i)(?:^|;|\$\w*\$|'|\b(?:begin|then|else|loop)\b)\s*(?:set\s+(?:(?:session|local)\s+)?(?:role|session\s+authorization)\b|reset\s+(?:role|session\s+authorization|all)\b|discard\s+all\b)"
+);
+// DO blocks can build a role change with dynamic SQL no pattern can see
+static DO_BLOCK_REGEX: Lazy<Regex> = lazy_regex!(r"(?i)^\s*do\b");
+static SET_CONFIG_ROLE_REGEX: Lazy<Regex> =
+    lazy_regex!(r#"(?i)set_config\s*\(\s*'(?:role|session_authorization)'"#);
+
+/// Read `db_execute_as_role`, returning the quoted role when it applies
+///
+/// Roles are PostgreSQL-only; for other databases the setting is ignored with
+/// a warning.
+///
+/// # Errors
+/// Returns error if the configured role name is invalid
+pub fn execute_as_role(
+    config: &ConfigManager,
+    db_type: DatabaseType,
+) -> Result<Option<String>, DatabaseError> {
+    let Some(role) = config.get_value("db_execute_as_role").and_then(|v| match v {
+        kodegen_config_manager::ConfigValue::String(s) if !s.trim().is_empty() => Some(s),
+        _ => None,
+    }) else {
+        return Ok(None);
+    };
+
+    if db_type != DatabaseType::Postgres {
+        log::warn!("db_execute_as_role is only supported for PostgreSQL; ignoring it");
+        return Ok(None);
+    }
+
+    validate_postgres_role(role.trim()).map(Some)
+}
+
+/// Whether a statement would (or could) change or drop the session's active role
+///
+/// Anonymous `DO` blocks always count, since their PL/pgSQL may assemble the
+/// change at run time.
+pub fn is_role_change(sql: &str, db_type: DatabaseType) -> bool {
+    let stripped = crate::sql_parser::strip_comments(sql, db_type);
+    ROLE_CHANGE_REGEX.is_match(&stripped)
+        || SET_CONFIG_ROLE_REGEX.is_match(&stripped)
+        || DO_BLOCK_REGEX.is_match(&stripped)
+}
+
+/// Switch `conn` to `role` (already quoted) for the session or current transaction
+pub(super) async fn set_role(
+    conn: &mut AnyConnection,
+    role: &str,
+    local: bool,
+) -> Result<(), sqlx::Error> {
+    let scope = if local { "LOCAL " } else { "" };
+    sqlx::query(&format!("SET {}ROLE {}", scope, role))
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_change_detection() {
+        let db = DatabaseType::Postgres;
+        assert!(is_role_change("SET ROLE postgres", db));
+        assert!(is_role_change("set local role admin", db));
+        assert!(is_role_change("SET SESSION AUTHORIZATION admin", db));
+        assert!(is_role_change("RESET ROLE", db));
+        assert!(is_role_change("-- sneaky\nRESET ALL", db));
+        assert!(is_role_change("DISCARD ALL", db));
+        assert!(is_role_change("SELECT set_config('role', 'postgres', false)", db));
+        assert!(!is_role_change("SET search_path TO app", db));
+        assert!(!is_role_change("SELECT * FROM roles", db));
+        assert!(!is_role_change("UPDATE users SET role = 'admin' WHERE id = 1", db));
+    }
+
+    #[test]
+    fn test_role_change_inside_plpgsql_bodies() {
+        let db = DatabaseType::Postgres;
+        assert!(is_role_change("DO $$ BEGIN SET ROLE postgres; END $$", db));
+        assert!(is_role_change("DO $$ BEGIN PERFORM 1; RESET ROLE; END $$", db));
+        assert!(is_role_change("do $$ begin execute 'se' || 't role postgres'; end $$", db));
+        assert!(is_role_change(
+            "CREATE FUNCTION f() RETURNS void AS $body$ BEGIN SET ROLE postgres; END $body$ LANGUAGE plpgsql",
+            db
+        ));
+        assert!(is_role_change(
+            "CREATE FUNCTION f() RETURNS void AS $$ BEGIN EXECUTE 'RESET ROLE'; END $$ LANGUAGE plpgsql",
+            db
+        ));
+    }
+
+    #[test]
+    fn test_execute_as_role_unset() {
+        let config = ConfigManager::new();
+        assert_eq!(execute_as_role(&config, DatabaseType::Postgres).unwrap(), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Session state applied to a connection around executed statements
+//!
+//! Covers the `db_execute_as_role` role and the per-call PostgreSQL
+//! `plan_cache_mode` override. Wherever the statements run inside a transaction
+//! the settings are applied with `SET LOCAL`, so they end with it, even when the
+//! call is cancelled. Batches that manage their own transactions need session
+//! `SET`s, which are reset before the connection returns to the pool; a
+//! [`SessionGuard`] closes the connection if the reset never runs.
+
+use super::role::set_role;
+use sqlx::pool::PoolConnection;
+use sqlx::{Any, AnyConnection};
+use std::ops::{Deref, DerefMut};
+
+/// Connection settings to apply around a statement
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct SessionSettings {
+    /// Quoted PostgreSQL role to switch to (`db_execute_as_role`)
+    pub role: Option<String>,
+    /// Set PostgreSQL `plan_cache_mode = force_custom_plan`
+    pub force_custom_plan: bool,
+}
+
+impl SessionSettings {
+    /// Whether nothing needs to be applied, so the statement may run directly on the pool
+    pub fn is_empty(&self) -> bool {
+        self.role.is_none() && !self.force_custom_plan
+    }
+
+    /// Apply the settings for the session, or only the current transaction when `local`
+    pub async fn apply(&self, conn: &mut AnyConnection, local: bool) -> Result<(), sqlx::Error> {
+        if let Some(role) = &self.role {
+            set_role(conn, role, local).await?;
+        }
+        if self.force_custom_plan {
+            let scope = if local { "LOCAL " } else { "" };
+            sqlx::query(&format!("SET {}plan_cache_mode = force_custom_plan", scope))
+                .execute(&mut *conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Reset session-level settings before the connection returns to the pool
+    ///
+    /// If a reset fails the connection is closed instead of being reused.
+    pub async fn release(&self, conn: &mut PoolConnection<Any>) {
+        let mut resets = Vec::new();
+        if self.role.is_some() {
+            resets.push("RESET ROLE");
+        }
+        if self.force_custom_plan {
+            resets.push("RESET plan_cache_mode");
+        }
+        for reset in resets {
+            if let Err(e) = sqlx::query(reset).execute(&mut **conn).await {
+                log::warn!("{} failed, closing connection: {}", reset, e);
+                conn.close_on_drop();
+                return;
+            }
+        }
+    }
+}
+
+/// A pooled connection that is closed on drop unless its session state was reset
+///
+/// Session settings, and a batch's own open transaction, stay on the connection
+/// until they are reset. If the future using the connection is cancelled (e.g.
+/// by the statement timeout) or returns early before [`SessionGuard::release`],
+/// dropping the guard closes the connection rather than returning it to the
+/// pool with that state.
+pub(super) struct SessionGuard<'c> {
+    conn: &'c mut PoolConnection<Any>,
+    clean: bool,
+}
+
+impl<'c> SessionGuard<'c> {
+    pub fn new(conn: &'c mut PoolConnection<Any>) -> Self {
+        Self { conn, clean: false }
+    }
+
+    /// Close the connection when the guard is dropped, even after a release
+    pub fn close_on_drop(&mut self) {
+        self.conn.close_on_drop();
+    }
+
+    /// Let the connection return to the pool; nothing session-level is left on it
+    pub fn reusable(mut self) {
+        self.clean = true;
+    }
+
+    /// Reset `settings`, then let the connection return to the pool
+    ///
+    /// If a reset fails the connection is closed instead.
+    pub async fn release(mut self, settings: &SessionSettings) {
+        settings.release(&mut *self.conn).await;
+        self.clean = true;
+    }
+}
+
+impl Deref for SessionGuard<'_> {
+    type Target = AnyConnection;
+
+    fn deref(&self) -> &AnyConnection {
+        &**self.conn
+    }
+}
+
+impl DerefMut for SessionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut AnyConnection {
+        &mut **self.conn
+    }
+}
+
+impl Drop for SessionGuard<'_> {
+    fn drop(&mut self) {
+        if !self.clean {
+            self.conn.close_on_drop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the connection handed out next still has the `marker` temp table
+    async fn marker_survives(release: bool) -> bool {
+        sqlx::any::install_default_drivers();
+        // One connection, so the next acquire gets the same one unless it was closed
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+
+        let mut pooled = pool.acquire().await.expect("acquire");
+        let mut conn = SessionGuard::new(&mut pooled);
+        sqlx::query("CREATE TEMP TABLE marker (id INTEGER)")
+            .execute(&mut *conn)
+            .await
+            .expect("temp table");
+        if release {
+            conn.release(&SessionSettings::default()).await;
+        } else {
+            // e.g. the statement timeout dropped the future before the reset ran
+            drop(conn);
+        }
+        drop(pooled);
+
+        let mut conn = pool.acquire().await.expect("reacquire");
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_temp_master WHERE name = 'marker'")
+                .fetch_one(&mut *conn)
+                .await
+                .expect("count");
+        count == 1
+    }
+
+    #[tokio::test]
+    async fn test_unreleased_guard_closes_connection() {
+        assert!(!marker_survives(false).await);
+        assert!(marker_survives(true).await);
+    }
+}
@@ -0,0 +1,250 @@
+//! Result truncation by row, byte, and column limits
+//!
+//! Each limit trims the top-level `columns`/`rows` of an output in place and
+//! records why in `ExecuteSQLOutput::truncation`, so clients can tell a short
+//! result from a cut-off one. `returned` counts what was kept: rows for the row
+//! and byte limits, columns for the column limit.
+//!
+//! The byte limit also covers `result_sets` (procedure calls), whose rows would
+//! otherwise bypass it.
+
+use super::row_converter::sql_row_size;
+use kodegen_mcp_schema::database::{ExecuteSQLOutput, SqlRow, Truncation, TruncationReason};
+
+/// Keep at most `max_rows` rows
+///
+/// Callers fetch one row more than the limit, so a cut here means rows really
+/// were left behind rather than the result happening to be exactly that long.
+pub(super) fn limit_rows(output: &mut ExecuteSQLOutput, max_rows: usize) {
+    if output.rows.len() <= max_rows {
+        return;
+    }
+    output.rows.truncate(max_rows);
+    record(output, TruncationReason::RowLimit, max_rows, max_rows);
+}
+
+/// Drop the first `skip_rows` rows (`skip_rows` that could not become an OFFSET)
+///
+/// Not a truncation: the rows were skipped on request, so `truncation` is left
+/// unset and `row_count` simply shrinks.
+pub(super) fn skip_leading_rows(output: &mut ExecuteSQLOutput, skip_rows: usize) {
+    let skipped = skip_rows.min(output.rows.len());
+    output.rows.drain(..skipped);
+    output.row_count = output.rows.len();
+}
+
+/// Keep the leading rows whose decoded size fits within `max_bytes`
+///
+/// With `result_sets`, the budget is shared by all of them in order, and the
+/// top-level rows, which repeat the first set, are cut to match it.
+pub(super) fn limit_bytes(output: &mut ExecuteSQLOutput, max_bytes: usize) {
+    let mut total = 0;
+    if output.result_sets.is_empty() {
+        let fitting = fitting_rows(&output.rows, max_bytes, &mut total);
+        if fitting == output.rows.len() {
+            return;
+        }
+        output.rows.truncate(fitting);
+        record(output, TruncationReason::ByteLimit, max_bytes, fitting);
+        return;
+    }
+
+    let mut cut = false;
+    let mut kept = 0;
+    for set in &mut output.result_sets {
+        let fitting = fitting_rows(&set.rows, max_bytes, &mut total);
+        cut |= fitting < set.rows.len();
+        set.rows.truncate(fitting);
+        set.row_count = fitting;
+        kept += fitting;
+    }
+    if !cut {
+        return;
+    }
+    let first_set_rows = output.result_sets[0].rows.len();
+    output.rows.truncate(first_set_rows);
+    record(output, TruncationReason::ByteLimit, max_bytes, kept);
+}
+
+/// How many leading `rows` fit, adding their sizes to the running `total`
+fn fitting_rows(rows: &[SqlRow], max_bytes: usize, total: &mut usize) -> usize {
+    rows.iter()
+        .take_while(|row| {
+            *total += sql_row_size(row);
+            *total <= max_bytes
+        })
+        .count()
+}
+
+/// Keep the first `max_columns` columns of every row
+pub(super) fn limit_columns(output: &mut ExecuteSQLOutput, max_columns: usize) {
+    if output.columns.len() <= max_columns {
+        return;
+    }
+    output.columns.truncate(max_columns);
+    output.column_meta.truncate(max_columns);
+    for row in &mut output.rows {
+        row.columns.truncate(max_columns);
+    }
+    record(output, TruncationReason::ColumnLimit, max_columns, max_columns);
+}
+
+/// Record a truncation; when several limits apply, the last one wins
+fn record(output: &mut ExecuteSQLOutput, reason: TruncationReason, limit: usize, returned: usize) {
+    output.row_count = output.rows.len();
+    output.truncation = Some(Truncation { reason, limit, returned });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodegen_mcp_schema::database::{ColumnMeta, ResultSet, SqlColumnValue, SqlValue};
+
+    fn output(column_names: &[&str], row_count: usize, text: &str) -> ExecuteSQLOutput {
+        let rows: Vec<SqlRow> = (0..row_count)
+            .map(|_| SqlRow {
+                columns: column_names
+                    .iter()
+                    .map(|name| SqlColumnValue {
+                        name: name.to_string(),
+                        value: SqlValue::Text(text.to_string()),
+                    })
+                    .collect(),
+            })
+            .collect();
+        ExecuteSQLOutput {
+            columns: column_names.iter().map(|name| name.to_string()).collect(),
+            column_meta: column_names
+                .iter()
+                .map(|name| ColumnMeta {
+                    name: name.to_string(),
+                    db_type: "TEXT".into(),
+                    nullable: None,
+                })
+                .collect(),
+            row_count: rows.len(),
+            rows,
+            affected_rows: None,
+            execution_time_ms: 0,
+            executed_statements: None,
+            total_statements: None,
+            errors: None,
+            warnings: vec![],
+            notices: vec![],
+            timed_out: false,
+            result_sets: vec![],
+            has_result_set: true,
+            truncation: None,
+            statement_timings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_row_limit() {
+        let mut out = output(&["a"], 11, "x");
+        limit_rows(&mut out, 10);
+        assert_eq!(out.row_count, 10);
+        assert_eq!(
+            out.truncation,
+            Some(Truncation { reason: TruncationReason::RowLimit, limit: 10, returned: 10 })
+        );
+
+        // Exactly at the limit is not a truncation
+        let mut out = output(&["a"], 10, "x");
+        limit_rows(&mut out, 10);
+        assert_eq!(out.row_count, 10);
+        assert_eq!(out.truncation, None);
+    }
+
+    #[test]
+    fn test_skip_leading_rows() {
+        let mut out = output(&["a"], 12, "x");
+        skip_leading_rows(&mut out, 5);
+        assert_eq!(out.row_count, 7);
+        limit_rows(&mut out, 5);
+        assert_eq!(out.row_count, 5);
+        assert_eq!(out.truncation.map(|t| t.reason), Some(TruncationReason::RowLimit));
+
+        // Skipping past the end leaves an empty result, not a truncated one
+        let mut out = output(&["a"], 3, "x");
+        skip_leading_rows(&mut out, 5);
+        assert_eq!(out.row_count, 0);
+        assert_eq!(out.truncation, None);
+    }
+
+    #[test]
+    fn test_byte_limit() {
+        // Two 100-byte cells per row: 3 rows fit in 650 bytes
+        let mut out = output(&["a", "b"], 5, &"x".repeat(100));
+        limit_bytes(&mut out, 650);
+        assert_eq!(out.row_count, 3);
+        assert_eq!(out.rows.len(), 3);
+        assert_eq!(
+            out.truncation,
+            Some(Truncation { reason: TruncationReason::ByteLimit, limit: 650, returned: 3 })
+        );
+
+        let mut out = output(&["a", "b"], 5, &"x".repeat(100));
+        limit_bytes(&mut out, 1000);
+        assert_eq!(out.row_count, 5);
+        assert_eq!(out.truncation, None);
+    }
+
+    #[test]
+    fn test_byte_limit_covers_result_sets() {
+        // A procedure call: the top-level rows repeat the first of two sets
+        let procedure_output = || {
+            let mut out = output(&["a"], 3, &"x".repeat(100));
+            let second = output(&["b"], 3, &"y".repeat(100));
+            let result_sets = [&out, &second]
+                .iter()
+                .map(|set| ResultSet {
+                    columns: set.columns.clone(),
+                    column_meta: set.column_meta.clone(),
+                    row_count: set.rows.len(),
+                    rows: set.rows.clone(),
+                })
+                .collect();
+            out.result_sets = result_sets;
+            out
+        };
+
+        // 450 bytes: the first set fits, one row of the second does
+        let mut out = procedure_output();
+        limit_bytes(&mut out, 450);
+        assert_eq!(out.result_sets[0].row_count, 3);
+        assert_eq!(out.result_sets[1].row_count, 1);
+        assert_eq!(out.result_sets[1].rows.len(), 1);
+        assert_eq!(out.row_count, 3);
+        assert_eq!(
+            out.truncation,
+            Some(Truncation { reason: TruncationReason::ByteLimit, limit: 450, returned: 4 })
+        );
+
+        // 250 bytes: the cut reaches the first set and the top-level rows
+        let mut out = procedure_output();
+        limit_bytes(&mut out, 250);
+        assert_eq!(out.result_sets[0].row_count, 2);
+        assert_eq!(out.result_sets[1].row_count, 0);
+        assert_eq!(out.row_count, 2);
+
+        // Everything fits: nothing recorded
+        let mut out = procedure_output();
+        limit_bytes(&mut out, 10_000);
+        assert_eq!(out.truncation, None);
+    }
+
+    #[test]
+    fn test_column_limit() {
+        let mut out = output(&["a", "b", "c"], 2, "x");
+        limit_columns(&mut out, 2);
+        assert_eq!(out.columns, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(out.column_meta.len(), 2);
+        assert!(out.rows.iter().all(|row| row.columns.len() == 2));
+        assert_eq!(out.row_count, 2);
+        assert_eq!(
+            out.truncation,
+            Some(Truncation { reason: TruncationReason::ColumnLimit, limit: 2, returned: 2 })
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! Decode strategies for database types the built-in conversion doesn't know
+//!
+//! Register a [`TypeMapper`] with
+//! [`ExecuteSQLTool::with_type_mapper`](super::ExecuteSQLTool::with_type_mapper),
+//! or list mappings in `db_type_mappings` (`"text=json"`). Mappings are
+//! consulted before the built-in type matching, so they override it.
+//!
+//! Type names are the ones the `sqlx::any` driver reports, which are its own
+//! kinds (`BOOLEAN`, `SMALLINT`, `INTEGER`, `BIGINT`, `REAL`, `DOUBLE`, `TEXT`,
+//! `BLOB`, `NULL`), not the database's. Columns of types the driver has no kind
+//! for, such as PostgreSQL enums, domains over them, `hstore` and `json`, are
+//! rejected by the driver before any mapping runs; select them with a cast
+//! (`mood::text`, `attrs::text`) and map `text` if the value needs more than
+//! plain text, e.g. `text=json` to turn `hstore` output into a JSON object.
+
+use crate::error::DatabaseError;
+use kodegen_config_manager::ConfigManager;
+use kodegen_mcp_schema::database::SqlValue;
+use serde_json::{Map, Value};
+use sqlx::Row;
+use std::collections::HashMap;
+
+/// How to decode a column of a mapped type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeDecode {
+    /// Decode as text (enums, domains over text, ...)
+    Text,
+    /// Decode as a 64-bit integer
+    Int,
+    /// Decode as a double
+    Float,
+    /// Decode as a boolean
+    Bool,
+    /// Decode as JSON text; `hstore` output (`"k"=>"v"`) is converted to a JSON object
+    Json,
+    /// Decode as raw bytes
+    Blob,
+}
+
+impl std::str::FromStr for TypeDecode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" | "string" => Ok(Self::Text),
+            "int" | "integer" => Ok(Self::Int),
+            "float" | "double" => Ok(Self::Float),
+            "bool" | "boolean" => Ok(Self::Bool),
+            "json" => Ok(Self::Json),
+            "blob" | "bytes" => Ok(Self::Blob),
+            other => Err(format!(
+                "unknown decode strategy '{}' (expected text, int, float, bool, json or blob)",
+                other
+            )),
+        }
+    }
+}
+
+/// Type name to decode strategy mappings
+///
+/// Type names are matched case-insensitively against the name the driver
+/// reports for the column (see the module docs for which names those are).
+///
+/// # Example
+/// ```rust
+/// # use kodegen_tools_database::{TypeDecode, TypeMapper};
+/// let mapper = TypeMapper::new()
+///     .map("text", TypeDecode::Json)
+///     .map("bigint", TypeDecode::Float);
+/// assert_eq!(mapper.get("TEXT"), Some(TypeDecode::Json));
+/// assert_eq!(mapper.get("blob"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeMapper {
+    mappings: HashMap<String, TypeDecode>,
+}
+
+impl TypeMapper {
+    /// Create an empty mapper
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode columns of `type_name` with `decode`
+    pub fn map(mut self, type_name: &str, decode: TypeDecode) -> Self {
+        self.mappings.insert(type_name.trim().to_uppercase(), decode);
+        self
+    }
+
+    /// Parse a comma-separated `type=strategy` list, e.g. `"text=json, bigint=float"`
+    ///
+    /// # Errors
+    /// Returns error for an entry without `=` or with an unknown strategy
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut mapper = Self::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (type_name, decode) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected 'type=strategy', got '{}'", entry))?;
+            mapper = mapper.map(type_name, decode.parse()?);
+        }
+        Ok(mapper)
+    }
+
+    /// Read `db_type_mappings`; an invalid value is logged and ignored
+    pub fn from_config(config: &ConfigManager) -> Self {
+        match config.get_value("db_type_mappings") {
+            Some(kodegen_config_manager::ConfigValue::String(spec)) => {
+                Self::parse(&spec).unwrap_or_else(|e| {
+                    log::warn!("Ignoring db_type_mappings: {}", e);
+                    Self::new()
+                })
+            }
+            _ => Self::new(),
+        }
+    }
+
+    /// Add `other`'s mappings, replacing any for the same type
+    pub fn extend(&mut self, other: &TypeMapper) {
+        self.mappings
+            .extend(other.mappings.iter().map(|(name, decode)| (name.clone(), *decode)));
+    }
+
+    /// Strategy registered for `type_name`
+    pub fn get(&self, type_name: &str) -> Option<TypeDecode> {
+        if self.mappings.is_empty() {
+            return None;
+        }
+        self.mappings.get(&type_name.to_uppercase()).copied()
+    }
+
+    /// Whether no mapping is registered
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+}
+
+/// Decode a column with a registered strategy
+pub(super) fn mapped_value(
+    row: &sqlx::any::AnyRow,
+    ordinal: usize,
+    name: &str,
+    type_name: &str,
+    decode: TypeDecode,
+) -> Result<SqlValue, DatabaseError> {
+    let failed = |e: &dyn std::fmt::Display| {
+        DatabaseError::QueryError(format!(
+            "Failed to decode column '{}' of type '{}' as {:?} (db_type_mappings): {}",
+            name, type_name, decode, e
+        ))
+    };
+    let value = match decode {
+        TypeDecode::Text => row
+            .try_get::<Option<String>, _>(ordinal)
+            .map_err(|e| failed(&e))?
+            .map(SqlValue::Text),
+        TypeDecode::Int => row
+            .try_get::<Option<i64>, _>(ordinal)
+            .map_err(|e| failed(&e))?
+            .map(SqlValue::Int),
+        TypeDecode::Float => row
+            .try_get::<Option<f64>, _>(ordinal)
+            .map_err(|e| failed(&e))?
+            .map(SqlValue::Float),
+        TypeDecode::Bool => row
+            .try_get::<Option<bool>, _>(ordinal)
+            .map_err(|e| failed(&e))?
+            .map(SqlValue::Bool),
+        TypeDecode::Blob => row
+            .try_get::<Option<Vec<u8>>, _>(ordinal)
+            .map_err(|e| failed(&e))?
+            .map(SqlValue::Blob),
+        TypeDecode::Json => {
+            let text = row.try_get::<Option<String>, _>(ordinal).map_err(|e| failed(&e))?;
+            match text {
+                Some(text) => Some(SqlValue::Text(
+                    json_text(&text).ok_or_else(|| failed(&"value is neither JSON nor hstore"))?,
+                )),
+                None => None,
+            }
+        }
+    };
+    Ok(value.unwrap_or(SqlValue::Null))
+}
+
+/// JSON text for a value that is JSON already or in hstore output format
+fn json_text(text: &str) -> Option<String> {
+    if serde_json::from_str::<Value>(text).is_ok() {
+        return Some(text.to_string());
+    }
+    hstore_to_json(text).map(|object| Value::Object(object).to_string())
+}
+
+/// Parse hstore output (`"a"=>"1", "b"=>NULL`) into a JSON object
+fn hstore_to_json(text: &str) -> Option<Map<String, Value>> {
+    let mut object = Map::new();
+    let mut chars = text.trim().chars().peekable();
+
+    // A double-quoted, backslash-escaped hstore string
+    fn quoted(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+        if chars.next()? != '"' {
+            return None;
+        }
+        let mut s = String::new();
+        loop {
+            match chars.next()? {
+                '\\' => s.push(chars.next()?),
+                '"' => return Some(s),
+                c => s.push(c),
+            }
+        }
+    }
+
+    while chars.peek().is_some() {
+        let key = quoted(&mut chars)?;
+        let arrow: String = chars.by_ref().take(2).collect();
+        if arrow != "=>" {
+            return None;
+        }
+        let value = if chars.peek() == Some(&'"') {
+            Value::String(quoted(&mut chars)?)
+        } else {
+            let word: String = chars.by_ref().take(4).collect();
+            if !word.eq_ignore_ascii_case("NULL") {
+                return None;
+            }
+            Value::Null
+        };
+        object.insert(key, value);
+
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        match chars.next() {
+            Some(',') => {
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            None => break,
+            Some(_) => return None,
+        }
+    }
+    Some(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mappings() {
+        let mapper = TypeMapper::parse("mood=text, HStore = json ,").expect("valid");
+        assert_eq!(mapper.get("mood"), Some(TypeDecode::Text));
+        assert_eq!(mapper.get("hstore"), Some(TypeDecode::Json));
+        assert_eq!(mapper.get("citext"), None);
+
+        assert!(TypeMapper::parse("mood").is_err());
+        assert!(TypeMapper::parse("mood=enum").is_err());
+    }
+
+    #[test]
+    fn test_hstore_to_json() {
+        let json = json_text(r#""a"=>"1", "quote \"q\""=>"x", "gone"=>NULL"#).expect("hstore");
+        let value: Value = serde_json::from_str(&json).expect("json");
+        assert_eq!(value["a"], "1");
+        assert_eq!(value["quote \"q\""], "x");
+        assert!(value["gone"].is_null());
+
+        assert_eq!(json_text(r#"{"k": 1}"#).as_deref(), Some(r#"{"k": 1}"#));
+        assert_eq!(json_text("not json"), None);
+    }
+
+    #[tokio::test]
+    async fn test_mapped_custom_type_decodes() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        let row = sqlx::query("SELECT 'happy' AS feeling, '\"size\"=>\"L\"' AS attrs")
+            .fetch_one(&pool)
+            .await
+            .expect("fetch row");
+
+        // Decoded as if the driver reported the custom type names
+        let feeling = mapped_value(&row, 0, "feeling", "mood", TypeDecode::Text).expect("text");
+        assert!(matches!(feeling, SqlValue::Text(ref s) if s == "happy"));
+        let attrs = mapped_value(&row, 1, "attrs", "hstore", TypeDecode::Json).expect("json");
+        assert!(matches!(attrs, SqlValue::Text(ref s) if s == r#"{"size":"L"}"#));
+        assert!(mapped_value(&row, 0, "feeling", "mood", TypeDecode::Json).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Introspection SQL tool - Shows the SQL a schema operation would run, without running it
+//!
+//! The metadata tools build their queries with the pure functions in
+//! [`crate::schema_queries`]; this tool exposes them directly so the SQL can be
+//! studied or run by hand. Nothing is sent to the database.
+
+use crate::error::DatabaseError;
+use crate::schema_queries::{
+    get_default_schema, get_indexes_query, get_schemas_query, get_stored_procedures_query,
+    get_table_schema_query, get_tables_query,
+};
+use crate::tools::helpers::explicit_or_configured_schema;
+use crate::types::DatabaseType;
+use kodegen_config_manager::ConfigManager;
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{
+    GetIntrospectionSqlArgs, GetIntrospectionSqlOutput, GetIntrospectionSqlPrompts,
+};
+use kodegen_mcp_schema::{McpError, Tool, ToolExecutionContext, ToolResponse};
+use std::sync::Arc;
+
+/// Schema operation whose SQL can be requested
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntrospectionOperation {
+    /// `db_list_schemas`
+    Schemas,
+    /// `db_list_tables`
+    Tables,
+    /// `db_table_schema`
+    Columns,
+    /// `db_table_indexes`
+    Indexes,
+    /// `db_stored_procedures`
+    Procedures,
+}
+
+impl IntrospectionOperation {
+    /// Every operation, in the order they are listed to callers
+    pub const ALL: [Self; 5] = [
+        Self::Schemas,
+        Self::Tables,
+        Self::Columns,
+        Self::Indexes,
+        Self::Procedures,
+    ];
+
+    /// Parse an operation name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|op| op.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Name used in tool arguments
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Schemas => "schemas",
+            Self::Tables => "tables",
+            Self::Columns => "columns",
+            Self::Indexes => "indexes",
+            Self::Procedures => "procedures",
+        }
+    }
+}
+
+/// SQL and bound parameters for `operation` on `db_type`
+///
+/// `schema` must already be resolved; `table` is required by `columns` and
+/// `indexes`. The SQL keeps the dialect's placeholders (`$1`, `?`, `@P1`).
+///
+/// # Errors
+/// Returns `DatabaseError` if `table` is missing where required or the dialect
+/// has no such operation
+pub fn introspection_sql(
+    db_type: DatabaseType,
+    operation: IntrospectionOperation,
+    schema: &str,
+    table: Option<&str>,
+) -> Result<(String, Vec<String>), DatabaseError> {
+    let require_table = || {
+        table.ok_or_else(|| {
+            DatabaseError::QueryError(format!("Operation '{}' requires a table", operation.name()))
+        })
+    };
+    match operation {
+        IntrospectionOperation::Schemas => Ok((get_schemas_query(db_type), Vec::new())),
+        IntrospectionOperation::Tables => Ok(get_tables_query(db_type, Some(schema), None, false)),
+        IntrospectionOperation::Columns => {
+            get_table_schema_query(db_type, schema, require_table()?)
+        }
+        IntrospectionOperation::Indexes => get_indexes_query(db_type, schema, require_table()?),
+        IntrospectionOperation::Procedures => get_stored_procedures_query(db_type, schema, false)
+            .ok_or_else(|| {
+                DatabaseError::FeatureNotSupported(format!(
+                    "{} does not support stored procedures",
+                    db_type
+                ))
+            }),
+    }
+}
+
+/// Tool for returning introspection SQL without executing it
+#[derive(Clone)]
+pub struct GetIntrospectionSQLTool {
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+}
+
+impl GetIntrospectionSQLTool {
+    /// Create a new GetIntrospectionSQLTool instance
+    ///
+    /// # Errors
+    /// Returns error if connection_url cannot be parsed to determine database type
+    pub fn new(connection_url: &str, config: Arc<ConfigManager>) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        Ok(Self { db_type, config })
+    }
+}
+
+impl Tool for GetIntrospectionSQLTool {
+    type Args = GetIntrospectionSqlArgs;
+    type Prompts = GetIntrospectionSqlPrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_INTROSPECTION_SQL
+    }
+
+    fn description() -> &'static str {
+        "Return the SQL and parameters that a schema operation would run on the connected \
+         database, without executing it. Operations: schemas, tables, columns, indexes, \
+         procedures (columns and indexes need a table). Useful for learning how the \
+         introspection works or for running the query yourself."
+    }
+
+    fn read_only() -> bool {
+        true
+    }
+
+    fn open_world() -> bool {
+        false // Never contacts the database
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        let db_type = self.db_type;
+        let operation = IntrospectionOperation::parse(&args.operation).ok_or_else(|| {
+            McpError::InvalidArguments(format!(
+                "Unknown operation '{}'; expected one of: {}",
+                args.operation,
+                IntrospectionOperation::ALL.map(IntrospectionOperation::name).join(", ")
+            ))
+        })?;
+
+        // Resolved without a query, so the tool never touches the database
+        let schema = explicit_or_configured_schema(
+            args.schema,
+            self.config.get_value("db_default_schema"),
+        )
+        .or_else(|| get_default_schema(db_type).map(str::to_string))
+        .ok_or_else(|| {
+            McpError::InvalidArguments(format!(
+                "{} has no static default schema; pass schema",
+                db_type
+            ))
+        })?;
+
+        let (sql, params) = introspection_sql(db_type, operation, &schema, args.table.as_deref())?;
+
+        let display = format!(
+            "📝 {} SQL for '{}'\n\n{}\n\nParameters: [{}]",
+            db_type,
+            operation.name(),
+            sql,
+            params.join(", ")
+        );
+        let output = GetIntrospectionSqlOutput {
+            operation: operation.name().to_string(),
+            database_type: db_type.to_string(),
+            sql,
+            params,
+        };
+        Ok(ToolResponse::new(display, output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sql_for(db_type: DatabaseType, operation: IntrospectionOperation) -> (String, Vec<String>) {
+        let schema = get_default_schema(db_type).unwrap_or("app");
+        introspection_sql(db_type, operation, schema, Some("users")).expect("supported")
+    }
+
+    #[test]
+    fn test_parse_operation() {
+        assert_eq!(
+            IntrospectionOperation::parse(" Indexes"),
+            Some(IntrospectionOperation::Indexes)
+        );
+        assert_eq!(IntrospectionOperation::parse("views"), None);
+    }
+
+    #[test]
+    fn test_postgres_templates_use_numbered_placeholders() {
+        let db = DatabaseType::Postgres;
+        let (sql, params) = sql_for(db, IntrospectionOperation::Schemas);
+        assert!(sql.contains("information_schema.schemata"), "{}", sql);
+        assert!(params.is_empty());
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Tables);
+        assert!(sql.contains("table_schema = $1"), "{}", sql);
+        assert_eq!(params, ["public"]);
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Columns);
+        assert!(sql.contains("table_schema = $1 AND table_name = $2"), "{}", sql);
+        assert_eq!(params, ["public", "users"]);
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Indexes);
+        assert!(sql.contains("pg_index") && sql.contains("$2"), "{}", sql);
+        assert_eq!(params, ["public", "users"]);
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Procedures);
+        assert!(sql.contains("r.routine_schema = $1"), "{}", sql);
+        assert_eq!(params, ["public"]);
+    }
+
+    #[test]
+    fn test_mysql_templates_use_question_marks() {
+        let db = DatabaseType::MySQL;
+        let (sql, params) = sql_for(db, IntrospectionOperation::Columns);
+        assert!(sql.contains("table_schema = ? AND table_name = ?"), "{}", sql);
+        assert_eq!(params, ["app", "users"]);
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Indexes);
+        assert!(sql.contains("information_schema.statistics"), "{}", sql);
+        assert_eq!(params, ["app", "users"]);
+
+        let (sql, _) = sql_for(db, IntrospectionOperation::Procedures);
+        assert!(!sql.contains('$'), "{}", sql);
+    }
+
+    #[test]
+    fn test_sqlite_templates() {
+        let db = DatabaseType::SQLite;
+        let (sql, params) = sql_for(db, IntrospectionOperation::Schemas);
+        assert!(sql.contains("pragma_database_list"), "{}", sql);
+        assert!(params.is_empty());
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Columns);
+        assert!(sql.contains("pragma_table_info(?, ?)"), "{}", sql);
+        assert_eq!(params, ["users", "main"]);
+
+        let (sql, params) = sql_for(db, IntrospectionOperation::Indexes);
+        assert!(sql.contains("pragma_index_list(?, ?)"), "{}", sql);
+        assert_eq!(params, ["users", "main", "main"]);
+
+        let err = introspection_sql(db, IntrospectionOperation::Procedures, "main", None);
+        assert!(matches!(err, Err(DatabaseError::FeatureNotSupported(_))));
+    }
+
+    #[test]
+    fn test_sql_server_templates_use_named_placeholders() {
+        let (sql, params) = sql_for(DatabaseType::SqlServer, IntrospectionOperation::Columns);
+        assert!(sql.contains("table_schema = @P1 AND table_name = @P2"), "{}", sql);
+        assert_eq!(params, ["dbo", "users"]);
+    }
+
+    #[test]
+    fn test_table_required() {
+        let err = introspection_sql(
+            DatabaseType::Postgres,
+            IntrospectionOperation::Indexes,
+            "public",
+            None,
+        );
+        assert!(matches!(err, Err(DatabaseError::QueryError(_))));
+    }
+}
@@ -0,0 +1,164 @@
+//! Get table constraints tool
+
+use crate::tools::concurrency::QueryLimiter;
+use crate::constraints::constraints_from_rows;
+use crate::schema_queries::get_constraints_query;
+use crate::tools::helpers::resolve_schema_default;
+use crate::tools::timeout::execute_with_timeout;
+use crate::types::{DatabaseType, TableConstraint};
+use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{
+    ConstraintInfo, GetTableConstraintsArgs, GetTableConstraintsOutput, TableConstraintsPrompts,
+};
+use kodegen_config_manager::ConfigManager;
+use sqlx::AnyPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tool for retrieving table constraint information
+#[derive(Clone)]
+pub struct GetTableConstraintsTool {
+    pool: Arc<AnyPool>,
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+    query_limiter: QueryLimiter,
+}
+
+impl GetTableConstraintsTool {
+    /// Create a new GetTableConstraintsTool instance
+    pub fn new(
+        pool: Arc<AnyPool>,
+        connection_url: &str,
+        config: Arc<ConfigManager>,
+    ) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
+        Ok(Self {
+            pool,
+            db_type,
+            config,
+            query_limiter,
+        })
+    }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
+}
+
+/// Convert a parsed constraint to its output form
+pub(crate) fn to_constraint_info(constraint: &TableConstraint) -> ConstraintInfo {
+    ConstraintInfo {
+        name: constraint.constraint_name.clone(),
+        constraint_type: constraint.constraint_type.clone(),
+        columns: constraint.column_names.clone(),
+        definition: constraint.definition.clone(),
+    }
+}
+
+impl Tool for GetTableConstraintsTool {
+    type Args = GetTableConstraintsArgs;
+    type Prompts = TableConstraintsPrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_TABLE_CONSTRAINTS
+    }
+
+    fn description() -> &'static str {
+        "Get constraint information for a table: PRIMARY KEY, FOREIGN KEY, UNIQUE, \
+         CHECK and NOT NULL constraints with their columns and, for CHECK and FOREIGN KEY, \
+         their definitions. Use this to learn which values a write must satisfy before \
+         running it. Returns array of constraints with metadata."
+    }
+
+    fn read_only() -> bool {
+        true // Only reads metadata
+    }
+
+    fn open_world() -> bool {
+        true // Queries external database
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
+        let db_type = self.db_type;
+
+        // Resolve schema
+        let schema = match args.schema {
+            Some(s) => s,
+            None => resolve_schema_default(db_type, &self.pool, &self.config).await?,
+        };
+
+        // Validation enforced for SQLite
+        let (query, params) = get_constraints_query(db_type, &schema, &args.table)?;
+
+        let pool = self.pool.clone();
+        let rows = execute_with_timeout(
+            &self.config,
+            self.db_type,
+            "db_metadata_query_timeout_secs",
+            Duration::from_secs(10), // 10s default for metadata
+            || {
+                let pool = pool.clone();
+                let query = query.clone();
+                let params = params.clone();
+                async move {
+                    let mut q = sqlx::query(&query);
+                    for param in &params {
+                        q = q.bind(param);
+                    }
+                    q.fetch_all(&*pool).await
+                }
+            },
+            "Getting table constraints",
+        )
+        .await?;
+
+        let constraints = constraints_from_rows(&rows, db_type, &args.table);
+
+        // Human-readable display
+        let display = format!(
+            "🔒 Constraints on {}.{}\n\n\
+             Found {} constraints:\n\
+             {}",
+            schema,
+            args.table,
+            constraints.len(),
+            constraints
+                .iter()
+                .map(|c| {
+                    let mut line = format!(
+                        "  • {} ({}): {}",
+                        c.constraint_name,
+                        c.constraint_type,
+                        c.column_names.join(", ")
+                    );
+                    if let Some(definition) = &c.definition {
+                        line.push_str(&format!("\n      {}", definition));
+                    }
+                    line
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        let output = GetTableConstraintsOutput {
+            schema: schema.clone(),
+            table: args.table.clone(),
+            constraints: constraints.iter().map(to_constraint_info).collect(),
+            count: constraints.len(),
+        };
+
+        Ok(ToolResponse::new(display, output))
+    }
+}
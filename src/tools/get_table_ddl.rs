@@ -0,0 +1,128 @@
+//! Get table DDL (CREATE TABLE statement) tool
+
+use crate::tools::concurrency::QueryLimiter;
+use crate::ddl::{fetch_table_ddl, validate_ddl_target};
+use crate::error::DatabaseError;
+use crate::tools::helpers::resolve_schema_default;
+use crate::tools::timeout::execute_with_timeout;
+use crate::types::DatabaseType;
+use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{GetTableDDLArgs, GetTableDDLOutput, TableDDLPrompts};
+use kodegen_config_manager::ConfigManager;
+use sqlx::AnyPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tool for generating the CREATE TABLE statement of an existing table
+#[derive(Clone)]
+pub struct GetTableDDLTool {
+    pool: Arc<AnyPool>,
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+    query_limiter: QueryLimiter,
+}
+
+impl GetTableDDLTool {
+    /// Create a new GetTableDDLTool instance
+    pub fn new(
+        pool: Arc<AnyPool>,
+        connection_url: &str,
+        config: Arc<ConfigManager>,
+    ) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
+        Ok(Self {
+            pool,
+            db_type,
+            config,
+            query_limiter,
+        })
+    }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
+}
+
+impl Tool for GetTableDDLTool {
+    type Args = GetTableDDLArgs;
+    type Prompts = TableDDLPrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_TABLE_DDL
+    }
+
+    fn description() -> &'static str {
+        "Get the CREATE TABLE statement for an existing table, including constraints \
+         and indexes. Use this to replicate a table's structure. MySQL and SQLite return \
+         the stored DDL; PostgreSQL DDL is reconstructed from the system catalog."
+    }
+
+    fn read_only() -> bool {
+        true // Only reads metadata
+    }
+
+    fn open_world() -> bool {
+        true // Queries external database
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
+        // Use stored database type
+        let db_type = self.db_type;
+
+        // Resolve schema (use provided or default)
+        let schema = match args.schema {
+            Some(s) => s,
+            None => resolve_schema_default(db_type, &self.pool, &self.config).await?,
+        };
+
+        // SHOW CREATE TABLE and sqlite_master interpolate (quoted) identifiers
+        validate_ddl_target(db_type, &schema, &args.table)?;
+
+        // Execute with timeout
+        let pool = self.pool.clone();
+        let schema_owned = schema.clone();
+        let table_owned = args.table.clone();
+        let ddl = execute_with_timeout(
+            &self.config,
+            self.db_type,
+            "db_metadata_query_timeout_secs",
+            Duration::from_secs(10), // 10s default for metadata
+            || {
+                let pool = pool.clone();
+                let schema = schema_owned.clone();
+                let table = table_owned.clone();
+                async move { fetch_table_ddl(&pool, db_type, &schema, &table).await }
+            },
+            "Getting table DDL",
+        )
+        .await?
+        .ok_or_else(|| DatabaseError::TableNotFound(format!("{}.{}", schema, args.table)))?;
+
+        // Human-readable display
+        let display = format!(
+            "\x1b[36mTable DDL: {}.{}\x1b[0m\n{}",
+            schema, args.table, ddl
+        );
+
+        // Create typed output
+        let output = GetTableDDLOutput {
+            schema: schema.clone(),
+            table: args.table.clone(),
+            ddl,
+        };
+
+        Ok(ToolResponse::new(display, output))
+    }
+}
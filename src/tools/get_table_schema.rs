@@ -1,6 +1,7 @@
 //! Get table schema (column information) tool
 
-use crate::error::DatabaseError;
+use crate::tools::concurrency::QueryLimiter;
+use crate::column_default::classify_default;
 use crate::schema_queries::get_table_schema_query;
 use crate::tools::helpers::resolve_schema_default;
 use crate::tools::timeout::execute_with_timeout;
@@ -10,6 +11,7 @@ use kodegen_mcp_schema::ToolArgs;
 use kodegen_mcp_schema::database::{GetTableSchemaArgs, GetTableSchemaOutput, ColumnInfo, TableSchemaPrompts};
 use kodegen_config_manager::ConfigManager;
 
+use sqlx::any::AnyRow;
 use sqlx::{AnyPool, Row};
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,6 +22,7 @@ pub struct GetTableSchemaTool {
     pool: Arc<AnyPool>,
     db_type: DatabaseType,
     config: Arc<ConfigManager>,
+    query_limiter: QueryLimiter,
 }
 
 impl GetTableSchemaTool {
@@ -31,12 +34,65 @@ impl GetTableSchemaTool {
     ) -> Result<Self, McpError> {
         let db_type = DatabaseType::from_url(connection_url)
             .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
         Ok(Self {
             pool,
             db_type,
             config,
+            query_limiter,
         })
     }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
+}
+
+/// Parse rows of [`get_table_schema_query`] into columns
+///
+/// Reads the information_schema names, falling back to SQLite's
+/// `pragma_table_info` names (`name`, `type`, `notnull`, `dflt_value`).
+pub(crate) fn columns_from_rows(rows: &[AnyRow]) -> Vec<TableColumn> {
+    rows.iter()
+        .map(|row| TableColumn {
+            column_name: row
+                .try_get("column_name")
+                .or_else(|_| row.try_get("name"))
+                .unwrap_or_default(),
+            data_type: row
+                .try_get("data_type")
+                .or_else(|_| row.try_get("type"))
+                .unwrap_or_default(),
+            is_nullable: row
+                .try_get("is_nullable")
+                .or_else(|_| {
+                    // SQLite: notnull field (0 = nullable, 1 = not null)
+                    row.try_get::<i32, _>("notnull")
+                        .map(|v| if v == 0 { "YES" } else { "NO" }.to_string())
+                })
+                .unwrap_or_else(|_| "YES".to_string()),
+            column_default: row
+                .try_get("column_default")
+                .or_else(|_| row.try_get("dflt_value"))
+                .ok(),
+        })
+        .collect()
+}
+
+/// Convert a parsed column to its output form
+pub(crate) fn to_column_info(column: &TableColumn, db_type: DatabaseType) -> ColumnInfo {
+    ColumnInfo {
+        name: column.column_name.clone(),
+        data_type: column.data_type.clone(),
+        nullable: column.is_nullable != "NO",
+        default_value: column.column_default.clone(),
+        default_kind: classify_default(column.column_default.as_deref(), db_type),
+        is_primary_key: false, // TableColumn doesn't track this
+    }
 }
 
 impl Tool for GetTableSchemaTool {
@@ -64,6 +120,9 @@ impl Tool for GetTableSchemaTool {
     async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext) 
         -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError> 
     {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
         // Use stored database type
         let db_type = self.db_type;
 
@@ -82,6 +141,7 @@ impl Tool for GetTableSchemaTool {
         let params_owned = params.clone();
         let rows = execute_with_timeout(
             &self.config,
+            self.db_type,
             "db_metadata_query_timeout_secs",
             Duration::from_secs(10), // 10s default for metadata
             || {
@@ -101,33 +161,7 @@ impl Tool for GetTableSchemaTool {
         .await?;
 
         // Parse into TableColumn structs
-        let columns: Vec<TableColumn> = rows
-            .iter()
-            .map(|row| {
-                Ok(TableColumn {
-                    column_name: row
-                        .try_get("column_name")
-                        .or_else(|_| row.try_get("name"))
-                        .unwrap_or_default(),
-                    data_type: row
-                        .try_get("data_type")
-                        .or_else(|_| row.try_get("type"))
-                        .unwrap_or_default(),
-                    is_nullable: row
-                        .try_get("is_nullable")
-                        .or_else(|_| {
-                            // SQLite: notnull field (0 = nullable, 1 = not null)
-                            row.try_get::<i32, _>("notnull")
-                                .map(|v| if v == 0 { "YES" } else { "NO" }.to_string())
-                        })
-                        .unwrap_or_else(|_| "YES".to_string()),
-                    column_default: row
-                        .try_get("column_default")
-                        .or_else(|_| row.try_get("dflt_value"))
-                        .ok(),
-                })
-            })
-            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        let columns = columns_from_rows(&rows);
 
         // Human-readable display
         let display = format!(
@@ -149,15 +183,8 @@ impl Tool for GetTableSchemaTool {
         );
         
         // Convert TableColumn to ColumnInfo
-        let column_info: Vec<ColumnInfo> = columns.iter()
-            .map(|c| ColumnInfo {
-                name: c.column_name.clone(),
-                data_type: c.data_type.clone(),
-                nullable: c.is_nullable != "NO",
-                default_value: c.column_default.clone(),
-                is_primary_key: false, // TableColumn doesn't track this
-            })
-            .collect();
+        let column_info: Vec<ColumnInfo> =
+            columns.iter().map(|c| to_column_info(c, db_type)).collect();
         
         // Create typed output
         let output = GetTableSchemaOutput {
@@ -4,14 +4,53 @@ use crate::error::DatabaseError;
 use crate::schema_queries::get_default_schema;
 use crate::tools::timeout::execute_with_timeout;
 use crate::types::DatabaseType;
-use kodegen_config_manager::ConfigManager;
+use kodegen_config_manager::{ConfigManager, ConfigValue};
 use sqlx::{AnyPool, Row};
+use std::cmp::Ordering;
 use std::time::Duration;
 
+/// Pick the schema for a tool call: the explicit argument, else `db_default_schema`
+///
+/// `configured` is the raw `db_default_schema` value; a blank string counts as
+/// unset. Returns `None` when the database default should be used.
+pub fn explicit_or_configured_schema(
+    explicit: Option<String>,
+    configured: Option<ConfigValue>,
+) -> Option<String> {
+    explicit.or_else(|| match configured {
+        Some(ConfigValue::String(schema)) if !schema.trim().is_empty() => {
+            Some(schema.trim().to_string())
+        }
+        _ => None,
+    })
+}
+
+/// Whether list tools sort their output in Rust (`db_sort_list_output`, default: true)
+///
+/// Turning it off keeps whatever order the database's `ORDER BY` and collation produce.
+pub fn sort_list_output(config: &ConfigManager) -> bool {
+    !matches!(
+        config.get_value("db_sort_list_output"),
+        Some(ConfigValue::Boolean(false))
+    )
+}
+
+/// Order two identifiers case-insensitively, independent of collation and locale
+///
+/// Compares the Unicode lowercase forms first, then the raw strings, so names
+/// that differ only in case always come out in the same relative order.
+pub fn compare_identifiers(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+        .then_with(|| a.cmp(b))
+}
+
 /// Resolve schema name: use provided value or query for default
 ///
-/// For most databases, uses get_default_schema() from DBTOOL_5.
-/// For MySQL (which has no static default), executes DATABASE() query with timeout protection.
+/// A configured `db_default_schema` wins, so no query is needed. Otherwise most
+/// databases use get_default_schema() from DBTOOL_5, and MySQL (which has no
+/// static default) executes a DATABASE() query with timeout protection.
 ///
 /// # Arguments
 ///
@@ -29,7 +68,7 @@ use std::time::Duration;
 /// ```rust,no_run
 /// use kodegen_tools_database::tools::helpers::resolve_schema_default;
 /// use kodegen_tools_database::types::DatabaseType;
-/// use kodegen_config_manager::ConfigManager;
+/// use kodegen_config_manager::{ConfigManager, ConfigValue};
 /// use sqlx::AnyPool;
 ///
 /// # async fn example(pool: &AnyPool, config: &ConfigManager) -> Result<(), Box<dyn std::error::Error>> {
@@ -43,6 +82,13 @@ pub async fn resolve_schema_default(
     pool: &AnyPool,
     config: &ConfigManager,
 ) -> Result<String, DatabaseError> {
+    // Deployment-wide override, e.g. when the working schema isn't `public`
+    if let Some(schema) =
+        explicit_or_configured_schema(None, config.get_value("db_default_schema"))
+    {
+        return Ok(schema);
+    }
+
     // Check if there's a static default
     if let Some(default) = get_default_schema(db_type) {
         return Ok(default.to_string());
@@ -53,6 +99,7 @@ pub async fn resolve_schema_default(
         let pool_clone = pool.clone();
         let row = execute_with_timeout(
             config,
+            db_type,
             "db_metadata_query_timeout_secs",
             Duration::from_secs(10),
             || {
@@ -85,3 +132,104 @@ pub async fn resolve_schema_default(
         db_type
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_default_schema_used_when_schema_omitted() {
+        let configured = Some(ConfigValue::String(" analytics ".to_string()));
+        assert_eq!(
+            explicit_or_configured_schema(None, configured).as_deref(),
+            Some("analytics")
+        );
+        // Blank or wrongly typed values fall back to the database default
+        assert_eq!(
+            explicit_or_configured_schema(None, Some(ConfigValue::String("  ".to_string()))),
+            None
+        );
+        assert_eq!(explicit_or_configured_schema(None, Some(ConfigValue::Number(1))), None);
+        assert_eq!(explicit_or_configured_schema(None, None), None);
+    }
+
+    #[test]
+    fn test_explicit_schema_ignores_configured_default() {
+        let configured = Some(ConfigValue::String("analytics".to_string()));
+        assert_eq!(
+            explicit_or_configured_schema(Some("hr".to_string()), configured).as_deref(),
+            Some("hr")
+        );
+    }
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        names.sort_by(|a, b| compare_identifiers(a, b));
+        names
+    }
+
+    #[test]
+    fn test_mixed_case_names_sort_case_insensitively() {
+        assert_eq!(
+            sorted(&["users", "Zeta", "orders", "Users_Archive", "apple", "Accounts"]),
+            ["Accounts", "apple", "orders", "users", "Users_Archive", "Zeta"]
+        );
+        // Names equal ignoring case still get a fixed order
+        assert_eq!(sorted(&["item", "Item", "ITEM"]), ["ITEM", "Item", "item"]);
+    }
+
+    #[test]
+    fn test_sort_ignores_dialect_collation_order() {
+        // Binary collation (SQLite, PostgreSQL "C") puts uppercase first;
+        // MySQL's *_ci collations interleave cases
+        let binary = ["Accounts", "Zeta", "apple", "orders", "users"];
+        let case_insensitive = ["Accounts", "apple", "orders", "users", "Zeta"];
+        assert_eq!(sorted(&binary), sorted(&case_insensitive));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_tables_sort_case_insensitively() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        for table in ["zebra", "Apple", "mango", "Banana"] {
+            sqlx::query(&format!("CREATE TABLE {} (id INTEGER)", table))
+                .execute(&pool)
+                .await
+                .expect("create table");
+        }
+
+        let (sql, params) =
+            crate::schema_queries::get_tables_query(DatabaseType::SQLite, None, None, false);
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        let mut names: Vec<String> = query
+            .fetch_all(&pool)
+            .await
+            .expect("list tables")
+            .iter()
+            .map(|row| row.get("table_name"))
+            .collect();
+        names.sort_by(|a, b| compare_identifiers(a, b));
+        assert_eq!(names, ["Apple", "Banana", "mango", "zebra"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_schema_default_without_override() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        let schema = resolve_schema_default(DatabaseType::SQLite, &pool, &ConfigManager::new())
+            .await
+            .expect("resolve schema");
+        assert_eq!(schema, "main");
+    }
+}
@@ -0,0 +1,208 @@
+//! Kill query tool - Cancels or terminates a statement running on the server
+
+use crate::connection::OwnSessions;
+use crate::error::DatabaseError;
+use crate::schema_queries::{get_current_process_id_query, get_kill_query_sql};
+use crate::types::DatabaseType;
+use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{KillQueryArgs, KillQueryOutput, KillQueryPrompts};
+use kodegen_config_manager::ConfigManager;
+use sqlx::{AnyPool, Row};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tool for cancelling a runaway statement by the process id shown in db_list_active_queries
+#[derive(Clone)]
+pub struct KillQueryTool {
+    pool: Arc<AnyPool>,
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+    own_sessions: OwnSessions,
+}
+
+impl KillQueryTool {
+    /// Create a new KillQueryTool instance
+    pub fn new(
+        pool: Arc<AnyPool>,
+        connection_url: &str,
+        config: Arc<ConfigManager>,
+    ) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        Ok(Self {
+            pool,
+            db_type,
+            config,
+            own_sessions: OwnSessions::default(),
+        })
+    }
+
+    /// Refuse to kill the sessions of these pools (`DatabaseConnection::own_sessions`)
+    ///
+    /// Without it only the connection issuing the kill is protected.
+    pub fn with_own_sessions(mut self, own_sessions: OwnSessions) -> Self {
+        self.own_sessions = own_sessions;
+        self
+    }
+
+    /// Whether `db_allow_kill_query` is enabled (default: false)
+    fn kill_allowed(&self) -> bool {
+        self.config
+            .get_value("db_allow_kill_query")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Boolean(b) => Some(b),
+                _ => None,
+            })
+            .unwrap_or(false)
+    }
+
+    /// Issue the kill from a single connection whose own id has been checked
+    async fn kill(&self, process_id: i64, terminate: bool) -> Result<bool, DatabaseError> {
+        let kill_sql = get_kill_query_sql(self.db_type, process_id, terminate)?;
+        let own_id_sql = get_current_process_id_query(self.db_type).ok_or_else(|| {
+            DatabaseError::FeatureNotSupported(format!(
+                "Cannot determine connection id for {}",
+                self.db_type
+            ))
+        })?;
+
+        let mut conn = self.pool.acquire().await?;
+
+        let own_id: i64 = sqlx::query(own_id_sql)
+            .fetch_one(&mut *conn)
+            .await?
+            .try_get("process_id")
+            .map_err(|e| DatabaseError::QueryError(format!("Failed to read process_id: {}", e)))?;
+        ensure_not_own_connection(process_id, own_id)?;
+        ensure_not_pooled_session(process_id, &self.own_sessions)?;
+
+        // pg_cancel_backend/pg_terminate_backend report whether the signal was sent;
+        // KILL errors out when the id does not exist
+        let rows = sqlx::query(&kill_sql).fetch_all(&mut *conn).await?;
+        match rows.first() {
+            Some(row) => Ok(row.try_get::<bool, _>("success").unwrap_or(false)),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Refuse to kill the connection the tool itself is running on
+fn ensure_not_own_connection(target: i64, own: i64) -> Result<(), DatabaseError> {
+    if target == own {
+        return Err(DatabaseError::QueryError(format!(
+            "Refusing to kill process {}: it is the connection issuing the kill",
+            target
+        )));
+    }
+    Ok(())
+}
+
+/// Refuse to kill a session of this server's own pools
+///
+/// Killing one would abort a statement some other tool call is running, or
+/// break a pooled connection under it.
+fn ensure_not_pooled_session(target: i64, own_sessions: &OwnSessions) -> Result<(), DatabaseError> {
+    if own_sessions.contains(target) {
+        return Err(DatabaseError::QueryError(format!(
+            "Refusing to kill process {}: it is one of this server's own pooled connections",
+            target
+        )));
+    }
+    Ok(())
+}
+
+impl Tool for KillQueryTool {
+    type Args = KillQueryArgs;
+    type Prompts = KillQueryPrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_KILL_QUERY
+    }
+
+    fn description() -> &'static str {
+        "Cancel a statement running on the database server by its process id \
+         (as reported by db_list_active_queries). By default only the running statement \
+         is cancelled; set terminate=true to close the whole session. \
+         Disabled unless the db_allow_kill_query setting is true. \
+         Refuses to kill any connection of this server's own pools. Not supported for SQLite."
+    }
+
+    fn destructive() -> bool {
+        true // Aborts another session's work
+    }
+
+    fn open_world() -> bool {
+        true // Acts on external database
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        if !self.kill_allowed() {
+            return Err(McpError::InvalidArguments(
+                "db_kill_query is disabled; set db_allow_kill_query to true to enable it"
+                    .to_string(),
+            ));
+        }
+
+        let timeout_duration = self
+            .config
+            .get_value("db_metadata_query_timeout_secs")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(Duration::from_secs(n as u64)),
+                _ => None,
+            })
+            .unwrap_or(Duration::from_secs(10));
+
+        // Not retried: a kill is not worth repeating on a fresh connection
+        let success = tokio::time::timeout(timeout_duration, self.kill(args.process_id, args.terminate))
+            .await
+            .map_err(|_| {
+                McpError::Other(anyhow::anyhow!(
+                    "Killing process {} timed out after {}s",
+                    args.process_id,
+                    timeout_duration.as_secs()
+                ))
+            })??;
+
+        let action = if args.terminate { "terminate" } else { "cancel" };
+        let display = format!(
+            "\x1b[36mKill Query\x1b[0m\n ℹ Process: {} · Action: {} · Sent: {}",
+            args.process_id,
+            action,
+            if success { "yes" } else { "no" }
+        );
+
+        let output = KillQueryOutput {
+            process_id: args.process_id,
+            action: action.to_string(),
+            success,
+        };
+
+        Ok(ToolResponse::new(display, output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuses_own_connection() {
+        let err = ensure_not_own_connection(42, 42).unwrap_err();
+        assert!(err.to_string().contains("Refusing to kill process 42"));
+        assert!(ensure_not_own_connection(41, 42).is_ok());
+    }
+
+    #[test]
+    fn test_refuses_pooled_sessions() {
+        let own_sessions = OwnSessions::default();
+        own_sessions.record(7);
+        own_sessions.record(9);
+        let err = ensure_not_pooled_session(9, &own_sessions).unwrap_err();
+        assert!(err.to_string().contains("this server's own pooled connections"));
+        assert!(ensure_not_pooled_session(8, &own_sessions).is_ok());
+        assert!(ensure_not_pooled_session(9, &OwnSessions::default()).is_ok());
+    }
+}
@@ -8,6 +8,8 @@ use sqlx::{AnyPool, Row};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::tools::concurrency::QueryLimiter;
+use crate::tools::helpers::{compare_identifiers, sort_list_output};
 use crate::tools::timeout::execute_with_timeout;
 use crate::types::DatabaseType;
 
@@ -20,6 +22,7 @@ pub struct ListSchemasTool {
     pool: Arc<AnyPool>,
     db_type: DatabaseType,
     config: ConfigManager,
+    query_limiter: QueryLimiter,
 }
 
 impl ListSchemasTool {
@@ -34,12 +37,22 @@ impl ListSchemasTool {
     ) -> Result<Self, McpError> {
         let db_type = DatabaseType::from_url(connection_url)
             .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
         Ok(Self {
             pool,
             db_type,
             config,
+            query_limiter,
         })
     }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
 }
 
 // =============================================================================
@@ -58,7 +71,7 @@ impl Tool for ListSchemasTool {
         "List all schemas (databases) in the current database connection. \
          For PostgreSQL, returns all user schemas (excludes pg_catalog, information_schema). \
          For MySQL/MariaDB, returns all databases you have access to. \
-         For SQLite, returns ['main']. \
+         For SQLite, returns 'main' plus any ATTACHed databases. \
          Returns JSON with schemas array and count."
     }
 
@@ -73,30 +86,12 @@ impl Tool for ListSchemasTool {
     async fn execute(&self, _args: Self::Args, _ctx: ToolExecutionContext) 
         -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError> 
     {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
         // Use stored database type
         let db_type = self.db_type;
 
-        // SQLite special case - no query needed
-        if matches!(db_type, DatabaseType::SQLite) {
-            let schemas = vec!["main".to_string()];
-            let count = schemas.len();
-            
-            // Human-readable summary
-            let display = format!(
-                "🗄️  Available Schemas\n\n\
-                 Found {} schema:\n\
-                 {}",
-                count,
-                schemas.iter()
-                    .map(|s| format!("  • {}", s))
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            );
-            
-            let output = ListSchemasOutput { schemas, count };
-            return Ok(ToolResponse::new(display, output));
-        }
-
         // Get SQL query from centralized schema_queries module
         let sql = crate::schema_queries::get_schemas_query(db_type);
 
@@ -105,6 +100,7 @@ impl Tool for ListSchemasTool {
         let sql_owned = sql.to_string();
         let rows = execute_with_timeout(
             &self.config,
+            self.db_type,
             "db_metadata_query_timeout_secs",
             Duration::from_secs(10), // 10s default for metadata
             || {
@@ -117,10 +113,14 @@ impl Tool for ListSchemasTool {
         .await?;
 
         // Extract schema names
-        let schemas: Vec<String> = rows
+        let mut schemas: Vec<String> = rows
             .iter()
             .filter_map(|row| row.try_get("schema_name").ok())
             .collect();
+        // Same order on every dialect, whatever the server collation
+        if sort_list_output(&self.config) {
+            schemas.sort_by(|a, b| compare_identifiers(a, b));
+        }
 
         let count = schemas.len();
         
@@ -8,6 +8,8 @@ use sqlx::{AnyPool, Row};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::tools::concurrency::QueryLimiter;
+use crate::tools::helpers::{compare_identifiers, explicit_or_configured_schema, sort_list_output};
 use crate::tools::timeout::execute_with_timeout;
 use crate::types::DatabaseType;
 
@@ -20,6 +22,7 @@ pub struct ListTablesTool {
     pool: Arc<AnyPool>,
     db_type: DatabaseType,
     config: ConfigManager,
+    query_limiter: QueryLimiter,
 }
 
 impl ListTablesTool {
@@ -34,12 +37,22 @@ impl ListTablesTool {
     ) -> Result<Self, McpError> {
         let db_type = DatabaseType::from_url(connection_url)
             .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
         Ok(Self {
             pool,
             db_type,
             config,
+            query_limiter,
         })
     }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
 }
 
 // =============================================================================
@@ -57,6 +70,8 @@ impl Tool for ListTablesTool {
     fn description() -> &'static str {
         "List all tables in a schema. If schema not provided, uses default schema \
          (public for PostgreSQL, current database for MySQL, main for SQLite, dbo for SQL Server). \
+         Optionally filter by a case-insensitive SQL LIKE pattern (name_pattern, e.g. 'user%') \
+         and include views (include_views). \
          Returns JSON with tables array, schema name, and count."
     }
 
@@ -71,15 +86,28 @@ impl Tool for ListTablesTool {
     async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext) 
         -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError> 
     {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
         // Use stored database type
         let db_type = self.db_type;
 
+        // Explicit schema, else db_default_schema, else the database default
+        let schema = explicit_or_configured_schema(
+            args.schema,
+            self.config.get_value("db_default_schema"),
+        );
+
         // Get SQL query from centralized schema_queries module
-        let (sql, params) =
-            crate::schema_queries::get_tables_query(db_type, args.schema.as_deref());
+        let (sql, params) = crate::schema_queries::get_tables_query(
+            db_type,
+            schema.as_deref(),
+            args.name_pattern.as_deref(),
+            args.include_views,
+        );
 
         // Determine resolved schema for response
-        let resolved_schema = args.schema.unwrap_or_else(|| {
+        let resolved_schema = schema.unwrap_or_else(|| {
             crate::schema_queries::get_default_schema(db_type)
                 .unwrap_or("main")
                 .to_string()
@@ -91,6 +119,7 @@ impl Tool for ListTablesTool {
         let params_owned = params.clone();
         let rows = execute_with_timeout(
             &self.config,
+            self.db_type,
             "db_metadata_query_timeout_secs",
             Duration::from_secs(10), // 10s default for metadata
             || {
@@ -109,11 +138,18 @@ impl Tool for ListTablesTool {
         )
         .await?;
 
-        // Extract table names
-        let tables: Vec<String> = rows
+        // Extract table names and types
+        let mut tables: Vec<(String, Option<String>)> = rows
             .iter()
-            .filter_map(|row| row.try_get("table_name").ok())
+            .filter_map(|row| {
+                let name: String = row.try_get("table_name").ok()?;
+                Some((name, row.try_get("table_type").ok()))
+            })
             .collect();
+        // Same order on every dialect, whatever the server collation
+        if sort_list_output(&self.config) {
+            tables.sort_by(|(a, _), (b, _)| compare_identifiers(a, b));
+        }
 
         // Human-readable display
         let display = format!(
@@ -125,9 +161,9 @@ impl Tool for ListTablesTool {
         
         // Convert Vec<String> to Vec<TableInfo>
         let table_info: Vec<TableInfo> = tables.iter()
-            .map(|name| TableInfo {
+            .map(|(name, table_type)| TableInfo {
                 name: name.clone(),
-                table_type: None,
+                table_type: table_type.clone(),
             })
             .collect();
         
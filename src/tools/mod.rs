@@ -6,9 +6,12 @@ pub use helpers::*;
 
 pub mod timeout;
 
+pub mod concurrency;
+pub use concurrency::QueryLimiter;
+
 // DBTOOL_6 - ExecuteSQL - SQL query execution tool
 pub mod execute_sql;
-pub use execute_sql::ExecuteSQLTool;
+pub use execute_sql::{ExecuteSQLTool, QueryAllowlist, QueryObserver, TypeDecode, TypeMapper};
 
 // DBTOOL_7 - List schemas and tables
 pub mod list_schemas;
@@ -24,8 +27,37 @@ pub use get_table_schema::*;
 pub mod get_table_indexes;
 pub use get_table_indexes::*;
 
+pub mod get_table_constraints;
+pub use get_table_constraints::GetTableConstraintsTool;
+
 pub mod get_stored_procedures;
 pub use get_stored_procedures::*;
 
+pub mod get_table_ddl;
+pub use get_table_ddl::GetTableDDLTool;
+
 pub mod get_pool_stats;
 pub use get_pool_stats::GetPoolStatsTool;
+
+pub mod list_active_queries;
+pub use list_active_queries::ListActiveQueriesTool;
+pub mod kill_query;
+pub use kill_query::KillQueryTool;
+
+pub mod search_schema;
+pub use search_schema::SearchSchemaTool;
+
+pub mod describe_connection;
+pub use describe_connection::DescribeConnectionTool;
+
+pub mod schema_overview;
+pub use schema_overview::SchemaOverviewTool;
+
+pub mod preview_write;
+pub use preview_write::PreviewWriteTool;
+
+pub mod test_connection;
+pub use test_connection::TestConnectionTool;
+
+pub mod get_introspection_sql;
+pub use get_introspection_sql::{GetIntrospectionSQLTool, IntrospectionOperation, introspection_sql};
@@ -0,0 +1,182 @@
+//! Preview write tool - Shows the rows an UPDATE/DELETE would affect without running it
+
+use crate::tools::concurrency::QueryLimiter;
+use crate::preview::preview_write;
+use crate::tools::execute_sql::row_to_typed;
+use crate::tools::timeout::execute_with_timeout;
+use crate::types::DatabaseType;
+use crate::{apply_row_limit, apply_table_prefix, normalize_sql_input, validate_readonly_sql};
+use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{PreviewWriteArgs, PreviewWriteOutput, PreviewWritePrompts};
+use kodegen_config_manager::ConfigManager;
+use sqlx::{AnyPool, Row};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Rows returned when neither the call nor `max_rows` sets a limit
+const DEFAULT_PREVIEW_ROWS: usize = 100;
+
+/// Tool for previewing the rows an UPDATE or DELETE statement would touch
+#[derive(Clone)]
+pub struct PreviewWriteTool {
+    pool: Arc<AnyPool>,
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+    query_limiter: QueryLimiter,
+}
+
+impl PreviewWriteTool {
+    /// Create a new PreviewWriteTool instance
+    pub fn new(
+        pool: Arc<AnyPool>,
+        connection_url: &str,
+        config: Arc<ConfigManager>,
+    ) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
+        Ok(Self {
+            pool,
+            db_type,
+            config,
+            query_limiter,
+        })
+    }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
+
+    /// Rows to return: the per-call limit, capped by the server's `max_rows`
+    fn row_limit(&self, requested: Option<usize>) -> usize {
+        let server_max_rows = self.config.get_value("max_rows").and_then(|v| match v {
+            kodegen_config_manager::ConfigValue::Number(n) => Some(n as usize),
+            _ => None,
+        });
+        match (requested, server_max_rows) {
+            (Some(call), Some(server)) => call.min(server),
+            (call, server) => call.or(server).unwrap_or(DEFAULT_PREVIEW_ROWS),
+        }
+    }
+
+    /// Run a preview query with the usual timeout and retry handling
+    async fn fetch(&self, sql: &str, desc: &str) -> Result<Vec<sqlx::any::AnyRow>, McpError> {
+        let pool = self.pool.clone();
+        let sql = sql.to_string();
+        execute_with_timeout(
+            &self.config,
+            self.db_type,
+            "db_query_timeout_secs",
+            Duration::from_secs(60),
+            || {
+                let pool = pool.clone();
+                let sql = sql.clone();
+                async move { sqlx::query(&sql).fetch_all(&*pool).await }
+            },
+            desc,
+        )
+        .await
+    }
+}
+
+impl Tool for PreviewWriteTool {
+    type Args = PreviewWriteArgs;
+    type Prompts = PreviewWritePrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_PREVIEW_WRITE
+    }
+
+    fn description() -> &'static str {
+        "Preview the rows an UPDATE or DELETE statement would affect, without running it. \
+         The statement is rewritten into SELECT ... FROM <target> WHERE <same predicate>, \
+         with any joined tables moved into a WHERE EXISTS subquery so each target row counts \
+         once; returns the matching rows (up to max_rows) and the total number that would be \
+         affected. Outer joins are not supported. Safe in read-only mode."
+    }
+
+    fn read_only() -> bool {
+        true // The write is never executed
+    }
+
+    fn open_world() -> bool {
+        true // Queries external database
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
+        // Same tenant prefix rewrite as db_execute_sql, so the preview hits the same tables
+        let table_prefix = self
+            .config
+            .get_value("db_table_prefix")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::String(s) if !s.is_empty() => Some(s),
+                _ => None,
+            });
+        let input = normalize_sql_input(&args.sql);
+        let sql = match table_prefix {
+            Some(prefix) => apply_table_prefix(input, &prefix, self.db_type)
+                .map_err(|e| anyhow::anyhow!("Table prefix rewrite failed: {}", e))?,
+            None => input.to_string(),
+        };
+
+        let preview = preview_write(&sql, self.db_type)
+            .map_err(|e| McpError::InvalidArguments(e.to_string()))?;
+
+        // The predicate may call functions; hold it to the same read-only rules as any read
+        validate_readonly_sql(&preview.select_sql, self.db_type)
+            .map_err(|e| anyhow::anyhow!("Read-only violation: {}", e))?;
+
+        let max_rows = self.row_limit(args.max_rows);
+        let select_sql = apply_row_limit(&preview.select_sql, max_rows, self.db_type)
+            .map_err(|e| anyhow::anyhow!("Row limit failed: {}", e))?;
+
+        let rows = self.fetch(&select_sql, "Previewing affected rows").await?;
+        let count_rows = self.fetch(&preview.count_sql, "Counting affected rows").await?;
+        let affected_rows = count_rows
+            .first()
+            .and_then(|row| row.try_get::<i64, _>("affected_rows").ok())
+            .unwrap_or_default()
+            .max(0) as u64;
+
+        let typed_rows = rows
+            .iter()
+            .map(row_to_typed)
+            .collect::<Result<Vec<_>, _>>()?;
+        let columns = typed_rows
+            .first()
+            .map(|row| row.columns.iter().map(|c| c.name.clone()).collect())
+            .unwrap_or_default();
+
+        // Human-readable display
+        let display = format!(
+            "\x1b[36mPreview {}: {}\x1b[0m\n ℹ Rows affected: {} · Shown: {}",
+            preview.statement_type,
+            preview.table,
+            affected_rows,
+            typed_rows.len()
+        );
+
+        let output = PreviewWriteOutput {
+            statement_type: preview.statement_type.to_string(),
+            table: preview.table,
+            preview_sql: preview.select_sql,
+            columns,
+            row_count: typed_rows.len(),
+            rows: typed_rows,
+            affected_rows,
+            truncated: affected_rows > max_rows as u64,
+        };
+
+        Ok(ToolResponse::new(display, output))
+    }
+}
@@ -0,0 +1,275 @@
+//! Schema overview tool - Columns and indexes for every table in one call
+
+use crate::tools::concurrency::QueryLimiter;
+use crate::schema_queries::{get_indexes_query, get_table_schema_query, get_tables_query};
+use crate::tools::get_table_indexes::{indexes_from_rows, to_index_info};
+use crate::tools::get_table_schema::{columns_from_rows, to_column_info};
+use crate::tools::helpers::resolve_schema_default;
+use crate::tools::timeout::execute_with_timeout;
+use crate::types::DatabaseType;
+use futures::StreamExt;
+use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{
+    SchemaOverviewArgs, SchemaOverviewOutput, SchemaOverviewPrompts, TableOverview,
+};
+use kodegen_config_manager::ConfigManager;
+use sqlx::any::AnyRow;
+use sqlx::{AnyPool, Row};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default number of tables introspected at once (`db_overview_concurrency`)
+const DEFAULT_OVERVIEW_CONCURRENCY: usize = 4;
+
+/// Tool for retrieving columns and indexes of all tables in a schema
+#[derive(Clone)]
+pub struct SchemaOverviewTool {
+    pool: Arc<AnyPool>,
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+    query_limiter: QueryLimiter,
+}
+
+impl SchemaOverviewTool {
+    /// Create a new SchemaOverviewTool instance
+    pub fn new(
+        pool: Arc<AnyPool>,
+        connection_url: &str,
+        config: Arc<ConfigManager>,
+    ) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
+        Ok(Self {
+            pool,
+            db_type,
+            config,
+            query_limiter,
+        })
+    }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
+
+    /// Read `db_overview_concurrency`, never below 1
+    fn concurrency(&self) -> usize {
+        self.config
+            .get_value("db_overview_concurrency")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(n.max(1) as usize),
+                _ => None,
+            })
+            .unwrap_or(DEFAULT_OVERVIEW_CONCURRENCY)
+    }
+
+    /// Run a metadata query with the usual timeout and retry handling
+    async fn fetch(
+        &self,
+        sql: String,
+        params: Vec<String>,
+        desc: &str,
+    ) -> Result<Vec<AnyRow>, McpError> {
+        let pool = self.pool.clone();
+        execute_with_timeout(
+            &self.config,
+            self.db_type,
+            "db_metadata_query_timeout_secs",
+            Duration::from_secs(10), // 10s default for metadata
+            || {
+                let pool = pool.clone();
+                let sql = sql.clone();
+                let params = params.clone();
+                async move {
+                    let mut query = sqlx::query(&sql);
+                    for param in &params {
+                        query = query.bind(param);
+                    }
+                    query.fetch_all(&*pool).await
+                }
+            },
+            desc,
+        )
+        .await
+    }
+
+    /// Columns and indexes of one table
+    async fn table_overview(&self, schema: &str, table: &str) -> Result<TableOverview, McpError> {
+        let (sql, params) = get_table_schema_query(self.db_type, schema, table)?;
+        let column_rows = self.fetch(sql, params, "Getting table schema").await?;
+        let (sql, params) = get_indexes_query(self.db_type, schema, table)?;
+        let index_rows = self.fetch(sql, params, "Getting table indexes").await?;
+
+        Ok(TableOverview {
+            columns: columns_from_rows(&column_rows)
+                .iter()
+                .map(|column| to_column_info(column, self.db_type))
+                .collect(),
+            indexes: indexes_from_rows(&index_rows, self.db_type)
+                .iter()
+                .map(to_index_info)
+                .collect(),
+        })
+    }
+
+    /// Introspect every base table in `schema`, `concurrency` tables at a time
+    async fn overview(
+        &self,
+        schema: &str,
+        concurrency: usize,
+    ) -> Result<BTreeMap<String, TableOverview>, McpError> {
+        let (sql, params) = get_tables_query(self.db_type, Some(schema), None, false);
+        let rows = self.fetch(sql, params, "Listing tables").await?;
+        let tables: Vec<String> = rows
+            .iter()
+            .filter_map(|row| row.try_get("table_name").ok())
+            .collect();
+
+        let lookups = tables.into_iter().map(|table| async move {
+            let overview = self.table_overview(schema, &table).await?;
+            Ok::<_, McpError>((table, overview))
+        });
+        run_bounded(lookups, concurrency).await.into_iter().collect()
+    }
+}
+
+/// Run futures with at most `limit` in flight, collecting results in completion order
+async fn run_bounded<F, T>(futures: impl IntoIterator<Item = F>, limit: usize) -> Vec<T>
+where
+    F: Future<Output = T>,
+{
+    futures::stream::iter(futures)
+        .buffer_unordered(limit.max(1))
+        .collect()
+        .await
+}
+
+impl Tool for SchemaOverviewTool {
+    type Args = SchemaOverviewArgs;
+    type Prompts = SchemaOverviewPrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_SCHEMA_OVERVIEW
+    }
+
+    fn description() -> &'static str {
+        "Get columns and indexes for every table in a schema in one call, instead of one \
+         db_table_schema/db_table_indexes call per table. Tables are introspected in \
+         parallel (db_overview_concurrency at a time, default 4). Returns a map of \
+         table name to {columns, indexes}. Views are not included."
+    }
+
+    fn read_only() -> bool {
+        true // Only reads metadata
+    }
+
+    fn open_world() -> bool {
+        true // Queries external database
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
+        // Resolve schema (use provided or default)
+        let schema = match args.schema {
+            Some(s) => s,
+            None => resolve_schema_default(self.db_type, &self.pool, &self.config).await?,
+        };
+
+        let tables = self.overview(&schema, self.concurrency()).await?;
+
+        // Human-readable display
+        let column_count: usize = tables.values().map(|table| table.columns.len()).sum();
+        let display = format!(
+            "\x1b[36mSchema Overview: {}\x1b[0m\n ℹ Tables: {} · Columns: {}",
+            schema,
+            tables.len(),
+            column_count
+        );
+
+        let output = SchemaOverviewOutput {
+            schema,
+            table_count: tables.len(),
+            tables,
+        };
+
+        Ok(ToolResponse::new(display, output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_overview_covers_all_tables() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        for stmt in [
+            "CREATE TABLE departments (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+            "CREATE TABLE employees (id INTEGER PRIMARY KEY, email TEXT, department_id INTEGER)",
+            "CREATE UNIQUE INDEX idx_employees_email ON employees (email)",
+            "CREATE TABLE projects (id INTEGER PRIMARY KEY, title TEXT)",
+            "CREATE VIEW employee_emails AS SELECT email FROM employees",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.expect("fixture");
+        }
+        let tool = SchemaOverviewTool::new(
+            Arc::new(pool),
+            "sqlite::memory:",
+            Arc::new(ConfigManager::new()),
+        )
+        .expect("tool");
+
+        let tables = tool.overview("main", 2).await.expect("overview");
+        assert_eq!(
+            tables.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["departments", "employees", "projects"]
+        );
+        let employees = &tables["employees"];
+        assert_eq!(
+            employees.columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            vec!["id", "email", "department_id"]
+        );
+        // SQLite lists indexes via PRAGMA index_list: one row per index
+        assert_eq!(employees.indexes.len(), 1);
+        assert!(tables["projects"].indexes.is_empty());
+        assert!(!tables["departments"].columns[1].nullable);
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_limits_concurrency() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let tasks = (0..10).map(|i| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i
+            }
+        });
+
+        let mut results = run_bounded(tasks, 3).await;
+        results.sort();
+        assert_eq!(results, (0..10).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+}
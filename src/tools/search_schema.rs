@@ -0,0 +1,145 @@
+//! Search schema tool - Finds tables and columns by partial name across the database
+
+use crate::tools::concurrency::QueryLimiter;
+use crate::error::DatabaseError;
+use crate::schema_queries::get_search_schema_query;
+use crate::tools::timeout::execute_with_timeout;
+use crate::types::DatabaseType;
+use kodegen_mcp_schema::{Tool, ToolExecutionContext, ToolResponse, McpError};
+use kodegen_mcp_schema::ToolArgs;
+use kodegen_mcp_schema::database::{SchemaMatch, SearchSchemaArgs, SearchSchemaOutput, SearchSchemaPrompts};
+use kodegen_config_manager::ConfigManager;
+use sqlx::{AnyPool, Row};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tool for locating a table or column without listing every table first
+#[derive(Clone)]
+pub struct SearchSchemaTool {
+    pool: Arc<AnyPool>,
+    db_type: DatabaseType,
+    config: Arc<ConfigManager>,
+    query_limiter: QueryLimiter,
+}
+
+impl SearchSchemaTool {
+    /// Create a new SearchSchemaTool instance
+    pub fn new(
+        pool: Arc<AnyPool>,
+        connection_url: &str,
+        config: Arc<ConfigManager>,
+    ) -> Result<Self, McpError> {
+        let db_type = DatabaseType::from_url(connection_url)
+            .map_err(|e| McpError::Other(anyhow::anyhow!("Invalid database URL: {}", e)))?;
+        let query_limiter = QueryLimiter::from_config(&config);
+        Ok(Self {
+            pool,
+            db_type,
+            config,
+            query_limiter,
+        })
+    }
+
+    /// Use a shared query limiter (e.g. `DatabaseConnection::query_limiter`)
+    ///
+    /// By default each tool sizes its own limiter from config.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Self {
+        self.query_limiter = query_limiter;
+        self
+    }
+}
+
+impl Tool for SearchSchemaTool {
+    type Args = SearchSchemaArgs;
+    type Prompts = SearchSchemaPrompts;
+
+    fn name() -> &'static str {
+        kodegen_mcp_schema::database::DB_SEARCH_SCHEMA
+    }
+
+    fn description() -> &'static str {
+        "Search all tables for table or column names containing a substring \
+         (case-insensitive, matched literally). Columns are returned when either the column \
+         name or its table name matches. Covers every user schema (the current database \
+         for MySQL). Returns JSON with matches as {schema, table, column} and count."
+    }
+
+    fn read_only() -> bool {
+        true
+    }
+
+    fn open_world() -> bool {
+        false
+    }
+
+    async fn execute(&self, args: Self::Args, _ctx: ToolExecutionContext)
+        -> Result<ToolResponse<<Self::Args as ToolArgs>::Output>, McpError>
+    {
+        // Counts against the same in-flight limit as db_execute_sql
+        let _permit = self.query_limiter.try_acquire()?;
+
+        let (sql, params) = get_search_schema_query(self.db_type, &args.term)
+            .map_err(|e| McpError::InvalidArguments(e.to_string()))?;
+
+        let pool = self.pool.clone();
+        let rows = execute_with_timeout(
+            &self.config,
+            self.db_type,
+            "db_metadata_query_timeout_secs",
+            Duration::from_secs(10), // 10s default for metadata
+            || {
+                let pool = pool.clone();
+                let sql = sql.clone();
+                let params = params.clone();
+                async move {
+                    let mut query = sqlx::query(&sql);
+                    for param in &params {
+                        query = query.bind(param);
+                    }
+                    query.fetch_all(&*pool).await
+                }
+            },
+            "Searching schema",
+        )
+        .await?;
+
+        let matches = rows
+            .iter()
+            .map(|row| {
+                let get = |column: &str| {
+                    row.try_get::<String, _>(column).map_err(|e| {
+                        DatabaseError::QueryError(format!("Failed to read {}: {}", column, e))
+                    })
+                };
+                Ok(SchemaMatch {
+                    schema: get("schema_name")?,
+                    table: get("table_name")?,
+                    column: get("column_name")?,
+                })
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+
+        let table_count = {
+            let mut tables: Vec<(&str, &str)> =
+                matches.iter().map(|m| (m.schema.as_str(), m.table.as_str())).collect();
+            tables.dedup();
+            tables.len()
+        };
+
+        // Human-readable display
+        let display = format!(
+            "\x1b[36mSchema Search: {}\x1b[0m\n ℹ Columns: {} · Tables: {}",
+            args.term.trim(),
+            matches.len(),
+            table_count
+        );
+
+        let output = SearchSchemaOutput {
+            term: args.term.trim().to_string(),
+            count: matches.len(),
+            matches,
+        };
+
+        Ok(ToolResponse::new(display, output))
+    }
+}
@@ -1,11 +1,60 @@
 //! Query timeout utilities for database operations
 
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, with_type_hint};
+use crate::types::DatabaseType;
 use kodegen_mcp_schema::McpError;
 use kodegen_config_manager::ConfigManager;
+use sqlx::pool::PoolConnection;
+use sqlx::{Any, AnyPool};
 use std::time::Duration;
 use tokio::time::timeout;
 
+/// How random jitter is mixed into the exponential retry backoff
+///
+/// Selected by the `db_retry_jitter` config key (`"full"`, `"equal"`, or `"fixed"`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RetryJitter {
+    /// Random delay between 0 and the computed backoff
+    Full,
+    /// Half the computed backoff, plus a random delay up to the other half
+    #[default]
+    Equal,
+    /// The computed backoff plus 0-100ms
+    Fixed,
+}
+
+impl RetryJitter {
+    /// Read `db_retry_jitter`, falling back to [`RetryJitter::Equal`]
+    pub fn from_config(config: &ConfigManager) -> Self {
+        match config.get_value("db_retry_jitter") {
+            Some(kodegen_config_manager::ConfigValue::String(mode)) => {
+                match mode.to_ascii_lowercase().as_str() {
+                    "full" => Self::Full,
+                    "equal" => Self::Equal,
+                    "fixed" => Self::Fixed,
+                    other => {
+                        log::warn!("Unknown db_retry_jitter '{}', using 'equal'", other);
+                        Self::Equal
+                    }
+                }
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Apply the jitter to a computed backoff of `backoff_ms` milliseconds
+    fn apply(self, backoff_ms: u64) -> u64 {
+        match self {
+            Self::Full => rand::random::<u64>() % (backoff_ms + 1),
+            Self::Equal => {
+                let half = backoff_ms / 2;
+                half + rand::random::<u64>() % (backoff_ms - half + 1)
+            }
+            Self::Fixed => backoff_ms + rand::random::<u64>() % 100,
+        }
+    }
+}
+
 /// Calculate retry backoff duration with exponential growth, cap, and jitter
 ///
 /// Uses configurable base backoff and maximum cap from config, with random jitter
@@ -24,20 +73,24 @@ use tokio::time::timeout;
 ///
 /// * `db_retry_backoff_ms` - Base backoff in milliseconds (default: 500)
 /// * `db_max_backoff_ms` - Maximum backoff cap in milliseconds (default: 5000)
+/// * `db_retry_jitter` - Jitter strategy, see [`RetryJitter`] (default: equal)
 ///
 /// # Formula
 ///
-/// `backoff = min(base_ms * 2^attempt, max_ms) + random_jitter(0-100ms)`
+/// `backoff = min(base_ms * 2^attempt, max_ms)`, then jittered:
+/// - full: `random(0..=backoff)`
+/// - equal: `backoff / 2 + random(0..=backoff / 2)`
+/// - fixed: `backoff + random(0-100ms)`
 ///
 /// # Example
 ///
-/// With defaults (base=500ms, max=5000ms):
-/// - Attempt 0: 500ms + jitter = 500-600ms
-/// - Attempt 1: 1000ms + jitter = 1000-1100ms
-/// - Attempt 2: 2000ms + jitter = 2000-2100ms
-/// - Attempt 3: 4000ms + jitter = 4000-4100ms
-/// - Attempt 4+: 5000ms + jitter = 5000-5100ms (capped)
-fn calculate_backoff(config: &ConfigManager, attempt: u32) -> Duration {
+/// With defaults (base=500ms, max=5000ms, equal jitter):
+/// - Attempt 0: 250-500ms
+/// - Attempt 1: 500-1000ms
+/// - Attempt 2: 1000-2000ms
+/// - Attempt 3: 2000-4000ms
+/// - Attempt 4+: 2500-5000ms (capped)
+pub(crate) fn calculate_backoff(config: &ConfigManager, attempt: u32) -> Duration {
     let base_backoff_ms = config
         .get_value("db_retry_backoff_ms")
         .and_then(|v| match v {
@@ -54,23 +107,25 @@ fn calculate_backoff(config: &ConfigManager, attempt: u32) -> Duration {
         })
         .unwrap_or(5000); // Default 5 seconds cap
 
-    // Add jitter to prevent thundering herd
-    let jitter = rand::random::<u64>() % 100; // 0-100ms random jitter
-
-    // Calculate backoff with exponential growth and cap
-    let backoff_ms = (base_backoff_ms * 2_u64.pow(attempt)).min(max_backoff_ms) + jitter;
+    jittered_backoff(base_backoff_ms, max_backoff_ms, attempt, RetryJitter::from_config(config))
+}
 
-    Duration::from_millis(backoff_ms)
+/// Exponential backoff capped at `max_ms`, with `jitter` applied
+fn jittered_backoff(base_ms: u64, max_ms: u64, attempt: u32, jitter: RetryJitter) -> Duration {
+    let backoff_ms = base_ms.saturating_mul(2_u64.saturating_pow(attempt)).min(max_ms);
+    Duration::from_millis(jitter.apply(backoff_ms))
 }
 
 /// Execute a database query with timeout protection and automatic retry
 ///
 /// Wraps any async database operation with tokio::time::timeout and retries
-/// connection errors automatically with exponential backoff.
+/// connection errors (and SQLite lock/busy errors) automatically with
+/// exponential backoff.
 ///
 /// # Arguments
 ///
 /// * `config` - ConfigManager to read timeout and retry configuration
+/// * `db_type` - Database dialect, used to recognize dialect-specific transient errors
 /// * `config_key` - Key to read timeout value (e.g., "db_query_timeout_secs")
 /// * `default_timeout` - Fallback timeout if config key not set
 /// * `query_fn` - Closure that returns the async query operation to execute
@@ -85,6 +140,7 @@ fn calculate_backoff(config: &ConfigManager, attempt: u32) -> Duration {
 ///
 /// ```rust
 /// # use kodegen_tools_database::tools::timeout::execute_with_timeout;
+/// # use kodegen_tools_database::types::DatabaseType;
 /// # use kodegen_config_manager::ConfigManager;
 /// # use std::time::Duration;
 /// # async fn example() -> Result<(), kodegen_mcp_schema::McpError> {
@@ -92,6 +148,7 @@ fn calculate_backoff(config: &ConfigManager, attempt: u32) -> Duration {
 /// # let pool = ();  // Placeholder
 /// let result = execute_with_timeout(
 ///     &config_manager,
+///     DatabaseType::Postgres,
 ///     "db_query_timeout_secs",
 ///     Duration::from_secs(60),
 ///     || async { Ok::<Vec<()>, sqlx::Error>(vec![]) },
@@ -102,6 +159,7 @@ fn calculate_backoff(config: &ConfigManager, attempt: u32) -> Duration {
 /// ```
 pub async fn execute_with_timeout<T, F, Fut>(
     config: &ConfigManager,
+    db_type: DatabaseType,
     config_key: &str,
     default_timeout: Duration,
     query_fn: F,
@@ -111,90 +169,441 @@ where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
 {
-    // Read timeout and retry configuration
-    let timeout_duration = config
-        .get_value(config_key)
-        .and_then(|v| match v {
-            kodegen_config_manager::ConfigValue::Number(n) => Some(Duration::from_secs(n as u64)),
-            _ => None,
-        })
-        .unwrap_or(default_timeout);
+    run_with_retry(
+        db_type,
+        None,
+        RetryLimits::from_config(config, config_key, default_timeout, None),
+        |attempt| calculate_backoff(config, attempt),
+        || std::future::ready(Ok(())),
+        |()| query_fn(),
+        config_key,
+        operation_description,
+    )
+    .await
+}
 
-    let max_retries = config
-        .get_value("db_max_retries")
-        .and_then(|v| match v {
-            kodegen_config_manager::ConfigValue::Number(n) => Some(n as u32),
-            _ => None,
-        })
-        .unwrap_or(2); // Retry twice by default (3 total attempts)
-
-    let mut last_error = None;
-
-    for attempt in 0..=max_retries {
-        // Execute with timeout
-        match timeout(timeout_duration, query_fn()).await {
-            Ok(Ok(result)) => return Ok(result),
-            Ok(Err(sqlx_err)) => {
-                // Check if error is retryable
-                if is_connection_error(&sqlx_err) && attempt < max_retries {
-                    log::warn!(
-                        "Connection error on attempt {}/{}: {}. Retrying...",
-                        attempt + 1,
-                        max_retries + 1,
-                        sqlx_err
-                    );
-                    last_error = Some(sqlx_err);
-
-                    // Use configurable exponential backoff with jitter
-                    tokio::time::sleep(calculate_backoff(config, attempt)).await;
-                    continue;
-                } else {
-                    // Non-retryable error or max retries exhausted
-                    return Err(DatabaseError::QueryError(format!(
-                        "{}: {}",
-                        operation_description, sqlx_err
-                    ))
-                    .into());
+/// Acquire a pool connection, then run an operation on it with timeout and retry
+///
+/// Unlike [`execute_with_timeout`], the two phases have separate budgets:
+/// waiting for a free connection is bounded by the pool's acquire timeout
+/// (`db_acquire_timeout_secs`), and only the operation itself counts against
+/// `config_key`. A failure says which phase it happened in, so an exhausted
+/// pool is not reported as a slow query. Each retry acquires a fresh connection.
+///
+/// A fatal connection error (see [`is_fatal_connection_error`]) means the
+/// server dropped every session, so the pool's idle connections are closed
+/// before retrying instead of being handed out and failing one by one.
+///
+/// # Arguments
+///
+/// * `config` - ConfigManager to read timeout and retry configuration
+/// * `db_type` - Database dialect, used to recognize dialect-specific transient errors
+/// * `pool` - Pool to acquire the connection from
+/// * `config_key` - Key to read the execution timeout (e.g., "db_query_timeout_secs")
+/// * `default_timeout` - Fallback execution timeout if config key not set
+/// * `query_fn` - Closure that runs the operation on the acquired connection
+/// * `operation_description` - Human-readable description for error messages
+pub async fn execute_on_connection<T, F, Fut>(
+    config: &ConfigManager,
+    db_type: DatabaseType,
+    pool: &AnyPool,
+    config_key: &str,
+    default_timeout: Duration,
+    query_fn: F,
+    operation_description: &str,
+) -> Result<T, McpError>
+where
+    F: Fn(PoolConnection<Any>) -> Fut,
+    Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
+{
+    let acquire_timeout = pool.options().get_acquire_timeout();
+    run_with_retry(
+        db_type,
+        Some(pool),
+        RetryLimits::from_config(config, config_key, default_timeout, Some(acquire_timeout)),
+        |attempt| calculate_backoff(config, attempt),
+        || pool.acquire(),
+        query_fn,
+        config_key,
+        operation_description,
+    )
+    .await
+}
+
+/// Acquire a pool connection with retry, reporting an exhausted pool distinctly
+///
+/// For callers that run several operations on one connection (transactions,
+/// session batches). Waiting is bounded by the pool's acquire timeout
+/// (`db_acquire_timeout_secs`) only.
+pub async fn acquire_connection(
+    config: &ConfigManager,
+    db_type: DatabaseType,
+    pool: &AnyPool,
+    operation_description: &str,
+) -> Result<PoolConnection<Any>, McpError> {
+    execute_on_connection(
+        config,
+        db_type,
+        pool,
+        "db_acquire_timeout_secs",
+        pool.options().get_acquire_timeout(),
+        |conn| std::future::ready(Ok(conn)),
+        operation_description,
+    )
+    .await
+}
+
+/// Resolved timeout and retry settings for [`run_with_retry`]
+struct RetryLimits {
+    /// Timeout for running the operation in each attempt
+    timeout: Duration,
+    /// Pool acquire timeout, reported when no connection could be acquired
+    acquire_timeout: Option<Duration>,
+    /// Retries after the first attempt
+    max_retries: u32,
+    /// Wall-clock cap on all attempts and backoffs (`db_max_total_retry_secs`)
+    budget: Option<Duration>,
+}
+
+impl RetryLimits {
+    /// Read the execution timeout from `config_key` and the retry settings
+    fn from_config(
+        config: &ConfigManager,
+        config_key: &str,
+        default_timeout: Duration,
+        acquire_timeout: Option<Duration>,
+    ) -> Self {
+        let timeout = config
+            .get_value(config_key)
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(Duration::from_secs(n as u64)),
+                _ => None,
+            })
+            .unwrap_or(default_timeout);
+
+        let max_retries = config
+            .get_value("db_max_retries")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) => Some(n as u32),
+                _ => None,
+            })
+            .unwrap_or(2); // Retry twice by default (3 total attempts)
+
+        // Wall-clock cap on the whole retry loop, including timeouts and backoffs
+        let budget = config
+            .get_value("db_max_total_retry_secs")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) if n > 0 => {
+                    Some(Duration::from_secs(n as u64))
                 }
-            }
-            Err(_elapsed) => {
-                // Timeout occurred
-                if attempt < max_retries {
-                    log::warn!(
-                        "Timeout on attempt {}/{}. Retrying...",
-                        attempt + 1,
-                        max_retries + 1
-                    );
-                    // Use configurable exponential backoff with jitter
-                    tokio::time::sleep(calculate_backoff(config, attempt)).await;
-                    continue;
-                } else {
-                    return Err(DatabaseError::QueryError(format!(
-                        "{} timed out after {:?}. \
-                         The operation may be too slow or the database may be overloaded.\n\
-                         Suggestions:\n\
-                         • For SELECT queries: Add WHERE clause, LIMIT, or indexes\n\
-                         • For UPDATE/DELETE: Add WHERE clause to reduce rows affected\n\
-                         • Check database performance with EXPLAIN\n\
-                         • Increase timeout via config: {} = <seconds>",
-                        operation_description, timeout_duration, config_key
-                    ))
-                    .into());
+                _ => None,
+            });
+
+        Self {
+            timeout,
+            acquire_timeout,
+            max_retries,
+            budget,
+        }
+    }
+}
+
+/// Why one attempt in [`run_with_retry`] failed
+enum AttemptError {
+    /// No connection could be acquired from the pool
+    Acquire(sqlx::Error),
+    /// The operation returned an error
+    Query(sqlx::Error),
+    /// The operation did not finish within the execution timeout
+    TimedOut(Duration),
+}
+
+impl AttemptError {
+    /// Classify an error returned by the operation
+    ///
+    /// Operations that still acquire their own connection report an exhausted
+    /// pool as `PoolTimedOut`, which belongs to the acquire phase.
+    fn from_query(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::PoolTimedOut => Self::Acquire(err),
+            err => Self::Query(err),
+        }
+    }
+
+    fn is_fatal_connection_error(&self) -> bool {
+        match self {
+            Self::Acquire(err) | Self::Query(err) => is_fatal_connection_error(err),
+            Self::TimedOut(_) => false,
+        }
+    }
+
+    fn is_retryable(&self, db_type: DatabaseType) -> bool {
+        match self {
+            Self::Acquire(err) | Self::Query(err) => is_retryable_error(err, db_type),
+            Self::TimedOut(_) => true,
+        }
+    }
+
+    /// Final error once retries are exhausted or the failure is not retryable
+    fn into_error(
+        self,
+        limits: &RetryLimits,
+        config_key: &str,
+        operation_description: &str,
+    ) -> McpError {
+        let message = match self {
+            Self::Acquire(sqlx::Error::PoolTimedOut) => format!(
+                "{}: timed out{} waiting for a connection from the pool; the query was not started. \
+                 All pooled connections are busy.\n\
+                 Suggestions:\n\
+                 • Retry once running queries finish\n\
+                 • Increase the pool size via config: db_max_connections = <count>\n\
+                 • Wait longer for a connection via config: db_acquire_timeout_secs = <seconds>",
+                operation_description,
+                limits
+                    .acquire_timeout
+                    .map(|t| format!(" after {:?}", t))
+                    .unwrap_or_default()
+            ),
+            Self::Acquire(err) => format!(
+                "{}: failed to acquire a connection from the pool: {}",
+                operation_description, err
+            ),
+            Self::Query(err) => format!(
+                "{}: {}",
+                operation_description,
+                with_type_hint(err.to_string())
+            ),
+            Self::TimedOut(after) => format!(
+                "{} timed out after {:?} while executing. \
+                 The operation may be too slow or the database may be overloaded.\n\
+                 Suggestions:\n\
+                 • For SELECT queries: Add WHERE clause, LIMIT, or indexes\n\
+                 • For UPDATE/DELETE: Add WHERE clause to reduce rows affected\n\
+                 • Check database performance with EXPLAIN\n\
+                 • Increase timeout via config: {} = <seconds>",
+                operation_description, after, config_key
+            ),
+        };
+        DatabaseError::QueryError(message).into()
+    }
+}
+
+impl std::fmt::Display for AttemptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Acquire(err) => write!(f, "failed to acquire a connection: {}", err),
+            Self::Query(err) => write!(f, "{}", err),
+            Self::TimedOut(after) => write!(f, "timed out after {:?}", after),
+        }
+    }
+}
+
+/// Retry loop behind [`execute_with_timeout`] and [`execute_on_connection`]
+///
+/// Each attempt runs `acquire`, which is not subject to the execution timeout,
+/// then `query_fn` on its result under `limits.timeout`. With a retry budget
+/// set, no retry is started once the time spent so far plus the next backoff
+/// would exceed it, even if retries remain. When `pool` is given, a fatal
+/// connection error refreshes it via [`refresh_pool`].
+async fn run_with_retry<C, A, AFut, T, F, Fut>(
+    db_type: DatabaseType,
+    pool: Option<&AnyPool>,
+    limits: RetryLimits,
+    backoff: impl Fn(u32) -> Duration,
+    acquire: A,
+    query_fn: F,
+    config_key: &str,
+    operation_description: &str,
+) -> Result<T, McpError>
+where
+    A: Fn() -> AFut,
+    AFut: std::future::Future<Output = Result<C, sqlx::Error>>,
+    F: Fn(C) -> Fut,
+    Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
+{
+    let max_retries = limits.max_retries;
+    let start = std::time::Instant::now();
+
+    let mut attempt = 0;
+    loop {
+        let failure = match acquire().await {
+            Err(err) => AttemptError::Acquire(err),
+            Ok(conn) => match timeout(limits.timeout, query_fn(conn)).await {
+                Ok(Ok(result)) => return Ok(result),
+                Ok(Err(err)) => AttemptError::from_query(err),
+                Err(_elapsed) => AttemptError::TimedOut(limits.timeout),
+            },
+        };
+
+        if let Some(pool) = pool
+            && failure.is_fatal_connection_error()
+        {
+            let closed = refresh_pool(pool).await;
+            log::warn!(
+                "Fatal connection error ({}); closed {} idle pooled connection(s)",
+                failure,
+                closed
+            );
+        }
+
+        if attempt == max_retries || !failure.is_retryable(db_type) {
+            return Err(failure.into_error(&limits, config_key, operation_description));
+        }
+        log::warn!(
+            "Transient error on attempt {}/{}: {}. Retrying...",
+            attempt + 1,
+            max_retries + 1,
+            failure
+        );
+
+        // Use configurable exponential backoff with jitter
+        let delay = backoff(attempt);
+        if let Some(budget) = limits.budget
+            && start.elapsed() + delay > budget
+        {
+            return Err(DatabaseError::QueryError(format!(
+                "{}: retry budget db_max_total_retry_secs ({:?}) exhausted after {} attempts. \
+                 Last error: {}",
+                operation_description,
+                budget,
+                attempt + 1,
+                failure
+            ))
+            .into());
+        }
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Check if a sqlx error is transient for the given dialect and worth retrying
+///
+/// Connection errors are retryable everywhere. SQLite additionally reports
+/// lock contention (`SQLITE_BUSY` / `SQLITE_LOCKED`, "database is locked")
+/// as an error that clears once the competing writer finishes.
+fn is_retryable_error(err: &sqlx::Error, db_type: DatabaseType) -> bool {
+    is_connection_error(err)
+        || is_fatal_connection_error(err)
+        || (db_type == DatabaseType::SQLite && is_sqlite_lock_error(err))
+}
+
+/// Check if a sqlx error is a SQLite busy/locked error
+fn is_sqlite_lock_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db_err) => {
+            // Primary result code is the low byte of the extended code
+            let busy_code = db_err
+                .code()
+                .and_then(|code| code.parse::<i32>().ok())
+                .is_some_and(|code| matches!(code & 0xff, 5 | 6)); // SQLITE_BUSY, SQLITE_LOCKED
+            let msg = db_err.message().to_lowercase();
+            busy_code || msg.contains("database is locked") || msg.contains("database table is locked")
+        }
+        _ => false,
+    }
+}
+
+/// Check if a sqlx error means the server dropped its sessions
+///
+/// PostgreSQL reports a shutdown or restart as class 57P (`57P01`
+/// admin_shutdown, `57P02` crash_shutdown, `57P03` cannot_connect_now) and a
+/// lost link as class 08, which MySQL also uses (`08S01`). Once one of these
+/// arrives, the pool's other idle connections are almost certainly dead too.
+pub fn is_fatal_connection_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(io) => matches!(
+            io.kind(),
+            std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::UnexpectedEof
+        ),
+        sqlx::Error::Database(db_err) => {
+            let code = db_err.code().map(|c| c.to_string()).unwrap_or_default();
+            let msg = db_err.message().to_lowercase();
+            code.starts_with("57P")
+                || code.starts_with("08")
+                || msg.contains("terminating connection")
+                || msg.contains("server closed the connection unexpectedly")
+                || msg.contains("server has gone away")
+                || msg.contains("lost connection to")
+        }
+        _ => false,
+    }
+}
+
+/// Check if a sqlx error means the server is up but not yet accepting sessions
+///
+/// PostgreSQL answers `57P03` (cannot_connect_now, "the database system is
+/// starting up") during startup and crash recovery, e.g. right after a
+/// failover; MySQL/MariaDB say the server is not ready. Unlike other
+/// connection failures these clear on their own once the server finishes.
+pub fn is_server_starting_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db_err) => {
+            let msg = db_err.message().to_lowercase();
+            db_err.code().is_some_and(|code| code == "57P03")
+                || msg.contains("the database system is starting up")
+                || msg.contains("the database system is in recovery mode")
+                || msg.contains("not yet accepting connections")
+                || msg.contains("server is not ready")
+        }
+        _ => false,
+    }
+}
+
+/// Connect, retrying while the server reports that it is starting up
+///
+/// `connect` is tried again after `backoff(attempt)` as long as it fails with
+/// [`is_server_starting_error`] and the retry would begin within `window` of
+/// the first attempt. Any other error, or the last startup error once the
+/// window is used up, is returned as is.
+pub(crate) async fn connect_with_startup_retry<T, F, Fut>(
+    window: Duration,
+    backoff: impl Fn(u32) -> Duration,
+    connect: F,
+) -> Result<T, sqlx::Error>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
+{
+    let start = std::time::Instant::now();
+    let mut attempt = 0;
+    loop {
+        match connect().await {
+            Err(err) if is_server_starting_error(&err) => {
+                let delay = backoff(attempt);
+                if start.elapsed() + delay > window {
+                    return Err(err);
                 }
+                log::warn!(
+                    "Database is not accepting connections yet ({}); retrying in {:?}",
+                    err,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
             }
+            result => return result,
         }
     }
+}
 
-    // If we exhausted retries
-    Err(DatabaseError::QueryError(format!(
-        "{}: Max retries ({}) exceeded. Last error: {}",
-        operation_description,
-        max_retries,
-        last_error
-            .map(|e| e.to_string())
-            .unwrap_or_else(|| "Unknown".to_string())
-    ))
-    .into())
+/// Close every idle connection in `pool`, returning how many were closed
+///
+/// Connections in use are left alone; the pool opens fresh ones on demand.
+pub async fn refresh_pool(pool: &AnyPool) -> usize {
+    let mut closed = 0;
+    for _ in 0..pool.num_idle() {
+        let Some(conn) = pool.try_acquire() else {
+            break;
+        };
+        // The server side is likely gone already, so a failed goodbye is expected
+        let _ = conn.close().await;
+        closed += 1;
+    }
+    closed
 }
 
 /// Check if a sqlx error is connection-related and retryable
@@ -211,3 +620,397 @@ fn is_connection_error(err: &sqlx::Error) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Minimal driver error for simulating database failures
+    #[derive(Debug)]
+    struct FakeDbError {
+        message: &'static str,
+        code: Option<&'static str>,
+    }
+
+    impl std::fmt::Display for FakeDbError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl std::error::Error for FakeDbError {}
+
+    impl sqlx::error::DatabaseError for FakeDbError {
+        fn message(&self) -> &str {
+            self.message
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            self.code.map(Cow::Borrowed)
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn db_error(message: &'static str, code: Option<&'static str>) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(FakeDbError { message, code }))
+    }
+
+    /// Run a query that fails once with `first_error`, then succeeds; returns attempts made
+    async fn attempts_after_failure(
+        db_type: DatabaseType,
+        first_error: fn() -> sqlx::Error,
+    ) -> (u32, bool) {
+        let attempts = AtomicU32::new(0);
+        let result = run_with_retry(
+            db_type,
+            None,
+            RetryLimits {
+                timeout: Duration::from_secs(5),
+                acquire_timeout: None,
+                max_retries: 2,
+                budget: None,
+            },
+            |_| Duration::ZERO,
+            || std::future::ready(Ok(())),
+            |()| {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        Err(first_error())
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+            "db_query_timeout_secs",
+            "Test query",
+        )
+        .await;
+        (attempts.load(Ordering::SeqCst), result.is_ok())
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_lock_error_is_retried() {
+        let (attempts, ok) = attempts_after_failure(DatabaseType::SQLite, || {
+            db_error("database is locked", Some("5"))
+        })
+        .await;
+        assert_eq!(attempts, 2);
+        assert!(ok);
+
+        // Extended busy code without the usual message (SQLITE_BUSY_SNAPSHOT)
+        let (attempts, ok) =
+            attempts_after_failure(DatabaseType::SQLite, || db_error("busy", Some("517"))).await;
+        assert_eq!(attempts, 2);
+        assert!(ok);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_syntax_error_is_not_retried() {
+        let (attempts, ok) = attempts_after_failure(DatabaseType::SQLite, || {
+            db_error("near \"SELEC\": syntax error", Some("1"))
+        })
+        .await;
+        assert_eq!(attempts, 1);
+        assert!(!ok);
+    }
+
+    #[tokio::test]
+    async fn test_lock_message_not_retried_for_other_dialects() {
+        let (attempts, ok) = attempts_after_failure(DatabaseType::Postgres, || {
+            db_error("database is locked", Some("5"))
+        })
+        .await;
+        assert_eq!(attempts, 1);
+        assert!(!ok);
+    }
+
+    /// Connect attempts that fail with `error` `failures` times, then succeed
+    async fn startup_attempts(
+        window: Duration,
+        failures: u32,
+        error: fn() -> sqlx::Error,
+    ) -> (u32, Result<(), sqlx::Error>) {
+        let attempts = AtomicU32::new(0);
+        let result = connect_with_startup_retry(
+            window,
+            |_| Duration::from_millis(10),
+            || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move { if attempt < failures { Err(error()) } else { Ok(()) } }
+            },
+        )
+        .await;
+        (attempts.load(Ordering::SeqCst), result)
+    }
+
+    #[tokio::test]
+    async fn test_starting_up_retried_within_window() {
+        let starting_up = || db_error("the database system is starting up", Some("57P03"));
+        let (attempts, result) = startup_attempts(Duration::from_secs(5), 2, starting_up).await;
+        assert_eq!(attempts, 3);
+        assert!(result.is_ok());
+
+        let not_ready = || db_error("Server is not ready, please retry", Some("HY000"));
+        let (attempts, result) = startup_attempts(Duration::from_secs(5), 1, not_ready).await;
+        assert_eq!(attempts, 2);
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_starting_up_gives_up_after_window() {
+        let starting_up = || db_error("the database system is starting up", Some("57P03"));
+        let (attempts, result) = startup_attempts(Duration::ZERO, 5, starting_up).await;
+        assert_eq!(attempts, 1);
+        assert!(result.is_err_and(|err| is_server_starting_error(&err)));
+    }
+
+    #[tokio::test]
+    async fn test_other_connect_errors_not_retried_at_startup() {
+        let auth = || db_error("password authentication failed for user \"app\"", Some("28P01"));
+        let (attempts, result) = startup_attempts(Duration::from_secs(5), 1, auth).await;
+        assert_eq!(attempts, 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_jitter_ranges() {
+        let ms = |d: Duration| d.as_millis() as u64;
+        for _ in 0..200 {
+            // Attempt 2 with base 500ms: 2000ms before jitter
+            let full = ms(jittered_backoff(500, 5000, 2, RetryJitter::Full));
+            assert!(full <= 2000, "full: {}", full);
+
+            let equal = ms(jittered_backoff(500, 5000, 2, RetryJitter::Equal));
+            assert!((1000..=2000).contains(&equal), "equal: {}", equal);
+
+            let fixed = ms(jittered_backoff(500, 5000, 2, RetryJitter::Fixed));
+            assert!((2000..2100).contains(&fixed), "fixed: {}", fixed);
+
+            // Capped attempts jitter around the cap
+            let capped = ms(jittered_backoff(500, 5000, 10, RetryJitter::Equal));
+            assert!((2500..=5000).contains(&capped), "capped: {}", capped);
+        }
+        assert_eq!(jittered_backoff(0, 5000, 3, RetryJitter::Full), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_jitter_defaults_to_equal() {
+        assert_eq!(RetryJitter::from_config(&ConfigManager::new()), RetryJitter::Equal);
+    }
+
+    #[tokio::test]
+    async fn test_connection_error_retried_for_all_dialects() {
+        for db_type in [DatabaseType::Postgres, DatabaseType::MySQL, DatabaseType::SQLite] {
+            let (attempts, ok) = attempts_after_failure(db_type, || sqlx::Error::PoolTimedOut).await;
+            assert_eq!(attempts, 2);
+            assert!(ok);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_stops_retries_early() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), McpError> = run_with_retry(
+            DatabaseType::Postgres,
+            None,
+            RetryLimits {
+                timeout: Duration::from_secs(5),
+                acquire_timeout: None,
+                max_retries: 10,
+                budget: Some(Duration::from_millis(250)),
+            },
+            |_| Duration::from_millis(100),
+            || std::future::ready(Ok(())),
+            |()| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(sqlx::Error::PoolTimedOut) }
+            },
+            "db_query_timeout_secs",
+            "Test query",
+        )
+        .await;
+
+        // Retries after 0ms and ~100ms fit; the one after ~200ms would end past 250ms
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("db_max_total_retry_secs"), "{}", msg);
+        assert!(msg.contains("after 3 attempts"), "{}", msg);
+    }
+
+    /// Single-connection SQLite pool that gives up waiting after 100ms
+    async fn single_connection_pool() -> AnyPool {
+        sqlx::any::install_default_drivers();
+        sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_millis(100))
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool")
+    }
+
+    fn single_attempt(timeout: Duration, acquire_timeout: Duration) -> RetryLimits {
+        RetryLimits {
+            timeout,
+            acquire_timeout: Some(acquire_timeout),
+            max_retries: 0,
+            budget: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_exhaustion_reports_acquire_timeout() {
+        let pool = single_connection_pool().await;
+        let _held = pool.acquire().await.expect("first connection");
+
+        let result: Result<(), McpError> = run_with_retry(
+            DatabaseType::SQLite,
+            None,
+            single_attempt(Duration::from_secs(5), pool.options().get_acquire_timeout()),
+            |_| Duration::ZERO,
+            || pool.acquire(),
+            |mut conn| async move { sqlx::query("SELECT 1").execute(&mut *conn).await.map(|_| ()) },
+            "db_query_timeout_secs",
+            "Test query",
+        )
+        .await;
+
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("waiting for a connection from the pool"), "{}", msg);
+        assert!(msg.contains("after 100ms"), "{}", msg);
+        assert!(msg.contains("db_acquire_timeout_secs"), "{}", msg);
+        assert!(!msg.contains("while executing"), "{}", msg);
+    }
+
+    #[tokio::test]
+    async fn test_slow_query_reports_execution_timeout() {
+        let pool = single_connection_pool().await;
+
+        let result: Result<(), McpError> = run_with_retry(
+            DatabaseType::SQLite,
+            None,
+            single_attempt(Duration::from_millis(50), pool.options().get_acquire_timeout()),
+            |_| Duration::ZERO,
+            || pool.acquire(),
+            |mut conn| async move {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                sqlx::query("SELECT 1").execute(&mut *conn).await.map(|_| ())
+            },
+            "db_query_timeout_secs",
+            "Test query",
+        )
+        .await;
+
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("timed out after 50ms while executing"), "{}", msg);
+        assert!(msg.contains("db_query_timeout_secs"), "{}", msg);
+        assert!(!msg.contains("db_acquire_timeout_secs"), "{}", msg);
+    }
+
+    #[test]
+    fn test_fatal_connection_errors_detected() {
+        let shutdown = db_error("terminating connection due to administrator command", Some("57P01"));
+        assert!(is_fatal_connection_error(&shutdown));
+        let gone = db_error("Server shutdown in progress", Some("08S01"));
+        assert!(is_fatal_connection_error(&gone));
+        let reset = sqlx::Error::Io(std::io::ErrorKind::ConnectionReset.into());
+        assert!(is_fatal_connection_error(&reset));
+
+        let syntax = db_error("syntax error at or near \"SELEC\"", Some("42601"));
+        assert!(!is_fatal_connection_error(&syntax));
+        assert!(!is_fatal_connection_error(&sqlx::Error::PoolTimedOut));
+    }
+
+    #[tokio::test]
+    async fn test_fatal_connection_error_refreshes_pool() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(3)
+            .min_connections(0)
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        // Leave two idle connections in the pool
+        let first = pool.acquire().await.expect("first connection");
+        let second = pool.acquire().await.expect("second connection");
+        drop((first, second));
+        while pool.num_idle() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), McpError> = run_with_retry(
+            DatabaseType::Postgres,
+            Some(&pool),
+            RetryLimits {
+                timeout: Duration::from_secs(5),
+                acquire_timeout: None,
+                max_retries: 2,
+                budget: None,
+            },
+            |_| Duration::ZERO,
+            || std::future::ready(Ok(())),
+            |()| {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        Err(db_error(
+                            "terminating connection due to administrator command",
+                            Some("57P01"),
+                        ))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+            "db_query_timeout_secs",
+            "Test query",
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.num_idle(), 0, "idle connections are closed after a fatal error");
+    }
+
+    #[tokio::test]
+    async fn test_non_fatal_error_leaves_pool_alone() {
+        let pool = single_connection_pool().await;
+        drop(pool.acquire().await.expect("connection"));
+        while pool.num_idle() < 1 {
+            tokio::task::yield_now().await;
+        }
+
+        let result: Result<(), McpError> = run_with_retry(
+            DatabaseType::Postgres,
+            Some(&pool),
+            single_attempt(Duration::from_secs(5), pool.options().get_acquire_timeout()),
+            |_| Duration::ZERO,
+            || std::future::ready(Ok(())),
+            |()| async { Err(db_error("syntax error", Some("42601"))) },
+            "db_query_timeout_secs",
+            "Test query",
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(pool.num_idle(), 1);
+    }
+}
@@ -0,0 +1,301 @@
+//! Registry of open transactions that span several calls
+//!
+//! A session-scoped transaction keeps its pooled connection checked out until
+//! it is committed or rolled back. When a client abandons one, its locks and
+//! connection would be held until the process exits, so a background reaper
+//! rolls back transactions idle longer than `db_transaction_idle_timeout_secs`
+//! and returns their connections to the pool.
+
+use crate::error::DatabaseError;
+use crate::tools::execute_sql::row_to_typed;
+use kodegen_config_manager::ConfigManager;
+use kodegen_mcp_schema::database::SqlRow;
+use sqlx::{Any, AnyPool, Transaction};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// Shortest interval between reaper sweeps
+const MIN_REAP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A transaction between calls
+struct OpenTransaction {
+    tx: Transaction<'static, Any>,
+    started: Instant,
+    last_used: Instant,
+}
+
+/// Open transactions by id
+///
+/// A transaction is checked out of the registry while a statement runs on it,
+/// so the reaper only ever sees transactions that are idle.
+///
+/// # Example
+/// ```rust,no_run
+/// # use kodegen_tools_database::transactions::TransactionRegistry;
+/// # use std::time::Duration;
+/// # async fn example(pool: std::sync::Arc<sqlx::AnyPool>) -> anyhow::Result<()> {
+/// let registry = TransactionRegistry::new(pool, Some(Duration::from_secs(300)));
+/// registry.spawn_reaper();
+/// let id = registry.begin().await?;
+/// registry.execute(&id, "UPDATE accounts SET balance = balance - 10 WHERE id = 1").await?;
+/// registry.commit(&id).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransactionRegistry {
+    pool: Arc<AnyPool>,
+    idle_timeout: Option<Duration>,
+    open: Mutex<HashMap<String, OpenTransaction>>,
+}
+
+impl TransactionRegistry {
+    /// Create a registry; `idle_timeout` of `None` never reaps
+    pub fn new(pool: Arc<AnyPool>, idle_timeout: Option<Duration>) -> Arc<Self> {
+        Arc::new(Self {
+            pool,
+            idle_timeout,
+            open: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Create a registry with `db_transaction_idle_timeout_secs` (unset or 0 disables reaping)
+    pub fn from_config(pool: Arc<AnyPool>, config_manager: &ConfigManager) -> Arc<Self> {
+        let idle_timeout = config_manager
+            .get_value("db_transaction_idle_timeout_secs")
+            .and_then(|v| match v {
+                kodegen_config_manager::ConfigValue::Number(n) if n > 0 => {
+                    Some(Duration::from_secs(n as u64))
+                }
+                _ => None,
+            });
+        Self::new(pool, idle_timeout)
+    }
+
+    /// Number of open transactions, excluding any running a statement
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no transaction is open
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Begin a transaction on a pooled connection and return its id
+    ///
+    /// # Errors
+    /// Returns error if no connection can be acquired or `BEGIN` fails
+    pub async fn begin(&self) -> Result<String, DatabaseError> {
+        let tx = self.pool.begin().await?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Instant::now();
+        self.lock().insert(
+            id.clone(),
+            OpenTransaction {
+                tx,
+                started: now,
+                last_used: now,
+            },
+        );
+        log::debug!("Transaction {} started", id);
+        Ok(id)
+    }
+
+    /// Run a row-returning statement inside transaction `id`
+    ///
+    /// # Errors
+    /// Returns error if the transaction is unknown or busy, or the statement fails
+    pub async fn query(&self, id: &str, sql: &str) -> Result<Vec<SqlRow>, DatabaseError> {
+        let mut open = self.checkout(id)?;
+        let rows = sqlx::query(sql).fetch_all(&mut *open.tx).await;
+        self.checkin(id, open);
+        rows?.iter().map(row_to_typed).collect()
+    }
+
+    /// Run a statement inside transaction `id` and return the number of affected rows
+    ///
+    /// # Errors
+    /// Returns error if the transaction is unknown or busy, or the statement fails
+    pub async fn execute(&self, id: &str, sql: &str) -> Result<u64, DatabaseError> {
+        let mut open = self.checkout(id)?;
+        let result = sqlx::query(sql).execute(&mut *open.tx).await;
+        self.checkin(id, open);
+        Ok(result?.rows_affected())
+    }
+
+    /// Commit transaction `id` and return its connection to the pool
+    ///
+    /// # Errors
+    /// Returns error if the transaction is unknown or busy, or `COMMIT` fails
+    pub async fn commit(&self, id: &str) -> Result<(), DatabaseError> {
+        let open = self.checkout(id)?;
+        open.tx.commit().await?;
+        Ok(())
+    }
+
+    /// Roll back transaction `id` and return its connection to the pool
+    ///
+    /// # Errors
+    /// Returns error if the transaction is unknown or busy, or `ROLLBACK` fails
+    pub async fn rollback(&self, id: &str) -> Result<(), DatabaseError> {
+        let open = self.checkout(id)?;
+        open.tx.rollback().await?;
+        Ok(())
+    }
+
+    /// Roll back every transaction idle longer than the timeout
+    ///
+    /// Returns the number of transactions rolled back. A failed rollback still
+    /// releases the connection; sqlx closes it instead of reusing it.
+    pub async fn reap_idle(&self) -> usize {
+        let Some(idle_timeout) = self.idle_timeout else {
+            return 0;
+        };
+        let expired: Vec<(String, OpenTransaction)> = {
+            let mut open = self.lock();
+            let ids: Vec<String> = open
+                .iter()
+                .filter(|(_, t)| t.last_used.elapsed() > idle_timeout)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| open.remove(&id).map(|t| (id, t)))
+                .collect()
+        };
+
+        let count = expired.len();
+        for (id, open) in expired {
+            log::warn!(
+                "Rolling back transaction {} idle for {:?} (age {:?}, db_transaction_idle_timeout_secs = {})",
+                id,
+                open.last_used.elapsed(),
+                open.started.elapsed(),
+                idle_timeout.as_secs()
+            );
+            if let Err(e) = open.tx.rollback().await {
+                log::warn!("Rollback of idle transaction {} failed: {}", id, e);
+            }
+        }
+        count
+    }
+
+    /// Reap idle transactions in the background until the registry is dropped
+    ///
+    /// Sweeps every half timeout (at least once a second). Does nothing when
+    /// no timeout is configured.
+    pub fn spawn_reaper(self: &Arc<Self>) {
+        let Some(idle_timeout) = self.idle_timeout else {
+            return;
+        };
+        let interval = (idle_timeout / 2).max(MIN_REAP_INTERVAL);
+        let registry: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(registry) = registry.upgrade() else { break };
+                if registry.pool.is_closed() {
+                    break;
+                }
+                registry.reap_idle().await;
+            }
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, OpenTransaction>> {
+        self.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn checkout(&self, id: &str) -> Result<OpenTransaction, DatabaseError> {
+        self.lock().remove(id).ok_or_else(|| {
+            DatabaseError::QueryError(format!(
+                "Transaction '{}' is not open (committed, rolled back, expired, or busy)",
+                id
+            ))
+        })
+    }
+
+    fn checkin(&self, id: &str, mut open: OpenTransaction) {
+        open.last_used = Instant::now();
+        self.lock().insert(id.to_string(), open);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn single_connection_pool() -> Arc<AnyPool> {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(2))
+            .connect("sqlite::memory:")
+            .await
+            .expect("sqlite pool");
+        sqlx::query("CREATE TABLE items (id INTEGER)")
+            .execute(&pool)
+            .await
+            .expect("create table");
+        Arc::new(pool)
+    }
+
+    async fn item_count(pool: &AnyPool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM items")
+            .fetch_one(pool)
+            .await
+            .expect("count")
+    }
+
+    #[tokio::test]
+    async fn test_commit_and_rollback() {
+        let pool = single_connection_pool().await;
+        let registry = TransactionRegistry::new(pool.clone(), None);
+
+        let id = registry.begin().await.expect("begin");
+        registry.execute(&id, "INSERT INTO items VALUES (1)").await.expect("insert");
+        let rows = registry.query(&id, "SELECT id FROM items").await.expect("select");
+        assert_eq!(rows.len(), 1);
+        registry.commit(&id).await.expect("commit");
+        assert!(registry.execute(&id, "SELECT 1").await.is_err());
+
+        let id = registry.begin().await.expect("begin");
+        registry.execute(&id, "INSERT INTO items VALUES (2)").await.expect("insert");
+        registry.rollback(&id).await.expect("rollback");
+        assert_eq!(item_count(&pool).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_idle_transaction_reaped() {
+        let pool = single_connection_pool().await;
+        let registry = TransactionRegistry::new(pool.clone(), Some(Duration::from_millis(50)));
+
+        let active = registry.begin().await.expect("begin");
+        registry.execute(&active, "INSERT INTO items VALUES (1)").await.expect("insert");
+        assert_eq!(registry.reap_idle().await, 0, "not idle long enough yet");
+        assert_eq!(pool.num_idle(), 0, "the only connection is held by the transaction");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(registry.reap_idle().await, 1);
+        assert!(registry.is_empty());
+        assert!(registry.commit(&active).await.is_err());
+
+        // Rolled back, and the pool's single connection can be acquired again
+        assert_eq!(item_count(&pool).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_background_reaper_returns_connection() {
+        let pool = single_connection_pool().await;
+        let registry = TransactionRegistry::new(pool.clone(), Some(Duration::from_millis(200)));
+        registry.spawn_reaper();
+
+        let id = registry.begin().await.expect("begin");
+        registry.execute(&id, "INSERT INTO items VALUES (1)").await.expect("insert");
+
+        // The reaper sweeps at least once a second
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(registry.is_empty());
+        assert_eq!(item_count(&pool).await, 0);
+    }
+}
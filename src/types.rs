@@ -35,6 +35,22 @@ pub struct TableIndex {
     pub is_primary: bool,
 }
 
+/// Table constraint metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TableConstraint {
+    /// Constraint name (generated for unnamed SQLite constraints and NOT NULLs)
+    pub constraint_name: String,
+
+    /// "PRIMARY KEY", "UNIQUE", "CHECK", "FOREIGN KEY" or "NOT NULL"
+    pub constraint_type: String,
+
+    /// Constrained columns in order (empty for a CHECK that names no column)
+    pub column_names: Vec<String>,
+
+    /// Defining expression, e.g. `CHECK (price > 0)`
+    pub definition: Option<String>,
+}
+
 /// Stored procedure or function metadata
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StoredProcedure {
@@ -47,21 +63,242 @@ pub struct StoredProcedure {
     /// Programming language (e.g., "SQL", "PLPGSQL", "PLSQL")
     pub language: Option<String>,
 
-    /// Parameter list definition
+    /// Parameter list definition, e.g. `IN dept_id integer, OUT total bigint`
     pub parameter_list: Option<String>,
 
+    /// Parameters in declaration order
+    pub parameters: Vec<ProcedureParameter>,
+
     /// Return type (for functions)
     pub return_type: Option<String>,
 
     /// Full procedure/function definition
     pub definition: Option<String>,
+
+    /// Whether `definition` was cut to `db_max_definition_bytes`
+    pub definition_truncated: bool,
 }
 
-/// Options for SQL query execution
+/// Stored procedure or function parameter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProcedureParameter {
+    /// Parameter name (unnamed PostgreSQL parameters have none)
+    pub name: Option<String>,
+
+    /// Data type (e.g., "integer", "varchar(255)")
+    pub data_type: String,
+
+    /// Parameter mode ("IN", "OUT", "INOUT", "VARIADIC")
+    pub mode: Option<String>,
+
+    /// 1-based position in the parameter list
+    pub ordinal_position: u32,
+}
+
+impl ProcedureParameter {
+    /// Render as it appears in a signature, e.g. `IN dept_id integer`
+    pub fn signature(&self) -> String {
+        [self.mode.as_deref(), self.name.as_deref(), Some(self.data_type.as_str())]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Statement currently running on the database server
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActiveQuery {
+    /// Server process / connection identifier
+    pub process_id: i64,
+
+    /// User owning the connection
+    pub user_name: Option<String>,
+
+    /// Connection state (e.g., "active", "idle in transaction")
+    pub state: String,
+
+    /// Elapsed time of the current statement in milliseconds
+    pub duration_ms: Option<i64>,
+
+    /// Fingerprinted statement text (literals replaced with `?`)
+    pub query: String,
+
+    /// Whether this is the connection that ran the listing query
+    pub is_current_connection: bool,
+}
+
+/// Identity and key settings of the connected database server
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ConnectionInfo {
+    /// Server version number (e.g., "16.2", "8.0.36", SQLite "3.45.1")
+    pub server_version: String,
+
+    /// Full version banner (e.g., PostgreSQL `version()`)
+    pub version_details: Option<String>,
+
+    /// Current database (or SQLite schema "main")
+    pub database: Option<String>,
+
+    /// User the connection is authenticated as
+    pub user: Option<String>,
+
+    /// Session time zone
+    pub timezone: Option<String>,
+
+    /// Server-wide connection limit
+    pub max_connections: Option<i64>,
+
+    /// SQLite database file path (None for in-memory databases and servers)
+    pub file_path: Option<String>,
+}
+
+/// Transport encryption of a database connection, as reported by the server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SecurityInfo {
+    /// Whether transport encryption applies at all (false for local SQLite files)
+    pub applicable: bool,
+
+    /// Whether the connection is encrypted with TLS
+    pub encrypted: bool,
+
+    /// TLS protocol version (e.g., "TLSv1.3")
+    pub protocol: Option<String>,
+
+    /// Cipher suite in use (e.g., "TLS_AES_256_GCM_SHA384")
+    pub cipher: Option<String>,
+
+    /// Explanation when encryption does not apply or cannot be fully reported
+    pub note: Option<String>,
+}
+
+/// A PostgreSQL `NOTIFY` event received on a listened channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Notification {
+    /// Channel the notification was sent on
+    pub channel: String,
+
+    /// Payload string (empty when NOTIFY was sent without one)
+    pub payload: String,
+
+    /// Backend process ID of the notifying session
+    pub process_id: u32,
+}
+
+/// Execution statistics from an analyzing EXPLAIN
+///
+/// Times are in milliseconds. Block counts are PostgreSQL buffer pages and
+/// are `None` on MySQL, which does not report them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QueryStats {
+    /// Time spent planning
+    pub planning_time_ms: Option<f64>,
+
+    /// Total execution time
+    pub execution_time_ms: Option<f64>,
+
+    /// Actual time of the root plan node
+    pub actual_total_time_ms: Option<f64>,
+
+    /// Rows produced by the root plan node
+    pub actual_rows: Option<u64>,
+
+    /// Shared buffer pages found in cache
+    pub shared_hit_blocks: Option<u64>,
+
+    /// Shared buffer pages read from disk
+    pub shared_read_blocks: Option<u64>,
+
+    /// Shared buffer pages modified
+    pub shared_dirtied_blocks: Option<u64>,
+
+    /// Shared buffer pages written out
+    pub shared_written_blocks: Option<u64>,
+
+    /// Local (temporary table) buffer pages found in cache
+    pub local_hit_blocks: Option<u64>,
+
+    /// Local (temporary table) buffer pages read from disk
+    pub local_read_blocks: Option<u64>,
+
+    /// Temporary file pages read (sorts and hashes spilling to disk)
+    pub temp_read_blocks: Option<u64>,
+
+    /// Temporary file pages written
+    pub temp_written_blocks: Option<u64>,
+}
+
+/// Planner estimates for a statement, read from a plain (non-analyzing) EXPLAIN
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PlanEstimate {
+    /// Rows the root plan node is expected to return
+    pub rows: Option<f64>,
+
+    /// Total cost of the root plan node, in the planner's own units
+    pub cost: Option<f64>,
+}
+
+/// Query plan, with statistics when the statement was analyzed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExplainResult {
+    /// Plan as returned by the database (JSON, tree text, or plan lines)
+    pub plan: String,
+
+    /// Execution statistics (None unless statistics were requested)
+    pub stats: Option<QueryStats>,
+}
+
+/// Per-call overrides for SQL query execution
+///
+/// Each field overrides the corresponding ConfigManager setting for a single
+/// call, but can only tighten what the server allows: `readonly` cannot turn
+/// off server-enforced read-only mode, and `max_rows` cannot exceed the
+/// server's `max_rows`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ExecuteOptions {
-    /// Maximum number of rows to return (None = unlimited)
+    /// Maximum number of rows to return (None = server default)
     pub max_rows: Option<usize>,
+
+    /// Enforce read-only validation for this call (None = server default)
+    #[serde(default)]
+    pub readonly: Option<bool>,
+
+    /// Overall timeout for this call in seconds (None = server default)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ExecuteOptions {
+    /// Effective read-only mode: the server setting can be tightened, never loosened
+    pub fn effective_readonly(&self, server_readonly: bool) -> bool {
+        server_readonly || self.readonly.unwrap_or(false)
+    }
+
+    /// Effective row limit: the per-call value, capped at the server limit if any
+    pub fn effective_max_rows(&self, server_max_rows: Option<usize>) -> Option<usize> {
+        match (self.max_rows, server_max_rows) {
+            (Some(call), Some(server)) => Some(call.min(server)),
+            (call, server) => call.or(server),
+        }
+    }
+
+    /// Overall deadline for the call, if one was requested
+    pub fn timeout(&self) -> Option<std::time::Duration> {
+        self.timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+}
+
+impl From<kodegen_mcp_schema::database::ExecuteSQLOptions> for ExecuteOptions {
+    fn from(options: kodegen_mcp_schema::database::ExecuteSQLOptions) -> Self {
+        Self {
+            max_rows: options.max_rows,
+            readonly: options.readonly,
+            timeout_secs: options.timeout_secs,
+        }
+    }
 }
 
 /// SQL query execution result
@@ -127,3 +364,72 @@ impl std::fmt::Display for DatabaseType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_call_max_rows_overrides_config() {
+        let options = ExecuteOptions {
+            max_rows: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(options.effective_max_rows(Some(1000)), Some(10));
+        assert_eq!(options.effective_max_rows(None), Some(10));
+
+        // Cannot raise the server cap
+        let options = ExecuteOptions {
+            max_rows: Some(5000),
+            ..Default::default()
+        };
+        assert_eq!(options.effective_max_rows(Some(1000)), Some(1000));
+
+        assert_eq!(ExecuteOptions::default().effective_max_rows(Some(1000)), Some(1000));
+    }
+
+    #[test]
+    fn test_readonly_cannot_be_loosened() {
+        let options = ExecuteOptions {
+            readonly: Some(false),
+            ..Default::default()
+        };
+        assert!(options.effective_readonly(true));
+        assert!(!options.effective_readonly(false));
+
+        let options = ExecuteOptions {
+            readonly: Some(true),
+            ..Default::default()
+        };
+        assert!(options.effective_readonly(false));
+    }
+
+    #[test]
+    fn test_procedure_parameter_signature() {
+        let named = ProcedureParameter {
+            name: Some("dept_id".to_string()),
+            data_type: "integer".to_string(),
+            mode: Some("IN".to_string()),
+            ordinal_position: 1,
+        };
+        assert_eq!(named.signature(), "IN dept_id integer");
+
+        let unnamed = ProcedureParameter {
+            name: None,
+            data_type: "text".to_string(),
+            mode: Some("OUT".to_string()),
+            ordinal_position: 2,
+        };
+        assert_eq!(unnamed.signature(), "OUT text");
+    }
+
+    #[test]
+    fn test_timeout_override() {
+        let options = ExecuteOptions {
+            timeout_secs: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(options.timeout(), Some(std::time::Duration::from_secs(5)));
+        assert_eq!(ExecuteOptions::default().timeout(), None);
+    }
+}
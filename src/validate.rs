@@ -97,3 +97,53 @@ pub fn validate_sqlite_identifier(name: &str) -> Result<(), DatabaseError> {
 
     Ok(())
 }
+
+/// Validate a PostgreSQL role name and return it as a quoted identifier
+///
+/// Role names are interpolated into `SET ROLE`, which does not accept bind
+/// parameters, so the name is emitted as a delimited identifier: wrapped in
+/// double quotes with embedded quotes doubled. Any characters are allowed and
+/// case is preserved, as for `CREATE ROLE "..."`. Empty names, NUL bytes, names
+/// over 63 bytes (PostgreSQL's `NAMEDATALEN` limit) and reserved role names
+/// (`none`, `public`, `session_user`, ...) are rejected.
+///
+/// ## Example
+///
+/// ```rust
+/// use kodegen_tools_database::validate::validate_postgres_role;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// assert_eq!(validate_postgres_role("report_reader")?, "\"report_reader\"");
+/// assert_eq!(validate_postgres_role("Team \"A\"")?, "\"Team \"\"A\"\"\"");
+///
+/// # assert_eq!(validate_postgres_role("reader; RESET ROLE")?, "\"reader; RESET ROLE\"");
+/// # assert!(validate_postgres_role("NONE").is_err());
+/// # assert!(validate_postgres_role("").is_err());
+/// # assert!(validate_postgres_role("a\0b").is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn validate_postgres_role(name: &str) -> Result<String, DatabaseError> {
+    if name.is_empty() || name.len() > 63 {
+        return Err(DatabaseError::QueryError(format!(
+            "Invalid role name: '{}'. Must be 1-63 bytes",
+            name
+        )));
+    }
+
+    if name.contains('\0') {
+        return Err(DatabaseError::QueryError(
+            "Invalid role name: contains a NUL byte".to_string(),
+        ));
+    }
+
+    let reserved = ["NONE", "PUBLIC", "CURRENT_ROLE", "CURRENT_USER", "SESSION_USER"];
+    if reserved.contains(&name.to_uppercase().as_str()) {
+        return Err(DatabaseError::QueryError(format!(
+            "Role name is reserved: '{}'",
+            name
+        )));
+    }
+
+    Ok(format!("\"{}\"", name.replace('"', "\"\"")))
+}